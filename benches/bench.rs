@@ -34,18 +34,7 @@ impl Iterator for PCG32 {
 }
 
 fn random_graph<G: InsertGraph>(mut rng: PCG32) -> G {
-	let mut g = G::default();
-	let mut verts = Vec::new();
-	for _ in 0..100 {
-		verts.push(g.insert_vert());
-	}
-	for _ in 0..1000 {
-		g.insert_edge(
-			verts[rng.next().unwrap() as usize % verts.len()],
-			verts[rng.next().unwrap() as usize % verts.len()],
-		);
-	}
-	g
+	G::random_sparse(100, 1000, &mut || rng.next().unwrap() as u64)
 }
 
 fn random_edge_costs<G: Digraph>(g: &G, mut rng: PCG32) -> G::EphemeralEdgeMap<'_, u32> {
@@ -90,6 +79,33 @@ where
 	}
 }
 
+fn dijkstra_all_sources_benchmark_routine<G: OutGraph, M: Map<G::Edge>>(
+	g: &G,
+	costs: &M,
+	zero: M::Value,
+) where
+	M::Value: std::ops::Add<Output = M::Value> + Clone + Debug + Ord,
+{
+	for source in g.verts() {
+		let distances = g.dijkstra(costs, source, zero.clone());
+		assert_eq!(*distances.get(source).borrow().as_ref().unwrap(), zero);
+	}
+}
+
+fn dijkstra_workspace_benchmark_routine<G: OutGraph, M: Map<G::Edge>>(
+	g: &G,
+	costs: &M,
+	zero: M::Value,
+) where
+	M::Value: std::ops::Add<Output = M::Value> + Clone + Debug + Ord,
+{
+	let mut dijkstra = Dijkstra::new(g);
+	for source in g.verts() {
+		let distances = dijkstra.run(costs, source, zero.clone());
+		assert_eq!(*distances.get(source).borrow().as_ref().unwrap(), zero);
+	}
+}
+
 fn depth_first_out_benchmark(c: &mut Criterion) {
 	let mut group = c.benchmark_group("depth_first");
 
@@ -142,5 +158,26 @@ fn dijkstra_out_benchmark(c: &mut Criterion) {
 	});
 }
 
-criterion_group!(benches, depth_first_out_benchmark, dijkstra_out_benchmark);
+fn dijkstra_all_sources_benchmark(c: &mut Criterion) {
+	let mut group = c.benchmark_group("dijkstra_all_sources");
+
+	group.bench_function("dijkstra", |b| {
+		let g = random_graph::<DenseOutAdjacencyList>(PCG32::new());
+		let costs = random_edge_costs(&g, PCG32::new());
+		b.iter(|| dijkstra_all_sources_benchmark_routine(black_box(&g), black_box(&costs), 0))
+	});
+
+	group.bench_function("Dijkstra::run", |b| {
+		let g = random_graph::<DenseOutAdjacencyList>(PCG32::new());
+		let costs = random_edge_costs(&g, PCG32::new());
+		b.iter(|| dijkstra_workspace_benchmark_routine(black_box(&g), black_box(&costs), 0))
+	});
+}
+
+criterion_group!(
+	benches,
+	depth_first_out_benchmark,
+	dijkstra_out_benchmark,
+	dijkstra_all_sources_benchmark
+);
 criterion_main!(benches);
@@ -17,6 +17,12 @@ pub trait Adjacencies<G: Digraph + ?Sized> {
 	/// The endpoint of the edge which it is not from per this definition of
 	/// adjacency.
 	fn to(g: &G, e: G::Edge) -> G::Vert;
+
+	/// Returns the vertex to which an adjacency of `_v` leads. This is `to(e)`
+	/// unless an edge may be an adjacency of either of its endpoints.
+	fn opposite(g: &G, _v: G::Vert, e: G::Edge) -> G::Vert {
+		Self::to(g, e)
+	}
 }
 
 /// Out-adjacencies.
@@ -56,3 +62,75 @@ impl<G: InGraph + ?Sized> Adjacencies<G> for InAdjacencies {
 		g.tail(e)
 	}
 }
+
+/// Out- and in-adjacencies together, so that edges are followed in either
+/// direction. Each edge is an adjacency of both of its endpoints, and a
+/// self-loop is an adjacency of its vertex twice.
+pub struct BothAdjacencies;
+
+impl<G: OutGraph + InGraph + ?Sized> Adjacencies<G> for BothAdjacencies {
+	type Of<'a> = std::iter::Chain<G::OutEdges<'a>, G::InEdges<'a>>;
+
+	fn of(g: &G, v: G::Vert) -> Self::Of<'_> {
+		g.out_edges(v).chain(g.in_edges(v))
+	}
+
+	fn from(g: &G, e: G::Edge) -> G::Vert {
+		g.tail(e)
+	}
+
+	fn to(g: &G, e: G::Edge) -> G::Vert {
+		g.head(e)
+	}
+
+	fn opposite(g: &G, v: G::Vert, e: G::Edge) -> G::Vert {
+		let (tail, head) = g.endpoints(e);
+		if tail == v {
+			head
+		} else {
+			tail
+		}
+	}
+}
+
+/// Iterator over the vertices to which the adjacencies of a vertex lead. A
+/// vertex is yielded once per adjacency, so parallel edges repeat it.
+pub struct Neighbors<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> {
+	graph: &'a G,
+	vert: G::Vert,
+	adjacencies: Adj::Of<'a>,
+}
+
+impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Neighbors<'a, G, Adj> {
+	/// Constructs an iterator over the neighbors of a vertex.
+	pub fn new(g: &'a G, v: G::Vert) -> Self {
+		Neighbors {
+			graph: g,
+			vert: v,
+			adjacencies: Adj::of(g, v),
+		}
+	}
+}
+
+impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Clone for Neighbors<'a, G, Adj> {
+	fn clone(&self) -> Self {
+		Neighbors {
+			graph: self.graph,
+			vert: self.vert,
+			adjacencies: self.adjacencies.clone(),
+		}
+	}
+}
+
+impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Iterator for Neighbors<'a, G, Adj> {
+	type Item = G::Vert;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let e = self.adjacencies.next()?;
+		Some(Adj::opposite(self.graph, self.vert, e))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.adjacencies.size_hint()
+	}
+}
@@ -0,0 +1,379 @@
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+use crate::{Digraph, Map, MapMut, OutGraph};
+
+/// Error indicating that a graph contains a directed cycle with negative total
+/// cost.
+pub struct NegativeCycle<G: Digraph + ?Sized> {
+	vert: G::Vert,
+}
+
+impl<G: Digraph + ?Sized> NegativeCycle<G> {
+	pub(crate) fn new(vert: G::Vert) -> Self {
+		NegativeCycle { vert }
+	}
+
+	/// Returns a vertex which lies on or is reachable from a negative cycle.
+	pub fn vert(&self) -> G::Vert {
+		self.vert
+	}
+}
+
+impl_vert_error!(NegativeCycle);
+
+/// Computes shortest distances from a set of sources with given initial
+/// distances using the queue-based variant of the Bellman-Ford algorithm, in
+/// which only vertices whose distances improved are relaxed again.
+#[allow(clippy::type_complexity)]
+pub(crate) fn spfa<'a, G: OutGraph + ?Sized, C: Clone, D>(
+	g: &'a G,
+	costs: &impl Map<G::Edge, Value = C>,
+	sources: impl IntoIterator<Item = (G::Vert, D)>,
+) -> Result<G::EphemeralVertMap<'a, Option<D>>, NegativeCycle<G>>
+where
+	D: Clone + Ord + Add<C, Output = D>,
+{
+	let order = g.verts().count();
+	let mut distances = g.ephemeral_vert_map(None);
+	// Number of edges in the path realizing each distance.
+	let mut lengths = g.ephemeral_vert_map(0usize);
+	let mut queued = g.ephemeral_vert_map(false);
+	let mut queue = VecDeque::new();
+	for (source, d) in sources {
+		*distances.get_mut(source) = Some(d);
+		*queued.get_mut(source) = true;
+		queue.push_back(source);
+	}
+	while let Some(v) = queue.pop_front() {
+		*queued.get_mut(v) = false;
+		let d = distances
+			.get(v)
+			.borrow()
+			.clone()
+			.expect("queued vertex has distance");
+		let length = *lengths.get(v).borrow() + 1;
+		for e in g.out_edges(v) {
+			let u = g.head(e);
+			let d_u = d.clone() + costs.get(e).borrow().clone();
+			if matches!(distances.get(u).borrow(), Some(d) if *d <= d_u) {
+				continue;
+			}
+			// A path with `order` edges repeats a vertex, and the cycle between must
+			// be negative for the path to improve on its shortcut.
+			if length >= order {
+				return Err(NegativeCycle::new(u));
+			}
+			*distances.get_mut(u) = Some(d_u);
+			*lengths.get_mut(u) = length;
+			if !*queued.get(u).borrow() {
+				*queued.get_mut(u) = true;
+				queue.push_back(u);
+			}
+		}
+	}
+	Ok(distances)
+}
+
+/// Computes the shortest distances between all pairs of vertices using
+/// Johnson's algorithm. Potentials from Bellman-Ford make every cost
+/// nonnegative, so Dijkstra's algorithm can then be run from each vertex.
+#[allow(clippy::type_complexity)]
+pub(crate) fn johnson<'a, G: OutGraph + ?Sized, C: Clone, D>(
+	g: &'a G,
+	costs: &impl Map<G::Edge, Value = C>,
+	zero: D,
+) -> Result<Vec<(G::Vert, G::EphemeralVertMap<'a, Option<D>>)>, NegativeCycle<G>>
+where
+	D: Clone + Ord + Add<C, Output = D> + Add<Output = D> + Sub<Output = D>,
+{
+	// Distances from a virtual source with a free edge to every vertex.
+	let potentials = spfa(g, costs, g.verts().map(|v| (v, zero.clone())))?;
+	let potential = |v| {
+		potentials
+			.get(v)
+			.borrow()
+			.clone()
+			.expect("every vertex has a potential")
+	};
+	let reweighted = |e| {
+		let (tail, head) = g.endpoints(e);
+		potential(tail) + costs.get(e).borrow().clone() - potential(head)
+	};
+	Ok(
+		g.verts()
+			.map(|source| {
+				let mut distances = g.dijkstra(&reweighted, source, zero.clone());
+				for v in g.verts() {
+					let mut d = distances.get_mut(v);
+					if let Some(d) = &mut *d {
+						*d = d.clone() + potential(v) - potential(source);
+					}
+				}
+				(source, distances)
+			})
+			.collect(),
+	)
+}
+
+/// Computes the shortest distances from a source over paths with at most `k`
+/// edges, relaxing every edge once per round against the previous round's
+/// distances.
+pub(crate) fn shortest_path_bounded<'a, G: OutGraph + ?Sized, C: Clone, D>(
+	g: &'a G,
+	costs: &impl Map<G::Edge, Value = C>,
+	source: G::Vert,
+	k: usize,
+	zero: D,
+) -> G::EphemeralVertMap<'a, Option<D>>
+where
+	D: Clone + Ord + Add<C, Output = D>,
+{
+	let mut previous = g.ephemeral_vert_map(None);
+	let mut current = g.ephemeral_vert_map(None);
+	*previous.get_mut(source) = Some(zero);
+	for _ in 0..k {
+		for v in g.verts() {
+			*current.get_mut(v) = previous.get(v).borrow().clone();
+		}
+		let mut relaxed = false;
+		for v in g.verts() {
+			let d = match previous.get(v).borrow() {
+				Some(d) => d.clone(),
+				None => continue,
+			};
+			for e in g.out_edges(v) {
+				let u = g.head(e);
+				let d_u = d.clone() + costs.get(e).borrow().clone();
+				if !matches!(current.get(u).borrow(), Some(d) if *d <= d_u) {
+					*current.get_mut(u) = Some(d_u);
+					relaxed = true;
+				}
+			}
+		}
+		std::mem::swap(&mut previous, &mut current);
+		// Later rounds would only repeat this one.
+		if !relaxed {
+			break;
+		}
+	}
+	previous
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::model::test_graph::*;
+	use crate::{DenseOutAdjacencyList, Digraph, Map, MapMut, OutGraph};
+	use proptest::proptest;
+	use std::borrow::Borrow;
+
+	/// Returns distances from the source by relaxing every edge `order - 1`
+	/// times, or `None` if a negative cycle is reachable.
+	fn bellman_ford(
+		g: &DenseOutAdjacencyList,
+		costs: &impl Map<<DenseOutAdjacencyList as Digraph>::Edge, Value = i64>,
+		source: <DenseOutAdjacencyList as Digraph>::Vert,
+	) -> Option<Vec<Option<i64>>> {
+		let verts: Vec<_> = g.verts().collect();
+		let index = |v| verts.iter().position(|u| *u == v).unwrap();
+		let mut distances = vec![None; verts.len()];
+		distances[index(source)] = Some(0);
+		let relax = |distances: &mut Vec<Option<i64>>| {
+			let mut relaxed = false;
+			for e in g.edges() {
+				let (t, h) = (index(g.tail(e)), index(g.head(e)));
+				if let Some(d) = distances[t] {
+					let d = d + *costs.get(e).borrow();
+					if !matches!(distances[h], Some(d_h) if d_h <= d) {
+						distances[h] = Some(d);
+						relaxed = true;
+					}
+				}
+			}
+			relaxed
+		};
+		for _ in 1..verts.len() {
+			relax(&mut distances);
+		}
+		if relax(&mut distances) {
+			None
+		} else {
+			Some(distances)
+		}
+	}
+
+	/// Returns the distances between all pairs of vertices, indexed by their
+	/// position in `verts`, or `None` if there is a negative cycle.
+	fn floyd_warshall(
+		g: &DenseOutAdjacencyList,
+		costs: &impl Map<<DenseOutAdjacencyList as Digraph>::Edge, Value = i64>,
+	) -> Option<Vec<Vec<Option<i64>>>> {
+		let verts: Vec<_> = g.verts().collect();
+		let index = |v| verts.iter().position(|u| *u == v).unwrap();
+		let mut distances = vec![vec![None; verts.len()]; verts.len()];
+		for (i, row) in distances.iter_mut().enumerate() {
+			row[i] = Some(0);
+		}
+		for e in g.edges() {
+			let (t, h) = (index(g.tail(e)), index(g.head(e)));
+			let c = *costs.get(e).borrow();
+			if !matches!(distances[t][h], Some(d) if d <= c) {
+				distances[t][h] = Some(c);
+			}
+		}
+		for k in 0..verts.len() {
+			for i in 0..verts.len() {
+				for j in 0..verts.len() {
+					if let (Some(a), Some(b)) = (distances[i][k], distances[k][j]) {
+						// Clamp to avoid overflow around negative cycles.
+						let through = (a + b).max(-1 << 40);
+						if !matches!(distances[i][j], Some(d) if d <= through) {
+							distances[i][j] = Some(through);
+						}
+					}
+				}
+			}
+		}
+		if (0..verts.len()).any(|i| matches!(distances[i][i], Some(d) if d < 0)) {
+			None
+		} else {
+			Some(distances)
+		}
+	}
+
+	fn assert_johnson_matches_floyd_warshall(g: &DenseOutAdjacencyList, offset: i64) {
+		let mut costs = g.ephemeral_edge_map(0i64);
+		let mut c = 0;
+		for e in g.edges() {
+			c = (c + 43) % 101;
+			*costs.get_mut(e) = c - offset;
+		}
+		match (g.johnson(&costs, 0), floyd_warshall(g, &costs)) {
+			(Ok(results), Some(expected)) => {
+				let verts: Vec<_> = g.verts().collect();
+				assert_eq!(results.len(), verts.len());
+				for ((source, distances), (expected_source, expected)) in
+					results.iter().zip(verts.iter().zip(expected))
+				{
+					assert_eq!(source, expected_source);
+					let distances: Vec<_> = verts.iter().map(|v| *distances.get(*v)).collect();
+					assert_eq!(distances, expected);
+				}
+			}
+			(Err(_), None) => {}
+			(results, expected) => panic!(
+				"johnson {} but floyd-warshall {}",
+				if results.is_ok() {
+					"succeeded"
+				} else {
+					"failed"
+				},
+				if expected.is_some() {
+					"succeeded"
+				} else {
+					"failed"
+				}
+			),
+		}
+	}
+
+	fn assert_spfa_matches_bellman_ford(g: &DenseOutAdjacencyList, offset: i64) {
+		let mut costs = g.ephemeral_edge_map(0i64);
+		let mut c = 0;
+		for e in g.edges() {
+			c = (c + 43) % 101;
+			*costs.get_mut(e) = c - offset;
+		}
+		for source in g.verts().take(10) {
+			let expected = bellman_ford(g, &costs, source);
+			match g.spfa(&costs, source, 0) {
+				Ok(distances) => {
+					let distances: Vec<_> = g.verts().map(|v| *distances.get(v)).collect();
+					assert_eq!(Some(distances), expected);
+				}
+				Err(_) => assert_eq!(expected, None),
+			}
+		}
+	}
+
+	fn assert_shortest_path_bounded_matches_bellman_ford(g: &DenseOutAdjacencyList, offset: i64) {
+		let mut costs = g.ephemeral_edge_map(0i64);
+		let mut c = 0;
+		for e in g.edges() {
+			c = (c + 43) % 101;
+			*costs.get_mut(e) = c - offset;
+		}
+		let order = g.verts().count();
+		for source in g.verts().take(10) {
+			if let Some(expected) = bellman_ford(g, &costs, source) {
+				for k in [order - 1, order, 2 * order] {
+					let distances = g.shortest_path_bounded(&costs, source, k, 0);
+					let distances: Vec<_> = g.verts().map(|v| *distances.get(v)).collect();
+					assert_eq!(distances, expected);
+				}
+			}
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn shortest_path_bounded_nonnegative(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_shortest_path_bounded_matches_bellman_ford(&g, 0);
+		}
+
+		#[test]
+		fn shortest_path_bounded_mixed(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_shortest_path_bounded_matches_bellman_ford(&g, 10);
+		}
+
+		#[test]
+		fn johnson_nonnegative(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_johnson_matches_floyd_warshall(&g, 0);
+		}
+
+		#[test]
+		fn johnson_acyclic(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test.acyclic());
+			assert_johnson_matches_floyd_warshall(&g, 50);
+		}
+
+		#[test]
+		fn johnson_mixed(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_johnson_matches_floyd_warshall(&g, 10);
+		}
+
+		#[test]
+		fn spfa_nonnegative(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_spfa_matches_bellman_ford(&g, 0);
+		}
+
+		#[test]
+		fn spfa_mixed(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			assert_spfa_matches_bellman_ford(&g, 10);
+		}
+	}
+
+	#[test]
+	fn shortest_path_bounded_hops() {
+		use crate::InsertGraph;
+		let g = DenseOutAdjacencyList::from_edges(4, vec![(0, 1), (1, 2), (2, 3), (0, 3)]);
+		let verts: Vec<_> = g.verts().collect();
+		let direct = g.edges().nth(3).unwrap();
+		let costs = |e| if e == direct { 5 } else { 1 };
+		let distances = |k| {
+			let distances = g.shortest_path_bounded(&costs, verts[0], k, 0);
+			verts.iter().map(|v| *distances.get(*v)).collect::<Vec<_>>()
+		};
+		assert_eq!(distances(0), vec![Some(0), None, None, None]);
+		assert_eq!(distances(1), vec![Some(0), Some(1), None, Some(5)]);
+		assert_eq!(distances(2), vec![Some(0), Some(1), Some(2), Some(5)]);
+		assert_eq!(distances(3), vec![Some(0), Some(1), Some(2), Some(3)]);
+	}
+}
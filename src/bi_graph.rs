@@ -0,0 +1,1064 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::ops::Add;
+
+use crate::map;
+#[cfg(feature = "rayon")]
+use crate::ConcurrentUnionFind;
+use crate::{
+	biconnected, flow, strongly_connected, BinaryHeap, ExactInDegreeDigraph, ExactOutDegreeDigraph,
+	Homomorphism, InGraph, InsertGraph, Map, MapMut, OutGraph, Reversed, SparseBiAdjacencyList,
+	UnionFind,
+};
+
+/// Represents a directed graph in which both the out- and in-adjacencies of
+/// vertices can be iterated.
+pub trait BiGraph: OutGraph + InGraph {
+	/// Returns the strongly connected components of the graph using Kosaraju's
+	/// algorithm. Components are returned in topological order of the
+	/// condensation, that is, no edge leads from a component to an earlier one.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.strongly_connected_components_kosaraju(), vec![vec![u], vec![v]]);
+	/// ```
+	fn strongly_connected_components_kosaraju(&self) -> Vec<Vec<Self::Vert>> {
+		strongly_connected::kosaraju(self)
+	}
+
+	/// Returns whether every vertex is reachable from every other, which holds
+	/// for graphs with fewer than two vertices. Rather than computing the
+	/// strongly connected components, checks that every vertex is reachable
+	/// both from and to an arbitrary vertex.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert!(!g.is_strongly_connected());
+	/// g.insert_edge(v, u);
+	/// assert!(g.is_strongly_connected());
+	/// ```
+	fn is_strongly_connected(&self) -> bool {
+		let root = match self.verts().next() {
+			Some(root) => root,
+			None => return true,
+		};
+		let reachable = self.reachable_from(root);
+		if !self.verts().all(|v| *reachable.get(v).borrow()) {
+			return false;
+		}
+		let reversed = Reversed::new(self);
+		let reaching = reversed.reachable_from(root);
+		self.verts().all(|v| *reaching.get(v).borrow())
+	}
+
+	/// Returns the total cost of the shortest path from a source to a target, or
+	/// `None` if the target is unreachable, by alternately searching forward from
+	/// the source and backward from the target. Assumes `d + costs.get(e) >= d`
+	/// for every edge `e` and `d: D`, and that `zero` is an identity for adding
+	/// distances.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let shortcut = g.insert_edge(u, w);
+	/// let costs = |e| if e == shortcut { 3 } else { 1 };
+	/// assert_eq!(g.bidirectional_dijkstra(&costs, u, w, 0), Some(2));
+	/// assert_eq!(g.bidirectional_dijkstra(&costs, w, u, 0), None);
+	/// ```
+	fn bidirectional_dijkstra<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		target: Self::Vert,
+		zero: D,
+	) -> Option<D>
+	where
+		D: Clone + Ord + Add<C, Output = D> + Add<D, Output = D>,
+	{
+		if source == target {
+			return Some(zero);
+		}
+		let mut forward = BinaryHeap::new(self.ephemeral_vert_map(None));
+		let mut backward = BinaryHeap::new(self.ephemeral_vert_map(None));
+		// Settled distances from the source and to the target.
+		let mut forward_settled = self.ephemeral_vert_map(false);
+		let mut backward_settled = self.ephemeral_vert_map(false);
+		// Best known distances from the source and to the target.
+		let mut forward_distances = self.ephemeral_vert_map(None);
+		let mut backward_distances = self.ephemeral_vert_map(None);
+		// Cost of the best path found so far.
+		let mut best: Option<D> = None;
+
+		forward.try_decrease(source, zero.clone());
+		*forward_distances.get_mut(source) = Some(zero.clone());
+		backward.try_decrease(target, zero.clone());
+		*backward_distances.get_mut(target) = Some(zero);
+		let mut expand_forward = true;
+		while let (Some((_, forward_min)), Some((_, backward_min))) = (forward.peek(), backward.peek())
+		{
+			// No path through unsettled vertices can improve on the best found.
+			if let Some(best) = &best {
+				if forward_min.clone() + backward_min.clone() >= *best {
+					break;
+				}
+			}
+
+			if expand_forward {
+				let (v, d) = forward.pop().unwrap();
+				*forward_settled.get_mut(v) = true;
+				for e in self.out_edges(v) {
+					let u = self.head(e);
+					let d_u = d.clone() + costs.get(e).borrow().clone();
+					if let Some(b) = backward_distances.get(u).borrow() {
+						let candidate = d_u.clone() + b.clone();
+						best = Some(match best.take() {
+							Some(best) if best <= candidate => best,
+							_ => candidate,
+						});
+					}
+					if !*forward_settled.get(u).borrow() && forward.try_decrease(u, d_u.clone()) {
+						*forward_distances.get_mut(u) = Some(d_u);
+					}
+				}
+			} else {
+				let (v, d) = backward.pop().unwrap();
+				*backward_settled.get_mut(v) = true;
+				for e in self.in_edges(v) {
+					let u = self.tail(e);
+					let d_u = d.clone() + costs.get(e).borrow().clone();
+					if let Some(f) = forward_distances.get(u).borrow() {
+						let candidate = f.clone() + d_u.clone();
+						best = Some(match best.take() {
+							Some(best) if best <= candidate => best,
+							_ => candidate,
+						});
+					}
+					if !*backward_settled.get(u).borrow() && backward.try_decrease(u, d_u.clone()) {
+						*backward_distances.get_mut(u) = Some(d_u);
+					}
+				}
+			}
+			expand_forward = !expand_forward;
+		}
+		best
+	}
+
+	/// Returns a map from vertices to the total cost of the shortest path from
+	/// the given source, or `None` if the vertex is unreachable, where paths may
+	/// traverse edges in either direction. Assumes `d + costs.get(e) >= d` for
+	/// every edge `e` in the graph and `d: D`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(w, v);
+	/// let distances = g.dijkstra_undirected(&|_| 1, u, 0);
+	/// assert_eq!(*distances.get(w), Some(2));
+	/// ```
+	fn dijkstra_undirected<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		zero: D,
+	) -> Self::EphemeralVertMap<'_, Option<D>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		let mut queue = BinaryHeap::new(self.ephemeral_vert_map(None));
+		let mut distances = self.ephemeral_vert_map(None);
+		queue.try_decrease(source, zero);
+		while let Some((v, d)) = queue.pop() {
+			*distances.get_mut(v) = Some(d.clone());
+			let heads = self.out_edges(v).map(|e| (e, self.head(e)));
+			let tails = self.in_edges(v).map(|e| (e, self.tail(e)));
+			for (e, u) in heads.chain(tails) {
+				// Self-loops lead back to the settled vertex and so are skipped here.
+				if distances.get(u).borrow().is_none() {
+					queue.try_decrease(u, d.clone() + costs.get(e).borrow().clone());
+				}
+			}
+		}
+		distances
+	}
+
+	/// Returns the value of a minimum cut of the underlying undirected graph,
+	/// treating each edge's weight symmetrically, along with the vertices on one
+	/// side of it, using the Stoer-Wagner algorithm. Self-loops are ignored and
+	/// `C::default()` must be zero. If the graph has fewer than two vertices,
+	/// the cut is empty with value zero and the side contains every vertex.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// g.insert_edge(w, v);
+	/// g.insert_edge(v, w);
+	/// let (value, side) = g.global_min_cut(&|e| if e == a { 1 } else { 2 });
+	/// assert_eq!(value, 1);
+	/// assert!(side == vec![u] || side.len() == 2 && !side.contains(&u));
+	/// ```
+	fn global_min_cut<C>(&self, weights: &impl Map<Self::Edge, Value = C>) -> (C, Vec<Self::Vert>)
+	where
+		C: Copy + Ord + Default + Add<Output = C>,
+	{
+		flow::global_min_cut(self, weights)
+	}
+
+	/// Returns a map from vertices to their clustering coefficients. The
+	/// neighbors of a vertex are the other vertices adjacent to it in either
+	/// direction, and its coefficient is the fraction of ordered pairs of
+	/// distinct neighbors `(u, w)` for which there is an edge from `u` to `w`.
+	/// Vertices with fewer than two neighbors have coefficient zero.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(u, w);
+	/// assert_eq!(*g.clustering_coefficient().get(u), 0.0);
+	/// g.insert_edge(v, w);
+	/// assert_eq!(*g.clustering_coefficient().get(u), 0.5);
+	/// ```
+	fn clustering_coefficient(&self) -> Self::EphemeralVertMap<'_, f64> {
+		let mut coefficients = self.ephemeral_vert_map(0.0);
+		for v in self.verts() {
+			let mut neighbors = self.successors_unique(v);
+			neighbors.extend(self.in_edges(v).map(|e| self.tail(e)));
+			neighbors.remove(&v);
+			let k = neighbors.len();
+			if k < 2 {
+				continue;
+			}
+			let links: usize = neighbors
+				.iter()
+				.map(|u| {
+					let successors = self.successors_unique(u);
+					neighbors
+						.iter()
+						.filter(|w| *w != u && successors.contains(w))
+						.count()
+				})
+				.sum();
+			*coefficients.get_mut(v) = links as f64 / (k * (k - 1)) as f64;
+		}
+		coefficients
+	}
+
+	/// Returns the articulation points of the underlying undirected graph, that
+	/// is, the vertices whose removal would increase the number of weakly
+	/// connected components, in the order of `verts`. Uses the low-link method
+	/// on a depth-first traversal which may follow edges in either direction.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(w, v);
+	/// assert_eq!(g.articulation_points(), vec![v]);
+	/// g.insert_edge(u, w);
+	/// assert!(g.articulation_points().is_empty());
+	/// ```
+	fn articulation_points(&self) -> Vec<Self::Vert> {
+		biconnected::articulation_points(self)
+	}
+
+	/// Returns the bridges of the underlying undirected graph, that is, the edges
+	/// whose removal would increase the number of weakly connected components.
+	/// Parallel edges and self-loops are never bridges.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// g.insert_edge(w, v);
+	/// g.insert_edge(v, w);
+	/// assert_eq!(g.bridges(), vec![e]);
+	/// ```
+	fn bridges(&self) -> Vec<Self::Edge> {
+		biconnected::bridges(self)
+	}
+
+	/// Returns the weakly connected components of the graph, that is, the
+	/// components connected by paths which may traverse edges in either
+	/// direction.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(v, u);
+	/// assert_eq!(g.weakly_connected_components(), vec![vec![u, v], vec![w]]);
+	/// ```
+	fn weakly_connected_components(&self) -> Vec<Vec<Self::Vert>> {
+		let component_ids = self.component_ids();
+		let mut components: Vec<Vec<Self::Vert>> = Vec::new();
+		for v in self.verts() {
+			let id = *component_ids.get(v).borrow();
+			if id == components.len() {
+				components.push(Vec::new());
+			}
+			components[id].push(v);
+		}
+		components
+	}
+
+	/// Like `weakly_connected_components`, but merges the endpoints of edges in
+	/// parallel using a concurrent disjoint-set forest.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(v, u);
+	/// assert_eq!(g.par_weakly_connected_components(), vec![vec![u, v], vec![w]]);
+	/// ```
+	#[cfg(feature = "rayon")]
+	fn par_weakly_connected_components(&self) -> Vec<Vec<Self::Vert>>
+	where
+		Self: Sync,
+		Self::Vert: Send + Sync,
+	{
+		use rayon::prelude::*;
+		let verts: Vec<_> = self.verts().collect();
+		let indices: HashMap<_, _> = verts.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+		let sets = ConcurrentUnionFind::new(verts.len());
+		verts.par_iter().enumerate().for_each(|(i, v)| {
+			for e in self.out_edges(v) {
+				sets.union(i, indices[&self.head(e)]);
+			}
+		});
+		let mut root_ids = vec![None; verts.len()];
+		let mut components: Vec<Vec<Self::Vert>> = Vec::new();
+		for (i, v) in verts.into_iter().enumerate() {
+			let root = sets.find(i);
+			let id = *root_ids[root].get_or_insert_with(|| {
+				components.push(Vec::new());
+				components.len() - 1
+			});
+			components[id].push(v);
+		}
+		components
+	}
+
+	/// Returns a map from vertices to the index of their weakly connected
+	/// component, numbered from zero in order of the first vertex of each, as in
+	/// `weakly_connected_components`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(w, u);
+	/// let ids = g.component_ids();
+	/// assert_eq!((*ids.get(u), *ids.get(v), *ids.get(w)), (0, 1, 0));
+	/// ```
+	fn component_ids(&self) -> Self::EphemeralVertMap<'_, usize> {
+		let mut sets = UnionFind::new(self.ephemeral_vert_map(None), self.ephemeral_vert_map(0));
+		for e in self.edges() {
+			let (tail, head) = self.endpoints(e);
+			sets.union(tail, head);
+		}
+		let mut root_ids = self.ephemeral_vert_map(None);
+		let mut component_ids = self.default_ephemeral_vert_map();
+		let mut count = 0;
+		for v in self.verts() {
+			let root = sets.find(v);
+			let id = *root_ids.get(root).borrow();
+			let id = id.unwrap_or_else(|| {
+				*root_ids.get_mut(root) = Some(count);
+				count += 1;
+				count - 1
+			});
+			*component_ids.get_mut(v) = id;
+		}
+		component_ids
+	}
+
+	/// Returns the number of colors used and a map from vertices to colors in
+	/// `0..count` such that no edge joins two vertices of the same color, ignoring
+	/// self-loops and edge direction. Vertices are colored in order of
+	/// decreasing degree, each with the smallest color not used by an already
+	/// colored neighbor.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let (count, colors) = g.greedy_coloring();
+	/// assert_eq!(count, 2);
+	/// assert_eq!((*colors.get(u), *colors.get(v), *colors.get(w)), (1, 0, 1));
+	/// ```
+	fn greedy_coloring(&self) -> (usize, Self::EphemeralVertMap<'_, usize>) {
+		let mut order: Vec<_> = self
+			.verts()
+			.map(|v| (self.out_edges(v).count() + self.in_edges(v).count(), v))
+			.collect();
+		order.sort_by(|(a, _), (b, _)| b.cmp(a));
+		let mut colors = self.ephemeral_vert_map(None);
+		let mut count = 0;
+		for (degree, v) in order {
+			// A vertex has at most `degree` distinct neighboring colors.
+			let mut used = vec![false; degree + 1];
+			let heads = self.out_edges(v).map(|e| self.head(e));
+			let tails = self.in_edges(v).map(|e| self.tail(e));
+			for u in heads.chain(tails) {
+				if let Some(color) = *colors.get(u).borrow() {
+					if color <= degree {
+						used[color] = true;
+					}
+				}
+			}
+			let color = used.iter().position(|used| !used).expect("unused color");
+			*colors.get_mut(v) = Some(color);
+			count = count.max(color + 1);
+		}
+		let mut result = self.ephemeral_vert_map(0);
+		for v in self.verts() {
+			*result.get_mut(v) = colors.get(v).borrow().expect("vertex colored");
+		}
+		(count, result)
+	}
+
+	/// Returns a map assigning each vertex to one of two sides such that every
+	/// edge joins opposite sides, ignoring edge direction, or `None` if no such
+	/// assignment exists because the graph has an odd cycle. A self-loop is an odd
+	/// cycle.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(w, v);
+	/// let sides = g.is_bipartite().unwrap();
+	/// assert_eq!((*sides.get(u), *sides.get(v), *sides.get(w)), (false, true, false));
+	/// g.insert_edge(u, w);
+	/// assert!(g.is_bipartite().is_none());
+	/// ```
+	fn is_bipartite(&self) -> Option<Self::EphemeralVertMap<'_, bool>> {
+		let mut sides = self.ephemeral_vert_map(None);
+		let mut queue = VecDeque::new();
+		for root in self.verts() {
+			if sides.get(root).borrow().is_some() {
+				continue;
+			}
+			*sides.get_mut(root) = Some(false);
+			queue.push_back(root);
+			while let Some(v) = queue.pop_front() {
+				let side = sides.get(v).borrow().expect("queued vertex has side");
+				let heads = self.out_edges(v).map(|e| self.head(e));
+				let tails = self.in_edges(v).map(|e| self.tail(e));
+				for u in heads.chain(tails) {
+					match *sides.get(u).borrow() {
+						Some(u_side) if u_side == side => return None,
+						Some(_) => continue,
+						None => {}
+					}
+					*sides.get_mut(u) = Some(!side);
+					queue.push_back(u);
+				}
+			}
+		}
+		let mut result = self.ephemeral_vert_map(false);
+		for v in self.verts() {
+			*result.get_mut(v) = sides.get(v).borrow().expect("vertex assigned");
+		}
+		Some(result)
+	}
+
+	/// Returns the quotient of the graph by a partition of its vertices into
+	/// numbered blocks, along with the mapping onto it. The quotient has one
+	/// vertex for each block containing a vertex, so block numbers need not be
+	/// contiguous, and one edge for each edge of the graph joining the blocks of
+	/// its endpoints. Edges within a block become self-loops.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let e = g.insert_edge(v, w);
+	/// let (q, homomorphism) = g.quotient(&|x| if x == w { 1 } else { 0 });
+	/// assert_eq!((q.order(), q.size()), (2, 2));
+	/// assert_eq!(q.tail(homomorphism.map_edge(e)), homomorphism.map_vert(u));
+	/// ```
+	fn quotient(
+		&self,
+		partition: &impl Map<Self::Vert, Value = usize>,
+	) -> (
+		SparseBiAdjacencyList,
+		Homomorphism<'_, Self, SparseBiAdjacencyList>,
+	)
+	where
+		Self: Sized,
+	{
+		let mut quotient = SparseBiAdjacencyList::new();
+		let mut blocks = HashMap::new();
+		let mut vmap = self.ephemeral_vert_map(None);
+		for v in self.verts() {
+			let block = *blocks
+				.entry(*partition.get(v).borrow())
+				.or_insert_with(|| quotient.insert_vert());
+			*vmap.get_mut(v) = Some(block);
+		}
+		let mut emap = self.ephemeral_edge_map(None);
+		for e in self.edges() {
+			let (tail, head) = self.endpoints(e);
+			let tail = vmap.get(tail).borrow().expect("tail in verts");
+			let head = vmap.get(head).borrow().expect("head in verts");
+			*emap.get_mut(e) = Some(quotient.insert_edge(tail, head));
+		}
+		(
+			quotient,
+			Homomorphism::new(map::Unwrap::new(vmap), map::Unwrap::new(emap)),
+		)
+	}
+
+	/// Returns the total degree of a vertex, that is, the sum of its out-degree
+	/// and in-degree. A self-loop is counted twice, once as an out-adjacency and
+	/// once as an in-adjacency.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, v);
+	/// assert_eq!((g.degree(u), g.degree(v)), (1, 3));
+	/// ```
+	fn degree(&self, v: impl Borrow<Self::Vert>) -> usize
+	where
+		Self: ExactOutDegreeDigraph + ExactInDegreeDigraph,
+	{
+		let v = *v.borrow();
+		self.out_degree(v) + self.in_degree(v)
+	}
+
+	/// Returns an iterator over the isolated vertices, that is, those with no
+	/// out- or in-adjacencies. A vertex with a self-loop is not isolated.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.isolated_vertices().collect::<Vec<_>>(), vec![w]);
+	/// g.insert_edge(w, w);
+	/// assert_eq!(g.isolated_vertices().count(), 0);
+	/// ```
+	fn isolated_vertices(&self) -> IsolatedVertices<'_, Self> {
+		IsolatedVertices {
+			graph: self,
+			verts: self.verts(),
+		}
+	}
+
+	/// Returns the out-degrees and in-degrees of the vertices, each sorted in
+	/// nonincreasing order.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(u, w);
+	/// assert_eq!(g.degree_sequence(), (vec![2, 0, 0], vec![1, 1, 0]));
+	/// ```
+	fn degree_sequence(&self) -> (Vec<usize>, Vec<usize>)
+	where
+		Self: ExactOutDegreeDigraph + ExactInDegreeDigraph,
+	{
+		let mut out_degrees: Vec<_> = self.verts().map(|v| self.out_degree(v)).collect();
+		let mut in_degrees: Vec<_> = self.verts().map(|v| self.in_degree(v)).collect();
+		out_degrees.sort_unstable_by(|a, b| b.cmp(a));
+		in_degrees.sort_unstable_by(|a, b| b.cmp(a));
+		(out_degrees, in_degrees)
+	}
+
+	/// Returns maps from each out-degree and in-degree to the number of vertices
+	/// with that degree.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let (out_histogram, in_histogram) = g.degree_histogram();
+	/// assert_eq!((out_histogram[&0], out_histogram[&1]), (1, 1));
+	/// assert_eq!((in_histogram[&0], in_histogram[&1]), (1, 1));
+	/// ```
+	fn degree_histogram(&self) -> (HashMap<usize, usize>, HashMap<usize, usize>)
+	where
+		Self: ExactOutDegreeDigraph + ExactInDegreeDigraph,
+	{
+		let mut out_histogram = HashMap::new();
+		let mut in_histogram = HashMap::new();
+		for v in self.verts() {
+			*out_histogram.entry(self.out_degree(v)).or_insert(0) += 1;
+			*in_histogram.entry(self.in_degree(v)).or_insert(0) += 1;
+		}
+		(out_histogram, in_histogram)
+	}
+}
+
+impl<G: OutGraph + InGraph + ?Sized> BiGraph for G {}
+
+/// Iterator over the vertices of a graph with no out- or in-adjacencies.
+pub struct IsolatedVertices<'a, G: BiGraph + ?Sized> {
+	graph: &'a G,
+	verts: G::Verts<'a>,
+}
+
+impl<'a, G: BiGraph + ?Sized> Clone for IsolatedVertices<'a, G> {
+	fn clone(&self) -> Self {
+		IsolatedVertices {
+			graph: self.graph,
+			verts: self.verts.clone(),
+		}
+	}
+}
+
+impl<'a, G: BiGraph + ?Sized> Iterator for IsolatedVertices<'a, G> {
+	type Item = G::Vert;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let graph = self.graph;
+		self
+			.verts
+			.find(|v| graph.out_edges(v).next().is_none() && graph.in_edges(v).next().is_none())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, self.verts.size_hint().1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::{DenseBiAdjacencyList, Digraph, ExactOrderDigraph, ExactSizeDigraph, Filtered};
+	use proptest::proptest;
+	use std::collections::HashSet;
+
+	/// Returns the set of vertices connected to a source ignoring direction.
+	pub fn weakly_reachable<G: BiGraph>(g: &G, source: G::Vert) -> HashSet<G::Vert> {
+		let mut reached = HashSet::new();
+		reached.insert(source);
+		let mut stack = vec![source];
+		while let Some(v) = stack.pop() {
+			let heads = g.out_edges(v).map(|e| g.head(e));
+			let tails = g.in_edges(v).map(|e| g.tail(e));
+			for u in heads.chain(tails) {
+				if reached.insert(u) {
+					stack.push(u);
+				}
+			}
+		}
+		reached
+	}
+
+	proptest! {
+		#[test]
+		fn bidirectional_dijkstra(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			for source in g.verts().take(10) {
+				let distances = g.dijkstra(&costs, source, 0);
+				for target in g.verts() {
+					assert_eq!(
+						g.bidirectional_dijkstra(&costs, source, target, 0),
+						*distances.get(target)
+					);
+				}
+			}
+		}
+
+		#[test]
+		fn dijkstra_undirected(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			let distances: HashMap<_, _> = g
+				.verts()
+				.take(10)
+				.map(|source| (source, g.dijkstra_undirected(&costs, source, 0)))
+				.collect();
+			for (&source, from_source) in distances.iter() {
+				assert_eq!(*from_source.get(source), Some(0));
+				let reachable = weakly_reachable(&g, source);
+				for v in g.verts() {
+					let d = *from_source.get(v);
+					assert_eq!(d.is_some(), reachable.contains(&v));
+					if let Some(from_v) = distances.get(&v) {
+						assert_eq!(d, *from_v.get(source));
+					} else {
+						assert_eq!(d, *g.dijkstra_undirected(&costs, v, 0).get(source));
+					}
+				}
+				// No edge offers a shortcut in either direction.
+				for e in g.edges() {
+					let (t, h) = g.endpoints(e);
+					if let (Some(dt), Some(dh)) = (*from_source.get(t), *from_source.get(h)) {
+						assert!(dh <= dt + costs.get(e));
+						assert!(dt <= dh + costs.get(e));
+					}
+				}
+			}
+		}
+
+		#[test]
+		fn clustering_coefficient(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let coefficients = g.clustering_coefficient();
+			for v in g.verts() {
+				let coefficient = *coefficients.get(v);
+				assert!((0.0..=1.0).contains(&coefficient));
+				if weakly_reachable(&g, v).len() < 3 {
+					assert_eq!(coefficient, 0.0);
+				}
+			}
+		}
+
+		#[test]
+		fn articulation_points(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let articulation_points: HashSet<_> = g.articulation_points().into_iter().collect();
+			let components = g.weakly_connected_components().len();
+			for v in g.verts().take(10) {
+				let without = Filtered::new(&g, |u| u != v, |_| true);
+				let increased = without.weakly_connected_components().len() > components;
+				assert_eq!(articulation_points.contains(&v), increased);
+			}
+		}
+
+		#[test]
+		fn bridges(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let bridges: HashSet<_> = g.bridges().into_iter().collect();
+			let components = g.weakly_connected_components().len();
+			for e in g.edges().take(10) {
+				let without = Filtered::new(&g, |_| true, |d| d != e);
+				let increased = without.weakly_connected_components().len() > components;
+				assert_eq!(bridges.contains(&e), increased);
+			}
+		}
+
+		#[test]
+		fn bridges_tree(parents in proptest::collection::vec(0usize..1000, 0..40)) {
+			let edges = parents.iter().enumerate().map(|(i, p)| (p % (i + 1), i + 1));
+			let g = DenseBiAdjacencyList::from_edges(parents.len() + 1, edges);
+			assert_eq!(g.bridges().len(), g.edges().count());
+		}
+
+		#[test]
+		fn bridges_cycle(order in 1usize..20) {
+			let g = DenseBiAdjacencyList::from_edges(order, (0..order).map(|i| (i, (i + 1) % order)));
+			assert!(g.bridges().is_empty());
+		}
+
+		#[test]
+		fn is_strongly_connected(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let components = g.strongly_connected_components();
+			assert_eq!(g.is_strongly_connected(), components.len() <= 1);
+		}
+
+		#[test]
+		fn is_strongly_connected_cycles(order in 1usize..20) {
+			let edges = (0..order).map(|i| (i, (i + 1) % order));
+			assert!(DenseBiAdjacencyList::from_edges(order, edges).is_strongly_connected());
+		}
+
+		#[test]
+		fn weakly_connected_components(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let components = g.weakly_connected_components();
+			let mut component_ids = HashMap::new();
+			for (i, component) in components.iter().enumerate() {
+				assert!(!component.is_empty());
+				for v in component {
+					assert!(component_ids.insert(*v, i).is_none());
+				}
+			}
+			assert_eq!(component_ids.len(), g.verts().count());
+			for u in g.verts() {
+				let reachable = weakly_reachable(&g, u);
+				for v in g.verts() {
+					assert_eq!(reachable.contains(&v), component_ids[&u] == component_ids[&v]);
+				}
+			}
+		}
+
+		#[cfg(feature = "rayon")]
+		#[test]
+		fn par_weakly_connected_components(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			assert_eq!(g.par_weakly_connected_components(), g.weakly_connected_components());
+		}
+
+		#[test]
+		fn component_ids(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let ids = g.component_ids();
+			let components = g.weakly_connected_components();
+			for (i, component) in components.iter().enumerate() {
+				for v in component {
+					assert_eq!(*ids.get(*v), i);
+				}
+			}
+		}
+
+		#[test]
+		fn greedy_coloring(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let (count, colors) = g.greedy_coloring();
+			for v in g.verts() {
+				assert!(*colors.get(v) < count);
+			}
+			assert_eq!(count, g.verts().map(|v| *colors.get(v) + 1).max().unwrap_or(0));
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				if tail != head {
+					assert_ne!(*colors.get(tail), *colors.get(head));
+				}
+			}
+		}
+
+		#[test]
+		fn is_bipartite(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			match g.is_bipartite() {
+				Some(sides) => {
+					for e in g.edges() {
+						let (tail, head) = g.endpoints(e);
+						assert_ne!(*sides.get(tail), *sides.get(head));
+					}
+				}
+				None => {
+					// Some vertex reaches itself along an odd undirected walk.
+					let odd_walk = g.verts().any(|root| {
+						let mut reached = HashSet::new();
+						reached.insert((root, false));
+						let mut stack = vec![(root, false)];
+						while let Some((v, parity)) = stack.pop() {
+							let heads = g.out_edges(v).map(|e| g.head(e));
+							let tails = g.in_edges(v).map(|e| g.tail(e));
+							for u in heads.chain(tails) {
+								if reached.insert((u, !parity)) {
+									stack.push((u, !parity));
+								}
+							}
+						}
+						reached.contains(&(root, true))
+					});
+					assert!(odd_walk);
+				}
+			}
+		}
+
+		#[test]
+		fn is_bipartite_cycles(order in 1usize..20) {
+			let g = DenseBiAdjacencyList::from_edges(order, (0..order).map(|i| (i, (i + 1) % order)));
+			assert_eq!(g.is_bipartite().is_some(), order % 2 == 0);
+		}
+
+		#[test]
+		fn quotient_identity(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let indices: HashMap<_, _> = g.verts().enumerate().map(|(i, v)| (v, i)).collect();
+			let (q, homomorphism) = g.quotient(&|v| indices[&v]);
+			assert!(g.is_isomorphic_with_maps(&q, homomorphism.vert_map(), homomorphism.edge_map()));
+		}
+
+		#[test]
+		fn quotient_single_block(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let (q, homomorphism) = g.quotient(&|_| 0);
+			assert_eq!(q.order(), if g.order() > 0 { 1 } else { 0 });
+			assert_eq!(q.size(), g.size());
+			assert!(q.edges().all(|e| q.tail(e) == q.head(e)));
+			let edges: HashSet<_> = g.edges().map(|e| homomorphism.map_edge(e)).collect();
+			assert_eq!(edges.len(), q.size());
+		}
+
+		#[test]
+		fn quotient_sparse_blocks(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let indices: HashMap<_, _> = g.verts().enumerate().map(|(i, v)| (v, i)).collect();
+			let (q, homomorphism) = g.quotient(&|v| usize::MAX - indices[&v] % 3);
+			assert_eq!(q.order(), g.order().min(3));
+			assert_eq!(q.size(), g.size());
+			for v in g.verts() {
+				let same_block = |u| indices[&u] % 3 == indices[&v] % 3;
+				for u in g.verts() {
+					assert_eq!(homomorphism.map_vert(u) == homomorphism.map_vert(v), same_block(u));
+				}
+			}
+		}
+
+		#[test]
+		fn isolated_vertices(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let mut incident = HashSet::new();
+			for e in g.edges() {
+				incident.insert(g.tail(e));
+				incident.insert(g.head(e));
+			}
+			let isolated: Vec<_> = g.isolated_vertices().collect();
+			let expected: Vec<_> = g.verts().filter(|v| !incident.contains(v)).collect();
+			assert_eq!(isolated, expected);
+		}
+
+		#[test]
+		fn degree_sequence(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let (out_degrees, in_degrees) = g.degree_sequence();
+			assert_eq!(out_degrees.len(), g.order());
+			assert_eq!(in_degrees.len(), g.order());
+			assert_eq!(out_degrees.iter().sum::<usize>(), g.size());
+			assert_eq!(in_degrees.iter().sum::<usize>(), g.size());
+			assert!(out_degrees.windows(2).all(|pair| pair[0] >= pair[1]));
+			assert!(in_degrees.windows(2).all(|pair| pair[0] >= pair[1]));
+
+			assert_eq!(g.verts().map(|v| g.degree(v)).sum::<usize>(), 2 * g.size());
+			for v in g.verts() {
+				let loops = g.out_edges(v).filter(|e| g.head(e) == v).count();
+				assert_eq!(g.degree(v), g.out_degree(v) + g.in_degree(v));
+				assert!(g.degree(v) >= 2 * loops);
+			}
+
+			let (out_histogram, in_histogram) = g.degree_histogram();
+			for (histogram, degrees) in [(out_histogram, out_degrees), (in_histogram, in_degrees)] {
+				assert_eq!(histogram.values().sum::<usize>(), g.order());
+				assert_eq!(histogram.iter().map(|(degree, count)| degree * count).sum::<usize>(), g.size());
+				for (degree, count) in histogram {
+					assert_eq!(degrees.iter().filter(|d| **d == degree).count(), count);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn clustering_coefficient_triangle() {
+		let cycle = DenseBiAdjacencyList::from_edges(3, vec![(0, 1), (1, 2), (2, 0)]);
+		let coefficients = cycle.clustering_coefficient();
+		assert!(cycle.verts().all(|v| *coefficients.get(v) == 0.5));
+		let complete =
+			DenseBiAdjacencyList::from_edges(3, vec![(0, 1), (1, 0), (1, 2), (2, 1), (2, 0), (0, 2)]);
+		let coefficients = complete.clustering_coefficient();
+		assert!(complete.verts().all(|v| *coefficients.get(v) == 1.0));
+	}
+
+	#[test]
+	fn clustering_coefficient_star() {
+		let star = DenseBiAdjacencyList::from_edges(5, (1..5).map(|i| (0, i)));
+		let coefficients = star.clustering_coefficient();
+		assert!(star.verts().all(|v| *coefficients.get(v) == 0.0));
+		// Linking two leaves closes one of the twelve ordered pairs at the center.
+		let mut star = star;
+		let leaves: Vec<_> = star.verts().skip(1).collect();
+		star.insert_edge(leaves[0], leaves[1]);
+		let coefficients = star.clustering_coefficient();
+		let center = star.verts().next().unwrap();
+		assert_eq!(*coefficients.get(center), 1.0 / 12.0);
+		assert_eq!(*coefficients.get(leaves[0]), 0.5);
+		assert_eq!(*coefficients.get(leaves[2]), 0.0);
+	}
+
+	#[test]
+	fn articulation_points_barbell() {
+		// Two triangles joined through a middle vertex.
+		let g = DenseBiAdjacencyList::from_edges(
+			7,
+			vec![
+				(0, 1),
+				(1, 2),
+				(2, 0),
+				(2, 3),
+				(3, 4),
+				(4, 5),
+				(5, 6),
+				(6, 4),
+			],
+		);
+		let verts: Vec<_> = g.verts().collect();
+		assert_eq!(g.articulation_points(), vec![verts[2], verts[3], verts[4]]);
+	}
+}
@@ -0,0 +1,132 @@
+use std::borrow::Borrow;
+
+use crate::{Digraph, InGraph, Map, MapMut, OutGraph};
+
+/// Depth-first search of the undirected graph underlying a graph.
+pub(crate) struct LowLinks<'a, G: Digraph + ?Sized + 'a> {
+	/// Order in which each vertex was discovered.
+	pub discovered: G::EphemeralVertMap<'a, usize>,
+	/// Earliest discovery among the vertices reachable from each vertex's
+	/// subtree using at most one edge which is not in the tree.
+	pub low: G::EphemeralVertMap<'a, usize>,
+	/// Edge from each vertex's parent in the tree, or `None` for roots.
+	pub parent_edges: G::EphemeralVertMap<'a, Option<G::Edge>>,
+}
+
+/// Runs a depth-first search of the undirected graph underlying a graph,
+/// computing the low-link of each vertex. The edge to a vertex's parent is
+/// excluded only by identity, so parallel edges to the parent count as back
+/// edges.
+pub(crate) fn low_links<G: OutGraph + InGraph + ?Sized>(g: &G) -> LowLinks<'_, G> {
+	let mut discovered = g.ephemeral_vert_map(None);
+	let mut low = g.ephemeral_vert_map(0);
+	let mut parent_edges = g.ephemeral_vert_map(None);
+	let incident = |v| {
+		let heads = g.out_edges(v).map(move |e| (e, g.head(e)));
+		heads.chain(g.in_edges(v).map(move |e| (e, g.tail(e))))
+	};
+	let mut time = 0;
+	for root in g.verts() {
+		if discovered.get(root).borrow().is_some() {
+			continue;
+		}
+		*discovered.get_mut(root) = Some(time);
+		*low.get_mut(root) = time;
+		time += 1;
+		let mut stack = vec![(root, incident(root))];
+		while let Some((v, edges)) = stack.last_mut() {
+			let v = *v;
+			if let Some((e, u)) = edges.next() {
+				if *parent_edges.get(v).borrow() == Some(e) {
+					continue;
+				}
+				let u_discovered = *discovered.get(u).borrow();
+				match u_discovered {
+					Some(u_discovered) => {
+						let mut v_low = low.get_mut(v);
+						*v_low = (*v_low).min(u_discovered);
+					}
+					None => {
+						*discovered.get_mut(u) = Some(time);
+						*low.get_mut(u) = time;
+						*parent_edges.get_mut(u) = Some(e);
+						time += 1;
+						stack.push((u, incident(u)));
+					}
+				}
+				continue;
+			}
+			stack.pop();
+			if let Some((parent, _)) = stack.last() {
+				let v_low = *low.get(v).borrow();
+				let mut parent_low = low.get_mut(*parent);
+				*parent_low = (*parent_low).min(v_low);
+			}
+		}
+	}
+	let mut discovered_times = g.ephemeral_vert_map(0);
+	for v in g.verts() {
+		*discovered_times.get_mut(v) = discovered.get(v).borrow().expect("vertex discovered");
+	}
+	LowLinks {
+		discovered: discovered_times,
+		low,
+		parent_edges,
+	}
+}
+
+/// Returns the vertices whose removal would increase the number of weakly
+/// connected components, in the order of `verts`.
+pub(crate) fn articulation_points<G: OutGraph + InGraph + ?Sized>(g: &G) -> Vec<G::Vert> {
+	let LowLinks {
+		discovered,
+		low,
+		parent_edges,
+	} = low_links(g);
+	// A root separates its children if it has more than one, and any other
+	// vertex separates a child whose subtree cannot reach above it.
+	let mut children = g.ephemeral_vert_map(0usize);
+	let mut separating = g.ephemeral_vert_map(false);
+	for v in g.verts() {
+		if let Some(e) = *parent_edges.get(v).borrow() {
+			let (tail, head) = g.endpoints(e);
+			let parent = if tail == v { head } else { tail };
+			*children.get_mut(parent) += 1;
+			if *low.get(v).borrow() >= *discovered.get(parent).borrow() {
+				*separating.get_mut(parent) = true;
+			}
+		}
+	}
+	g.verts()
+		.filter(|v| match parent_edges.get(*v).borrow() {
+			None => *children.get(*v).borrow() >= 2,
+			Some(_) => *separating.get(*v).borrow(),
+		})
+		.collect()
+}
+
+/// Returns the edges whose removal would increase the number of weakly
+/// connected components, ordered by the position in `verts` of their endpoint
+/// farther from the root of the depth-first tree.
+pub(crate) fn bridges<G: OutGraph + InGraph + ?Sized>(g: &G) -> Vec<G::Edge> {
+	let LowLinks {
+		discovered,
+		low,
+		parent_edges,
+	} = low_links(g);
+	// A tree edge is a bridge if the child's subtree cannot reach its parent
+	// any other way. Since parallel edges count as back edges, they are never
+	// bridges.
+	g.verts()
+		.filter_map(|v| {
+			let e = (*parent_edges.get(v).borrow())?;
+			let (tail, head) = g.endpoints(e);
+			let parent = if tail == v { head } else { tail };
+			if *low.get(v).borrow() > *discovered.get(parent).borrow() {
+				Some(e)
+			} else {
+				None
+			}
+		})
+		.collect()
+}
@@ -3,16 +3,21 @@ use crate::MapMut;
 use std::borrow::Borrow;
 use std::marker::PhantomData;
 
-pub struct BinaryHeap<K, T, M> {
+/// Priority queue implemented as an implicit `D`-ary tree. The index of each
+/// key in the tree is tracked by a map so that values can be decreased.
+pub struct DaryHeap<const D: usize, K, T, M> {
 	heap: Vec<Option<(K, T)>>,
 	map: M,
 	_phantom_data: PhantomData<T>,
 }
 
-impl<K: Clone, T: Ord, M: MapMut<K, Value = Option<Index>>> BinaryHeap<K, T, M> {
-	/// Constructs a new binary heap.
+/// Priority queue implemented as an implicit binary tree.
+pub type BinaryHeap<K, T, M> = DaryHeap<2, K, T, M>;
+
+impl<const D: usize, K: Clone, T: Ord, M: MapMut<K, Value = Option<Index>>> DaryHeap<D, K, T, M> {
+	/// Constructs a new heap.
 	pub fn new(map: M) -> Self {
-		BinaryHeap {
+		DaryHeap {
 			heap: Vec::new(),
 			map,
 			_phantom_data: PhantomData,
@@ -27,7 +32,7 @@ impl<K: Clone, T: Ord, M: MapMut<K, Value = Option<Index>>> BinaryHeap<K, T, M>
 
 	fn bubble_up(&mut self, mut index: usize, item: (K, T)) {
 		while index > 0 {
-			let parent_index = (index - 1) >> 1;
+			let parent_index = (index - 1) / D;
 			let parent = &mut self.heap[parent_index];
 			if parent.as_ref().unwrap().1 <= item.1 {
 				break;
@@ -44,29 +49,19 @@ impl<K: Clone, T: Ord, M: MapMut<K, Value = Option<Index>>> BinaryHeap<K, T, M>
 	/// Sets `map[key]` to `Some((value, index))` and restores the heap property assuming the value was increased.
 	fn sink_down(&mut self, mut index: usize, item: (K, T)) {
 		loop {
-			let left_index = (index << 1) + 1;
-			let right_index = left_index + 1;
-
-			if right_index >= self.heap.len() {
-				if left_index < self.heap.len() {
-					let child_index = left_index;
-					let child = &mut self.heap[child_index];
-
-					if child.as_ref().unwrap().1 < item.1 {
-						let child_item = child.take().unwrap();
-						self.set_item(index, child_item);
-						index = child_index;
-					}
-				}
+			let first_child_index = index * D + 1;
+			if first_child_index >= self.heap.len() {
 				break;
 			}
+			let end_child_index = (first_child_index + D).min(self.heap.len());
 
-			let child_index =
-				if self.heap[left_index].as_ref().unwrap().1 < self.heap[right_index].as_ref().unwrap().1 {
-					left_index
-				} else {
-					right_index
-				};
+			let mut child_index = first_child_index;
+			for sibling_index in first_child_index + 1..end_child_index {
+				if self.heap[sibling_index].as_ref().unwrap().1 < self.heap[child_index].as_ref().unwrap().1
+				{
+					child_index = sibling_index;
+				}
+			}
 			let child = &mut self.heap[child_index];
 			if item.1 <= child.as_ref().unwrap().1 {
 				break;
@@ -97,6 +92,11 @@ impl<K: Clone, T: Ord, M: MapMut<K, Value = Option<Index>>> BinaryHeap<K, T, M>
 		true
 	}
 
+	/// Returns an item with the least value without removing it.
+	pub fn peek(&self) -> Option<&(K, T)> {
+		self.heap.first().map(|item| item.as_ref().unwrap())
+	}
+
 	/// Removes and returns an item with the least value.
 	pub fn pop(&mut self) -> Option<(K, T)> {
 		let last = self.heap.pop()?;
@@ -159,35 +159,55 @@ mod tests {
 		}
 	}
 
+	fn assert_try_decrease_and_pop<const D: usize>(items: Vec<(u8, u32)>) {
+		// Determine the expected order for popped items.
+		let mut minimums = HashMap::new();
+		for (k, v) in items.iter() {
+			minimums
+				.entry(*k)
+				.and_modify(|m: &mut u32| *m = (*m).min(*v))
+				.or_insert(*v);
+		}
+		let mut sorted = BTreeMap::<u32, HashSet<u8>>::new();
+		for (k, v) in minimums {
+			sorted.entry(v).or_insert(HashSet::new()).insert(k);
+		}
+
+		// Add all the items to a heap.
+		let mut heap = DaryHeap::<D, u8, u32, TestMap<_, _>>::new(TestMap::default());
+		for (k, v) in items {
+			heap.try_decrease(k, v);
+		}
+
+		// Pop them off, asserting they arrive in the right order.
+		while let Some((key, value)) = heap.peek().cloned() {
+			assert_eq!(heap.pop(), Some((key, value)));
+			while let Some(e) = sorted.first_entry() {
+				if !e.get().is_empty() {
+					break;
+				}
+				e.remove_entry();
+			}
+			let mut e = sorted.first_entry().unwrap();
+			assert_eq!(value, *e.key());
+			assert!(e.get_mut().remove(&key));
+		}
+	}
+
 	proptest! {
 		#[test]
 		fn try_decrease_and_pop(items: Vec<(u8, u32)>) {
-			// Determine the expected order for popped items.
-			let mut minimums = HashMap::new();
-			for (k, v) in items.iter() {
-				minimums.entry(*k).and_modify(|m: &mut u32| *m = (*m).min(*v)).or_insert(*v);
-			}
-			let mut sorted = BTreeMap::<u32, HashSet<u8>>::new();
-			for (k, v) in minimums {
-				sorted.entry(v).or_insert(HashSet::new()).insert(k);
-			}
+			assert_try_decrease_and_pop::<2>(items);
+		}
 
-			// Add all the items to a heap.
-			let mut heap = BinaryHeap::<u8, u32, TestMap<_, _>>::new(TestMap::default());
-			for (k, v) in items {
-				heap.try_decrease(k, v);
-			}
+		#[test]
+		fn try_decrease_and_pop_ternary(items: Vec<(u8, u32)>) {
+			assert_try_decrease_and_pop::<3>(items);
+		}
 
-			// Pop them off, asserting they arrive in the right order.
-			while let Some((key, value)) = heap.pop() {
-				while let Some(e) = sorted.first_entry() {
-					if !e.get().is_empty() { break; }
-					e.remove_entry();
-				}
-				let mut e = sorted.first_entry().unwrap();
-				assert_eq!(value, *e.key());
-				assert!(e.get_mut().remove(&key));
-			}
+		#[test]
+		fn try_decrease_and_pop_quaternary(items: Vec<(u8, u32)>) {
+			assert_try_decrease_and_pop::<4>(items);
 		}
 	}
 }
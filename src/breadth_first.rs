@@ -0,0 +1,162 @@
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+use crate::{Adjacencies, Digraph, Map, MapMut};
+
+/// Step of a breadth-first graph traversal.
+#[non_exhaustive]
+pub enum BreadthFirstEvent<G: Digraph + ?Sized> {
+	/// Discovered a vertex, either as the root of a new tree or as the far
+	/// endpoint of an earlier tree edge. Vertices are reported in breadth-first
+	/// order, immediately before their adjacencies are explored.
+	Discover(G::Vert),
+	/// Found an edge to an undiscovered vertex.
+	TreeEdge(G::Edge),
+	/// Found an edge to an already discovered vertex.
+	NonTreeEdge(G::Edge),
+}
+
+/// Iterator that performs a breadth-first graph traversal.
+pub struct BreadthFirst<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> {
+	graph: &'a G,
+	visited: G::EphemeralVertMap<'a, bool>,
+	queue: VecDeque<G::Vert>,
+	adjacencies: Option<(G::Vert, Adj::Of<'a>)>,
+	vert_iter: G::Verts<'a>,
+}
+
+impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> BreadthFirst<'a, G, Adj> {
+	/// Constructs a new breadth-first search over a graph.
+	pub fn new(g: &'a G) -> Self {
+		let (size_hint, _) = g.verts().size_hint();
+		BreadthFirst {
+			graph: g,
+			visited: g.default_ephemeral_vert_map(),
+			queue: VecDeque::with_capacity(size_hint),
+			adjacencies: None,
+			vert_iter: g.verts(),
+		}
+	}
+}
+
+impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Iterator for BreadthFirst<'a, G, Adj> {
+	type Item = BreadthFirstEvent<G>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use BreadthFirstEvent::*;
+		let visited = &mut self.visited;
+		let adjacency = self
+			.adjacencies
+			.as_mut()
+			.and_then(|(u, adjacencies)| Some((*u, adjacencies.next()?)));
+		if let Some((u, e)) = adjacency {
+			let v = Adj::opposite(self.graph, u, e);
+			if *visited.get(v).borrow() {
+				return Some(NonTreeEdge(e));
+			}
+			*visited.get_mut(v) = true;
+			self.queue.push_back(v);
+			return Some(TreeEdge(e));
+		}
+		let v = if let Some(v) = self.queue.pop_front() {
+			v
+		} else {
+			let v = self.vert_iter.find(|v| !*visited.get(*v).borrow())?;
+			*visited.get_mut(v) = true;
+			v
+		};
+		self.adjacencies = Some((v, Adj::of(self.graph, v)));
+		Some(Discover(v))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+	use std::collections::{HashMap, HashSet};
+
+	proptest! {
+		#[test]
+		fn breadth_first_out(g_test: TestGraph) {
+			use crate::OutGraph;
+			let g = crate::DenseOutAdjacencyList::from(&g_test);
+			use BreadthFirstEvent::*;
+			let mut vs = HashSet::new();
+			let mut es = HashSet::new();
+			// Depth of each discovered vertex within its tree.
+			let mut depths = HashMap::new();
+			let mut current = None;
+			for event in g.breadth_first_out() {
+				match event {
+					Discover(v) => {
+						assert!(vs.insert(v));
+						let depth = *depths.entry(v).or_insert(0);
+						// Vertices within a tree are discovered in order of depth.
+						if depth > 0 {
+							assert!(depths[&current.unwrap()] <= depth);
+						}
+						current = Some(v);
+					},
+					TreeEdge(e) => {
+						assert_eq!(g.tail(e), current.unwrap());
+						assert!(es.insert(e));
+						let depth = depths[&g.tail(e)] + 1;
+						assert!(depths.insert(g.head(e), depth).is_none());
+					},
+					NonTreeEdge(e) => {
+						assert_eq!(g.tail(e), current.unwrap());
+						assert!(es.insert(e));
+						assert!(depths.contains_key(&g.head(e)));
+					},
+				}
+			}
+			// Every vertex and edge should have been visited.
+			assert_eq!(g.verts().collect::<HashSet<_>>(), vs);
+			assert_eq!(g.edges().collect::<HashSet<_>>(), es);
+		}
+
+		#[test]
+		fn breadth_first_in(g_test: TestGraph) {
+			use crate::InGraph;
+			let g = crate::DenseInAdjacencyList::from(&g_test);
+			use BreadthFirstEvent::*;
+			let mut vs = HashSet::new();
+			let mut es = HashSet::new();
+			let mut current = None;
+			for event in g.breadth_first_in() {
+				match event {
+					Discover(v) => {
+						assert!(vs.insert(v));
+						current = Some(v);
+					},
+					TreeEdge(e) => {
+						assert_eq!(g.head(e), current.unwrap());
+						assert!(es.insert(e));
+						assert!(!vs.contains(&g.tail(e)));
+					},
+					NonTreeEdge(e) => {
+						assert_eq!(g.head(e), current.unwrap());
+						assert!(es.insert(e));
+					},
+				}
+			}
+			// Every vertex and edge should have been visited.
+			assert_eq!(g.verts().collect::<HashSet<_>>(), vs);
+			assert_eq!(g.edges().collect::<HashSet<_>>(), es);
+		}
+	}
+
+	#[test]
+	fn self_loop_is_non_tree_edge() {
+		use crate::{InsertGraph, OutGraph};
+		let mut g = crate::DenseOutAdjacencyList::new();
+		let v = g.insert_vert();
+		let e = g.insert_edge(v, v);
+		let events: Vec<_> = g.breadth_first_out().collect();
+		assert!(
+			matches!(events[..], [BreadthFirstEvent::Discover(u), BreadthFirstEvent::NonTreeEdge(d)] if u == v && d == e)
+		);
+	}
+}
@@ -0,0 +1,280 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Add;
+
+use crate::{BinaryHeap, ExactOutDegreeDigraph, Map, MapMut, OutGraph};
+
+/// Accumulates the dependencies of a source on the vertices in `order`, which
+/// holds the vertices reachable from the source in nondecreasing distance, into
+/// the centralities.
+fn accumulate<G: OutGraph + ?Sized>(
+	g: &G,
+	source: G::Vert,
+	mut order: Vec<G::Vert>,
+	preds: &impl Map<G::Vert, Value = Vec<G::Vert>>,
+	counts: &impl Map<G::Vert, Value = f64>,
+	centralities: &mut impl MapMut<G::Vert, Value = f64>,
+) {
+	let mut dependencies = g.ephemeral_vert_map(0.0);
+	while let Some(w) = order.pop() {
+		let dependency = *dependencies.get(w).borrow();
+		let count = *counts.get(w).borrow();
+		for v in preds.get(w).borrow() {
+			*dependencies.get_mut(*v) += *counts.get(*v).borrow() / count * (1.0 + dependency);
+		}
+		if w != source {
+			*centralities.get_mut(w) += dependency;
+		}
+	}
+}
+
+/// Computes betweenness centrality using Brandes' algorithm with a
+/// breadth-first search from each source.
+pub(crate) fn betweenness<G: OutGraph + ?Sized>(g: &G) -> G::EphemeralVertMap<'_, f64> {
+	let mut centralities = g.ephemeral_vert_map(0.0);
+	for source in g.verts() {
+		let mut distances = g.ephemeral_vert_map(None);
+		let mut counts = g.ephemeral_vert_map(0.0);
+		let mut preds = g.ephemeral_vert_map(Vec::new());
+		let mut order = Vec::new();
+		let mut queue = VecDeque::new();
+		*distances.get_mut(source) = Some(0usize);
+		*counts.get_mut(source) = 1.0;
+		queue.push_back(source);
+		while let Some(v) = queue.pop_front() {
+			order.push(v);
+			let d = distances
+				.get(v)
+				.borrow()
+				.expect("queued vertex has distance");
+			let count = *counts.get(v).borrow();
+			for w in g.successors(v) {
+				let d_w = *distances.get(w).borrow();
+				if d_w.is_none() {
+					*distances.get_mut(w) = Some(d + 1);
+					queue.push_back(w);
+				}
+				if d_w.unwrap_or(d + 1) == d + 1 {
+					*counts.get_mut(w) += count;
+					preds.get_mut(w).push(v);
+				}
+			}
+		}
+		accumulate(g, source, order, &preds, &counts, &mut centralities);
+	}
+	centralities
+}
+
+/// Computes betweenness centrality using Brandes' algorithm with Dijkstra's
+/// algorithm from each source.
+pub(crate) fn betweenness_weighted<'a, G: OutGraph + ?Sized, C: Clone, D>(
+	g: &'a G,
+	costs: &impl Map<G::Edge, Value = C>,
+	zero: D,
+) -> G::EphemeralVertMap<'a, f64>
+where
+	D: Clone + Ord + Add<C, Output = D>,
+{
+	let mut centralities = g.ephemeral_vert_map(0.0);
+	for source in g.verts() {
+		let mut queue = BinaryHeap::new(g.ephemeral_vert_map(None));
+		let mut distances = g.ephemeral_vert_map(None);
+		let mut counts = g.ephemeral_vert_map(0.0);
+		let mut preds = g.ephemeral_vert_map(Vec::new());
+		let mut order = Vec::new();
+		*distances.get_mut(source) = Some(zero.clone());
+		*counts.get_mut(source) = 1.0;
+		queue.try_decrease(source, zero.clone());
+		while let Some((v, d)) = queue.pop() {
+			order.push(v);
+			let count = *counts.get(v).borrow();
+			for e in g.out_edges(v) {
+				let w = g.head(e);
+				let d_w = d.clone() + costs.get(e).borrow().clone();
+				let ordering = match distances.get(w).borrow() {
+					Some(d_prime) => d_w.cmp(d_prime),
+					None => Ordering::Less,
+				};
+				match ordering {
+					Ordering::Less => {
+						*distances.get_mut(w) = Some(d_w.clone());
+						*counts.get_mut(w) = count;
+						*preds.get_mut(w) = vec![v];
+						queue.try_decrease(w, d_w);
+					}
+					Ordering::Equal => {
+						*counts.get_mut(w) += count;
+						preds.get_mut(w).push(v);
+					}
+					Ordering::Greater => {}
+				}
+			}
+		}
+		accumulate(g, source, order, &preds, &counts, &mut centralities);
+	}
+	centralities
+}
+
+/// Performs one iteration of PageRank, returning the new ranks and the L1
+/// distance from the old ones.
+fn pagerank_step<'a, G: ExactOutDegreeDigraph + ?Sized>(
+	g: &'a G,
+	ranks: &G::EphemeralVertMap<'a, f64>,
+	damping: f64,
+	order: usize,
+) -> (G::EphemeralVertMap<'a, f64>, f64) {
+	let mut next = g.ephemeral_vert_map((1.0 - damping) / order as f64);
+	// Dangling vertices distribute their rank uniformly.
+	let mut dangling = 0.0;
+	for v in g.verts() {
+		let rank = *ranks.get(v).borrow();
+		let out_degree = g.out_degree(v);
+		if out_degree == 0 {
+			dangling += rank;
+			continue;
+		}
+		let share = damping * rank / out_degree as f64;
+		for e in g.out_edges(v) {
+			*next.get_mut(g.head(e)) += share;
+		}
+	}
+	let dangling_share = damping * dangling / order as f64;
+	let mut delta = 0.0;
+	for v in g.verts() {
+		let mut rank = next.get_mut(v);
+		*rank += dangling_share;
+		delta += (*rank - *ranks.get(v).borrow()).abs();
+	}
+	(next, delta)
+}
+
+/// Computes PageRank by iterating until `done` returns true given the number of
+/// completed iterations and the L1 distance between the last two iterates.
+pub(crate) fn pagerank<G: ExactOutDegreeDigraph + ?Sized>(
+	g: &G,
+	damping: f64,
+	mut done: impl FnMut(usize, f64) -> bool,
+) -> G::EphemeralVertMap<'_, f64> {
+	let order = g.verts().count();
+	let mut ranks = g.ephemeral_vert_map(1.0 / order as f64);
+	if order == 0 {
+		return ranks;
+	}
+	let mut iterations = 0;
+	let mut delta = f64::INFINITY;
+	while !done(iterations, delta) {
+		let (next, next_delta) = pagerank_step(g, &ranks, damping, order);
+		ranks = next;
+		delta = next_delta;
+		iterations += 1;
+	}
+	ranks
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::model::test_graph::*;
+	use crate::{
+		DenseOutAdjacencyList, Digraph, ExactOutDegreeDigraph, InsertGraph, Map, OutGraph,
+		SparseOutAdjacencyList,
+	};
+	use proptest::proptest;
+
+	#[test]
+	fn directed_path() {
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2)]);
+		let centralities = g.betweenness_centrality();
+		let centralities: Vec<_> = g.verts().map(|v| *centralities.get(v)).collect();
+		assert_eq!(centralities, vec![0.0, 1.0, 0.0]);
+	}
+
+	#[test]
+	fn symmetric_path() {
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+		let centralities = g.betweenness_centrality();
+		let centralities: Vec<_> = g.verts().map(|v| *centralities.get(v)).collect();
+		assert_eq!(centralities, vec![0.0, 2.0, 0.0]);
+	}
+
+	#[test]
+	fn diamond() {
+		// Two shortest paths from 0 to 3 share the dependency equally, but the
+		// weighted variant prefers the cheaper one.
+		let g = DenseOutAdjacencyList::from_edges(4, vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+		let centralities = g.betweenness_centrality();
+		let centralities: Vec<_> = g.verts().map(|v| *centralities.get(v)).collect();
+		assert_eq!(centralities, vec![0.0, 0.5, 0.5, 0.0]);
+		let expensive = g.edges().nth(3).unwrap();
+		let centralities =
+			g.betweenness_centrality_weighted(&|e| if e == expensive { 2 } else { 1 }, 0);
+		let centralities: Vec<_> = g.verts().map(|v| *centralities.get(v)).collect();
+		assert_eq!(centralities, vec![0.0, 1.0, 0.0, 0.0]);
+	}
+
+	#[test]
+	fn pagerank_ring() {
+		let mut g = SparseOutAdjacencyList::new();
+		let verts: Vec<_> = (0..5).map(|_| g.insert_vert()).collect();
+		for i in 0..verts.len() {
+			let (u, v) = (verts[i], verts[(i + 1) % verts.len()]);
+			g.insert_edge(u, v);
+			g.insert_edge(v, u);
+		}
+		let ranks = g.pagerank_until(0.85, 1e-12);
+		for v in g.verts() {
+			assert!((*ranks.get(v) - 0.2).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn pagerank_dangling() {
+		// The sink receives rank from the source and returns it to both.
+		let g = DenseOutAdjacencyList::from_edges(2, vec![(0, 1)]);
+		let ranks = g.pagerank_until(0.5, 1e-12);
+		let ranks: Vec<_> = g.verts().map(|v| *ranks.get(v)).collect();
+		assert!((ranks[0] - 0.4).abs() < 1e-9);
+		assert!((ranks[1] - 0.6).abs() < 1e-9);
+	}
+
+	#[test]
+	fn pagerank_iteration_limit() {
+		// Any result within the limit is still a distribution.
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (0, 2), (1, 2)]);
+		let ranks = g.pagerank_until_or_after(0.85, f64::MIN_POSITIVE, 100);
+		let sum: f64 = g.verts().map(|v| *ranks.get(v)).sum();
+		assert!((sum - 1.0).abs() < 1e-9);
+	}
+
+	#[test]
+	#[should_panic(expected = "epsilon must be positive")]
+	fn pagerank_nan_epsilon() {
+		let g = DenseOutAdjacencyList::from_edges(1, vec![(0, 0)]);
+		g.pagerank_until(0.85, f64::NAN);
+	}
+
+	proptest! {
+		#[test]
+		fn pagerank_sums_to_one(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			if g.verts().next().is_some() {
+				let ranks = g.pagerank(0.85, 20);
+				let sum: f64 = g.verts().map(|v| *ranks.get(v)).sum();
+				assert!((sum - 1.0).abs() < 1e-9);
+				let ranks = g.pagerank_until(0.85, 1e-6);
+				let sum: f64 = g.verts().map(|v| *ranks.get(v)).sum();
+				assert!((sum - 1.0).abs() < 1e-9);
+			}
+		}
+
+		#[test]
+		fn weighted_matches_unweighted(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let expected = g.betweenness_centrality();
+			let actual = g.betweenness_centrality_weighted(&|_| 1, 0);
+			for v in g.verts() {
+				assert!((actual.get(v) - expected.get(v)).abs() < 1e-9);
+			}
+		}
+	}
+}
@@ -32,25 +32,73 @@ impl Default for DepthFirstVisited {
 	}
 }
 
+// Vertex being explored, the edge through which it was opened, and its
+// remaining adjacencies.
+struct DepthFirstFrame<V, E, Of> {
+	vert: V,
+	edge: Option<E>,
+	adjacencies: Of,
+}
+
 /// Iterator that performs a depth-first graph traversal.
 pub struct DepthFirst<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> {
 	graph: &'a G,
 	visited: G::EphemeralVertMap<'a, DepthFirstVisited>,
-	stack: Vec<(Option<G::Edge>, Adj::Of<'a>)>,
-	vert_iter: G::Verts<'a>,
+	stack: Vec<DepthFirstFrame<G::Vert, G::Edge, Adj::Of<'a>>>,
+	roots: std::vec::IntoIter<G::Vert>,
+	vert_iter: Option<G::Verts<'a>>,
+	pruned: bool,
 }
 
 impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> DepthFirst<'a, G, Adj> {
 	/// Constructs a new depth-first search over a graph.
 	pub fn new(g: &'a G) -> Self {
+		let mut depth_first = Self::from_roots(g, None);
+		depth_first.vert_iter = Some(g.verts());
+		depth_first
+	}
+
+	/// Constructs a new depth-first search over the part of a graph reachable
+	/// from the given roots. Trees are started from the roots in order, skipping
+	/// those already visited, and never from any other vertex.
+	pub fn from_roots(g: &'a G, roots: impl IntoIterator<Item = G::Vert>) -> Self {
 		let (size_hint, _) = g.edges().size_hint();
 		DepthFirst {
 			graph: g,
 			visited: g.default_ephemeral_vert_map(),
 			stack: Vec::with_capacity(size_hint),
-			vert_iter: g.verts(),
+			roots: roots.into_iter().collect::<Vec<_>>().into_iter(),
+			vert_iter: None,
+			pruned: false,
 		}
 	}
+
+	/// Skips the adjacencies of the most recently opened vertex, that is, the
+	/// head of the last `OpenEdge` or the root of the last `StartTree`, which
+	/// must not yet have been closed. The next event closes it. Vertices
+	/// reachable only through it are not visited.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let mut dfs = DepthFirst::<_, OutAdjacencies>::from_roots(&g, vec![u]);
+	/// assert!(matches!(dfs.next(), Some(DepthFirstEvent::StartTree(_))));
+	/// assert!(matches!(dfs.next(), Some(DepthFirstEvent::OpenEdge(d)) if d == e));
+	/// dfs.prune();
+	/// assert!(matches!(dfs.next(), Some(DepthFirstEvent::CloseEdge(d)) if d == e));
+	/// assert!(matches!(dfs.next(), Some(DepthFirstEvent::EndTree)));
+	/// assert!(dfs.next().is_none());
+	/// ```
+	pub fn prune(&mut self) {
+		debug_assert!(!self.stack.is_empty(), "vertex to prune");
+		self.pruned = true;
+	}
 }
 
 impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Iterator for DepthFirst<'a, G, Adj> {
@@ -60,30 +108,49 @@ impl<'a, G: Digraph + ?Sized, Adj: Adjacencies<G>> Iterator for DepthFirst<'a, G
 		use DepthFirstEvent::*;
 		use DepthFirstVisited::*;
 		let visited = &mut self.visited;
+		let pruned = std::mem::replace(&mut self.pruned, false);
 		if let Some(frame) = self.stack.last_mut() {
-			if let Some(e) = frame.1.next() {
-				let v = Adj::from(self.graph, e);
+			let next = if pruned {
+				None
+			} else {
+				frame.adjacencies.next()
+			};
+			if let Some(e) = next {
+				let v = Adj::opposite(self.graph, frame.vert, e);
 				let v_visited = *visited.get(v).borrow();
 				match v_visited {
 					No => {
 						*visited.get_mut(v) = Open;
-						self.stack.push((Some(e), Adj::of(self.graph, v)));
+						self.stack.push(DepthFirstFrame {
+							vert: v,
+							edge: Some(e),
+							adjacencies: Adj::of(self.graph, v),
+						});
 						Some(OpenEdge(e))
 					}
 					Open => Some(BackEdge(e)),
 					Closed => Some(CrossEdge(e)),
 				}
-			} else if let (Some(e), _) = self.stack.pop().unwrap() {
-				let v = Adj::from(self.graph, e);
-				*visited.get_mut(v) = Closed;
-				Some(CloseEdge(e))
 			} else {
-				Some(EndTree)
+				let frame = self.stack.pop().unwrap();
+				*visited.get_mut(frame.vert) = Closed;
+				match frame.edge {
+					Some(e) => Some(CloseEdge(e)),
+					None => Some(EndTree),
+				}
 			}
 		} else {
-			let v = self.vert_iter.find(|v| *visited.get(*v).borrow() == No)?;
+			let mut unvisited = |v: &G::Vert| *visited.get(*v).borrow() == No;
+			let v = match self.roots.find(&mut unvisited) {
+				Some(v) => v,
+				None => self.vert_iter.as_mut()?.find(unvisited)?,
+			};
 			*visited.get_mut(v) = Open;
-			self.stack.push((None, Adj::of(self.graph, v)));
+			self.stack.push(DepthFirstFrame {
+				vert: v,
+				edge: None,
+				adjacencies: Adj::of(self.graph, v),
+			});
 			Some(StartTree(v))
 		}
 	}
@@ -115,6 +182,7 @@ mod tests {
 						stack.pop().unwrap();
 					}
 					OpenEdge(e) => {
+						assert_eq!(g.tail(e), *stack.last().unwrap());
 						assert!(es.insert(e));
 						let v = g.head(e);
 						assert!(vs.insert(v));
@@ -122,10 +190,13 @@ mod tests {
 					},
 					CrossEdge(e) => {
 						assert_eq!(g.tail(e), *stack.last().unwrap());
+						assert!(vs.contains(&g.head(e)));
+						assert!(!stack.contains(&g.head(e)));
 						assert!(es.insert(e));
 					},
 					BackEdge(e) => {
 						assert_eq!(g.tail(e), *stack.last().unwrap());
+						assert!(stack.contains(&g.head(e)));
 						assert!(es.insert(e));
 					},
 					CloseEdge(e) => {
@@ -158,6 +229,7 @@ mod tests {
 						stack.pop().unwrap();
 					}
 					OpenEdge(e) => {
+						assert_eq!(g.head(e), *stack.last().unwrap());
 						assert!(es.insert(e));
 						let v = g.tail(e);
 						assert!(vs.insert(v));
@@ -165,10 +237,13 @@ mod tests {
 					},
 					CrossEdge(e) => {
 						assert_eq!(g.head(e), *stack.last().unwrap());
+						assert!(vs.contains(&g.tail(e)));
+						assert!(!stack.contains(&g.tail(e)));
 						assert!(es.insert(e));
 					},
 					BackEdge(e) => {
 						assert_eq!(g.head(e), *stack.last().unwrap());
+						assert!(stack.contains(&g.tail(e)));
 						assert!(es.insert(e));
 					},
 					CloseEdge(e) => {
@@ -182,5 +257,132 @@ mod tests {
 			assert_eq!(g.edges().collect::<HashSet<_>>(), es);
 			assert_eq!(stack.len(), 0);
 		}
+
+		#[test]
+		fn depth_first_custom(g_test: TestGraph) {
+			use crate::{Adjacencies, InGraph};
+			// Follows edges against their direction, like `InAdjacencies`.
+			struct Reversing;
+			impl<G: InGraph> Adjacencies<G> for Reversing {
+				type Of<'a> = G::InEdges<'a>;
+				fn of(g: &G, v: G::Vert) -> Self::Of<'_> {
+					g.in_edges(v)
+				}
+				fn from(g: &G, e: G::Edge) -> G::Vert {
+					g.head(e)
+				}
+				fn to(g: &G, e: G::Edge) -> G::Vert {
+					g.tail(e)
+				}
+			}
+			let g = crate::DenseInAdjacencyList::from(&g_test);
+			let trace = |events: &mut dyn Iterator<Item = DepthFirstEvent<_>>| {
+				use DepthFirstEvent::*;
+				events
+					.map(|event| match event {
+						StartTree(v) => (0, Some(v), None),
+						EndTree => (1, None, None),
+						OpenEdge(e) => (2, None, Some(e)),
+						CrossEdge(e) => (3, None, Some(e)),
+						BackEdge(e) => (4, None, Some(e)),
+						CloseEdge(e) => (5, None, Some(e)),
+					})
+					.collect::<Vec<_>>()
+			};
+			assert_eq!(
+				trace(&mut g.depth_first::<Reversing>()),
+				trace(&mut g.depth_first_in())
+			);
+		}
+
+		#[test]
+		fn depth_first_from_roots(g_test: TestGraph, roots in proptest::collection::vec(0usize..8, 0..4)) {
+			use crate::strongly_connected::tests::reachable;
+			let g = crate::DenseOutAdjacencyList::from(&g_test);
+			let verts: Vec<_> = g.verts().collect();
+			let roots: Vec<_> = roots.into_iter().filter_map(|i| verts.get(i).copied()).collect();
+			use DepthFirstEvent::*;
+			let mut vs = HashSet::new();
+			for event in DepthFirst::<_, crate::OutAdjacencies>::from_roots(&g, roots.iter().copied()) {
+				match event {
+					StartTree(v) => {
+						assert!(roots.contains(&v));
+						assert!(vs.insert(v));
+					}
+					OpenEdge(e) => assert!(vs.insert(g.head(e))),
+					_ => {}
+				}
+			}
+			let expected: HashSet<_> = roots.iter().flat_map(|root| reachable(&g, *root)).collect();
+			assert_eq!(vs, expected);
+		}
+
+		#[test]
+		fn depth_first_both(g_test: TestGraph, root in 0usize..8) {
+			use crate::BiGraph;
+			let g = crate::DenseBiAdjacencyList::from(&g_test);
+			let root = match g.verts().nth(root) {
+				Some(root) => root,
+				None => return Ok(()),
+			};
+			use DepthFirstEvent::*;
+			let mut vs = HashSet::new();
+			for event in DepthFirst::<_, crate::BothAdjacencies>::from_roots(&g, Some(root)) {
+				match event {
+					StartTree(v) => assert!(vs.insert(v)),
+					OpenEdge(e) => {
+						let (tail, head) = g.endpoints(e);
+						// Edges are opened from either endpoint.
+						assert!(vs.contains(&tail) != vs.contains(&head));
+						vs.insert(tail);
+						vs.insert(head);
+					}
+					_ => {}
+				}
+			}
+			let ids = g.component_ids();
+			let expected: HashSet<_> = g.verts().filter(|v| ids.get(*v) == ids.get(root)).collect();
+			assert_eq!(vs, expected);
+		}
+
+		#[test]
+		fn depth_first_prune(g_test: TestGraph, root in 0usize..8, pruned in 0usize..8) {
+			use crate::OutGraph;
+			let g = crate::DenseOutAdjacencyList::from(&g_test);
+			let verts: Vec<_> = g.verts().collect();
+			let (root, pruned) = match (verts.get(root), verts.get(pruned)) {
+				(Some(root), Some(pruned)) => (*root, *pruned),
+				_ => return Ok(()),
+			};
+			use DepthFirstEvent::*;
+			let mut vs = HashSet::new();
+			let mut dfs = DepthFirst::<_, crate::OutAdjacencies>::from_roots(&g, Some(root));
+			while let Some(event) = dfs.next() {
+				let v = match event {
+					StartTree(v) => v,
+					OpenEdge(e) => g.head(e),
+					_ => continue,
+				};
+				assert!(vs.insert(v));
+				if v == pruned {
+					dfs.prune();
+				}
+			}
+			// Expect the vertices reachable without leaving the pruned vertex.
+			let mut expected = HashSet::new();
+			expected.insert(root);
+			let mut stack = vec![root];
+			while let Some(v) = stack.pop() {
+				if v == pruned {
+					continue;
+				}
+				for e in g.out_edges(v) {
+					if expected.insert(g.head(e)) {
+						stack.push(g.head(e));
+					}
+				}
+			}
+			assert_eq!(vs, expected);
+		}
 	}
 }
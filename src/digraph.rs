@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::hash::Hash;
 
 use super::map::{Map, MapMut};
+use crate::{Adjacencies, DepthFirst, Reversed};
 
 /// Represents a [directed graph](https://en.wikipedia.org/wiki/Directed_graph).
 pub trait Digraph {
@@ -25,6 +26,26 @@ pub trait Digraph {
 	/// ```
 	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert);
 
+	/// Returns the vertices at the tail and head of an edge, or `None` if the
+	/// edge is not in the graph, such as after it has been removed. By default,
+	/// this assumes every edge is in the graph. Models which cannot detect
+	/// removed keys may panic or return the endpoints of a different edge.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = SparseBiAdjacencyList::new();
+	/// # let tail = g.insert_vert();
+	/// # let head = g.insert_vert();
+	/// let e = g.insert_edge(tail, head);
+	/// assert_eq!(g.try_endpoints(e), Some((tail, head)));
+	/// g.remove_edge(e);
+	/// assert_eq!(g.try_endpoints(e), None);
+	/// ```
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		Some(self.endpoints(e))
+	}
+
 	/// Returns the vertex at the tail of an edge.
 	///
 	/// # Examples
@@ -118,6 +139,33 @@ pub trait Digraph {
 		self.vert_map(Default::default())
 	}
 
+	/// Constructs a new mutable mapping from vertices to values with each vertex
+	/// mapped to the result of a function, which is evaluated eagerly. Vertices
+	/// inserted later are mapped to `default`, which is required since a graph
+	/// without vertices gives no value to fall back on.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseEdgeList::new();
+	/// # let v = g.insert_vert();
+	/// # let u = g.insert_vert();
+	/// let m = g.vert_map_from_fn(false, |w| w == v);
+	/// assert_eq!(*m.get(v), true);
+	/// assert_eq!(*m.get(u), false);
+	/// ```
+	fn vert_map_from_fn<T: Clone>(
+		&self,
+		default: T,
+		mut f: impl FnMut(Self::Vert) -> T,
+	) -> Self::VertMap<T> {
+		let mut map = self.vert_map(default);
+		for v in self.verts() {
+			*map.get_mut(v) = f(v);
+		}
+		map
+	}
+
 	/// A mutable map from edges to values.
 	type EdgeMap<T: Clone>: MapMut<Self::Edge, Value = T>;
 
@@ -157,6 +205,32 @@ pub trait Digraph {
 		self.edge_map(Default::default())
 	}
 
+	/// Constructs a new mutable mapping from edges to values with each edge
+	/// mapped to the result of a function, which is evaluated eagerly. Edges
+	/// inserted later are mapped to `default`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseEdgeList::new();
+	/// # let v = g.insert_vert();
+	/// # let u = g.insert_vert();
+	/// # let e = g.insert_edge(v, u);
+	/// let m = g.edge_map_from_fn(false, |d| g.head(d) == u);
+	/// assert_eq!(*m.get(e), true);
+	/// ```
+	fn edge_map_from_fn<T: Clone>(
+		&self,
+		default: T,
+		mut f: impl FnMut(Self::Edge) -> T,
+	) -> Self::EdgeMap<T> {
+		let mut map = self.edge_map(default);
+		for e in self.edges() {
+			*map.get_mut(e) = f(e);
+		}
+		map
+	}
+
 	/// A mutable map from vertices to values that requires the graph remain
 	/// immutable for its lifetime.
 	type EphemeralVertMap<'a, T: Clone>: MapMut<Self::Vert, Value = T> = Self::VertMap<T>;
@@ -237,6 +311,92 @@ pub trait Digraph {
 		self.ephemeral_edge_map(Default::default())
 	}
 
+	/// Returns an iterator that performs a depth-first traversal following the
+	/// given adjacencies.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(v, u);
+	/// let roots = g.depth_first::<BothAdjacencies>().filter(|event| matches!(event, DepthFirstEvent::StartTree(_)));
+	/// assert_eq!(roots.count(), 1);
+	/// ```
+	fn depth_first<Adj: Adjacencies<Self>>(&self) -> DepthFirst<'_, Self, Adj> {
+		DepthFirst::new(self)
+	}
+
+	/// Returns a view of the graph with the direction of every edge reversed.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let tail = g.insert_vert();
+	/// # let head = g.insert_vert();
+	/// let e = g.insert_edge(tail, head);
+	/// assert_eq!(g.reversed().endpoints(e), (head, tail));
+	/// assert!(g.reversed().out_edges(head).any(|d| d == e));
+	/// ```
+	fn reversed(&self) -> Reversed<'_, Self> {
+		Reversed::new(self)
+	}
+
+	/// Returns whether a given graph with the same vertex and edge types has
+	/// exactly the same vertices, edges, and endpoints as this graph. Unlike
+	/// `is_isomorphic_with_maps`, keys are compared directly.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let build = || {
+	///     let mut g = DenseOutAdjacencyList::new();
+	///     let v = g.insert_vert();
+	///     g.insert_edge(v, v);
+	///     g
+	/// };
+	/// assert!(build().structurally_eq(&build()));
+	/// ```
+	fn structurally_eq<G: Digraph<Vert = Self::Vert, Edge = Self::Edge>>(&self, other: &G) -> bool {
+		let verts: std::collections::HashSet<_> = self.verts().collect();
+		if other.verts().count() != verts.len() || !other.verts().all(|v| verts.contains(&v)) {
+			return false;
+		}
+		let edges: std::collections::HashSet<_> = self.edges().collect();
+		if other.edges().count() != edges.len() || !other.edges().all(|e| edges.contains(&e)) {
+			return false;
+		}
+		self
+			.edges()
+			.all(|e| self.endpoints(e) == other.endpoints(e))
+	}
+
+	/// Returns the endpoints of each edge, in the order of `edges`, as indices of
+	/// vertices in the order of `verts`. Together with `InsertGraph::from_edges`,
+	/// this converts between graph representations.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (2, 1)]);
+	/// assert_eq!(g.to_edge_pairs(), vec![(0, 1), (2, 1)]);
+	/// ```
+	fn to_edge_pairs(&self) -> Vec<(usize, usize)> {
+		let mut indices = self.ephemeral_vert_map(0);
+		for (i, v) in self.verts().enumerate() {
+			*indices.get_mut(v) = i;
+		}
+		self
+			.edges()
+			.map(|e| {
+				let (tail, head) = self.endpoints(e);
+				(*indices.get(tail).borrow(), *indices.get(head).borrow())
+			})
+			.collect()
+	}
+
 	/// Returns whether a given graph is isomorphic to this graph with given vertex and edge mappings.
 	fn is_isomorphic_with_maps<G: Digraph>(
 		&self,
@@ -291,6 +451,9 @@ impl<'g, G: Digraph> Digraph for &'g G {
 	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
 		(**self).endpoints(e)
 	}
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		(**self).try_endpoints(e)
+	}
 	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
 		(**self).tail(e)
 	}
@@ -0,0 +1,101 @@
+use std::borrow::Borrow;
+use std::ops::Add;
+
+use crate::model::index::Index;
+use crate::{BinaryHeap, Map, MapMut, OutGraph};
+
+/// Reusable state for running Dijkstra's algorithm from many sources on the
+/// same graph. Running from a new source clears the results of the previous
+/// run rather than allocating new maps.
+///
+/// # Examples
+/// ```
+/// # use sif::*;
+/// # let mut g = DenseOutAdjacencyList::new();
+/// # let u = g.insert_vert();
+/// # let v = g.insert_vert();
+/// g.insert_edge(u, v);
+/// let mut dijkstra = Dijkstra::new(&g);
+/// assert_eq!(*dijkstra.run(&|_| 1, u, 0).get(v), Some(1));
+/// assert_eq!(*dijkstra.run(&|_| 1, v, 0).get(u), None);
+/// ```
+pub struct Dijkstra<'g, G: OutGraph + ?Sized, D: Clone> {
+	graph: &'g G,
+	queue: BinaryHeap<G::Vert, D, G::EphemeralVertMap<'g, Option<Index>>>,
+	distances: G::EphemeralVertMap<'g, Option<D>>,
+	// Vertices whose distances were set by the last run.
+	settled: Vec<G::Vert>,
+}
+
+impl<'g, G: OutGraph + ?Sized, D: Clone + Ord> Dijkstra<'g, G, D> {
+	/// Constructs the state for running Dijkstra's algorithm on a graph.
+	pub fn new(g: &'g G) -> Self {
+		Dijkstra {
+			graph: g,
+			queue: BinaryHeap::new(g.ephemeral_vert_map(None)),
+			distances: g.ephemeral_vert_map(None),
+			settled: Vec::new(),
+		}
+	}
+
+	/// Returns a map from target vertices to the total cost of the shortest path
+	/// from the given source, as `OutGraph::dijkstra` does.
+	pub fn run<C: Clone>(
+		&mut self,
+		costs: &impl Map<G::Edge, Value = C>,
+		source: G::Vert,
+		zero: D,
+	) -> &G::EphemeralVertMap<'g, Option<D>>
+	where
+		D: Add<C, Output = D>,
+	{
+		let g = self.graph;
+		for v in self.settled.drain(..) {
+			*self.distances.get_mut(v) = None;
+		}
+		// The queue is always left empty, so it needs no clearing.
+		self.queue.try_decrease(source, zero);
+		while let Some((v, d)) = self.queue.pop() {
+			*self.distances.get_mut(v) = Some(d.clone());
+			self.settled.push(v);
+			for e in g.out_edges(v) {
+				let u = g.head(e);
+				if self.distances.get(u).borrow().is_none() {
+					self
+						.queue
+						.try_decrease(u, d.clone() + costs.get(e).borrow().clone());
+				}
+			}
+		}
+		&self.distances
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::{DenseOutAdjacencyList, Digraph};
+	use proptest::proptest;
+
+	proptest! {
+		#[test]
+		fn matches_dijkstra(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			let mut dijkstra = Dijkstra::new(&g);
+			for source in g.verts() {
+				let expected = g.dijkstra(&costs, source, 0);
+				let actual = dijkstra.run(&costs, source, 0);
+				for v in g.verts() {
+					assert_eq!(actual.get(v), expected.get(v));
+				}
+			}
+		}
+	}
+}
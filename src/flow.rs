@@ -0,0 +1,346 @@
+//! Module implementing network flow algorithms.
+
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+use crate::{Map, MapMut, OutGraph};
+
+/// Residual network over dense vertex indices. Arc `2 * i` is the forward arc of
+/// the `i`th edge and arc `2 * i + 1` is its reverse.
+struct Residual<C> {
+	heads: Vec<usize>,
+	capacities: Vec<C>,
+	out_arcs: Vec<Vec<usize>>,
+}
+
+impl<C: Copy + Ord + Default + Add<Output = C> + Sub<Output = C>> Residual<C> {
+	/// Returns the distance of each vertex from the source in the residual
+	/// network, or `None` if the sink is unreachable.
+	fn levels(&self, source: usize, sink: usize) -> Option<Vec<Option<usize>>> {
+		let mut levels = vec![None; self.out_arcs.len()];
+		levels[source] = Some(0);
+		let mut queue = VecDeque::new();
+		queue.push_back(source);
+		while let Some(v) = queue.pop_front() {
+			let level = levels[v].expect("queued vertex has level");
+			for &arc in &self.out_arcs[v] {
+				let u = self.heads[arc];
+				if levels[u].is_none() && self.capacities[arc] > C::default() {
+					levels[u] = Some(level + 1);
+					queue.push_back(u);
+				}
+			}
+		}
+		levels[sink].map(|_| levels)
+	}
+
+	/// Pushes flow from the source to the sink along a path of arcs which each
+	/// advance a level, returning the amount pushed, or zero if there is no such
+	/// path. `next_arcs` holds the position of the first arc of each vertex which
+	/// may still admit flow.
+	fn push(
+		&mut self,
+		source: usize,
+		sink: usize,
+		levels: &[Option<usize>],
+		next_arcs: &mut [usize],
+	) -> C {
+		// Arcs of the path so far, each with the least capacity up to it. The path
+		// is kept explicitly rather than recursing, so that long paths do not
+		// overflow the stack.
+		let mut path: Vec<(usize, C)> = Vec::new();
+		let mut v = source;
+		while v != sink {
+			if next_arcs[v] == self.out_arcs[v].len() {
+				// No path continues from `v`, so retreat and skip the arc to it.
+				match path.pop() {
+					Some((arc, _)) => v = self.heads[arc ^ 1],
+					None => return C::default(),
+				}
+				next_arcs[v] += 1;
+				continue;
+			}
+			let arc = self.out_arcs[v][next_arcs[v]];
+			let u = self.heads[arc];
+			let capacity = self.capacities[arc];
+			if capacity > C::default() && levels[u] == levels[v].map(|level| level + 1) {
+				let limit = match path.last() {
+					Some(&(_, limit)) if limit < capacity => limit,
+					_ => capacity,
+				};
+				path.push((arc, limit));
+				v = u;
+			} else {
+				next_arcs[v] += 1;
+			}
+		}
+		let (_, pushed) = *path.last().expect("source is not the sink");
+		for (arc, _) in path {
+			self.capacities[arc] = self.capacities[arc] - pushed;
+			self.capacities[arc ^ 1] = self.capacities[arc ^ 1] + pushed;
+		}
+		pushed
+	}
+}
+
+/// Returns the value of a maximum flow from `source` to `sink` subject to the
+/// given edge capacities, along with a map from edges to the flow along them,
+/// using Dinic's algorithm. `C::default()` must be zero. If the source is the
+/// sink, the flow is zero.
+///
+/// # Examples
+/// ```
+/// # use sif::{flow::max_flow, *};
+/// # let mut g = DenseOutAdjacencyList::new();
+/// # let s = g.insert_vert();
+/// # let v = g.insert_vert();
+/// # let t = g.insert_vert();
+/// let a = g.insert_edge(s, v);
+/// let b = g.insert_edge(v, t);
+/// let c = g.insert_edge(s, t);
+/// let (value, flows) = max_flow(&g, &|e| if e == a { 2 } else { 3 }, s, t);
+/// assert_eq!(value, 5);
+/// assert_eq!((*flows.get(a), *flows.get(b), *flows.get(c)), (2, 2, 3));
+/// ```
+pub fn max_flow<'a, G: OutGraph + ?Sized, C>(
+	g: &'a G,
+	capacities: &impl Map<G::Edge, Value = C>,
+	source: G::Vert,
+	sink: G::Vert,
+) -> (C, G::EphemeralEdgeMap<'a, C>)
+where
+	C: Copy + Ord + Default + Add<Output = C> + Sub<Output = C>,
+{
+	let mut indices = g.ephemeral_vert_map(0);
+	let mut order = 0;
+	for v in g.verts() {
+		*indices.get_mut(v) = order;
+		order += 1;
+	}
+	let mut residual = Residual {
+		heads: Vec::new(),
+		capacities: Vec::new(),
+		out_arcs: vec![Vec::new(); order],
+	};
+	let mut edges = Vec::new();
+	for e in g.edges() {
+		let (tail, head) = g.endpoints(e);
+		let (tail, head) = (*indices.get(tail).borrow(), *indices.get(head).borrow());
+		let arc = residual.heads.len();
+		residual.heads.extend([head, tail]);
+		residual
+			.capacities
+			.extend([*capacities.get(e).borrow(), C::default()]);
+		residual.out_arcs[tail].push(arc);
+		residual.out_arcs[head].push(arc + 1);
+		edges.push(e);
+	}
+
+	let (source, sink) = (*indices.get(source).borrow(), *indices.get(sink).borrow());
+	let mut value = C::default();
+	if source != sink {
+		while let Some(levels) = residual.levels(source, sink) {
+			let mut next_arcs = vec![0; order];
+			loop {
+				let pushed = residual.push(source, sink, &levels, &mut next_arcs);
+				if pushed == C::default() {
+					break;
+				}
+				value = value + pushed;
+			}
+		}
+	}
+
+	let mut flows = g.ephemeral_edge_map(C::default());
+	for (i, e) in edges.into_iter().enumerate() {
+		// The reverse arc's capacity is exactly the flow along the edge.
+		*flows.get_mut(e) = residual.capacities[2 * i + 1];
+	}
+	(value, flows)
+}
+
+/// Returns the value of a minimum cut of the undirected graph underlying `g`
+/// along with the vertices on one side of it, using the Stoer-Wagner
+/// algorithm. Each edge contributes its weight between its endpoints
+/// regardless of direction, and self-loops are ignored. `C::default()` must be
+/// zero. If the graph has fewer than two vertices, the cut is empty with value
+/// zero and the side contains every vertex.
+pub(crate) fn global_min_cut<G: OutGraph + ?Sized, C>(
+	g: &G,
+	weights: &impl Map<G::Edge, Value = C>,
+) -> (C, Vec<G::Vert>)
+where
+	C: Copy + Ord + Default + Add<Output = C>,
+{
+	let mut indices = g.ephemeral_vert_map(0);
+	let verts: Vec<_> = g.verts().collect();
+	for (i, v) in verts.iter().enumerate() {
+		*indices.get_mut(*v) = i;
+	}
+	let order = verts.len();
+	if order < 2 {
+		return (C::default(), verts);
+	}
+	let mut adjacency = vec![vec![C::default(); order]; order];
+	for e in g.edges() {
+		let (tail, head) = g.endpoints(e);
+		let (tail, head) = (*indices.get(tail).borrow(), *indices.get(head).borrow());
+		if tail != head {
+			let weight = *weights.get(e).borrow();
+			adjacency[tail][head] = adjacency[tail][head] + weight;
+			adjacency[head][tail] = adjacency[head][tail] + weight;
+		}
+	}
+
+	// Each remaining vertex stands for the group of original vertices merged
+	// into it.
+	let mut groups: Vec<Vec<usize>> = (0..order).map(|i| vec![i]).collect();
+	let mut remaining: Vec<usize> = (0..order).collect();
+	let mut best: Option<(C, Vec<usize>)> = None;
+	while remaining.len() > 1 {
+		// Add vertices in maximum adjacency order, tracking the last two.
+		let mut added = vec![false; order];
+		let mut connectivity = vec![C::default(); order];
+		let (mut previous, mut last) = (remaining[0], remaining[0]);
+		for _ in 0..remaining.len() {
+			let next = *remaining
+				.iter()
+				.filter(|v| !added[**v])
+				.max_by_key(|v| connectivity[**v])
+				.expect("unadded vertex remains");
+			added[next] = true;
+			previous = last;
+			last = next;
+			for &u in &remaining {
+				if !added[u] {
+					connectivity[u] = connectivity[u] + adjacency[next][u];
+				}
+			}
+		}
+
+		// The cut of the phase separates the last vertex from the rest.
+		let cut = connectivity[last];
+		if !matches!(best, Some((value, _)) if value <= cut) {
+			best = Some((cut, groups[last].clone()));
+		}
+
+		let group = std::mem::take(&mut groups[last]);
+		groups[previous].extend(group);
+		remaining.retain(|v| *v != last);
+		for &u in &remaining {
+			if u != previous {
+				adjacency[previous][u] = adjacency[previous][u] + adjacency[last][u];
+				adjacency[u][previous] = adjacency[previous][u];
+			}
+		}
+	}
+
+	let (value, side) = best.expect("at least one phase ran");
+	(value, side.into_iter().map(|i| verts[i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{BiGraph, DenseBiAdjacencyList, DenseOutAdjacencyList, Digraph, InsertGraph};
+	use proptest::{collection::vec, proptest};
+	use std::collections::HashSet;
+
+	#[test]
+	fn long_path() {
+		// Deep enough to overflow the stack if augmenting paths were recursive.
+		let order = 1_000_000;
+		let g = DenseOutAdjacencyList::from_edges(order, (1..order).map(|i| (i - 1, i)));
+		let verts: Vec<_> = g.verts().collect();
+		let (value, _) = max_flow(&g, &|_| 1, verts[0], verts[order - 1]);
+		assert_eq!(value, 1);
+	}
+
+	proptest! {
+		#[test]
+		fn max_flow_is_min_cut(order in 2usize..8, edges in vec((0usize..8, 0usize..8, 0u32..10), 0..24)) {
+			let edges: Vec<_> = edges.into_iter().filter(|(tail, head, _)| *tail < order && *head < order).collect();
+			let g = DenseOutAdjacencyList::from_edges(order, edges.iter().map(|(tail, head, _)| (*tail, *head)));
+			let mut capacities = g.ephemeral_edge_map(0);
+			for (e, (_, _, capacity)) in g.edges().zip(&edges) {
+				*capacities.get_mut(e) = *capacity;
+			}
+			let verts: Vec<_> = g.verts().collect();
+			let (source, sink) = (verts[0], verts[order - 1]);
+			let (value, flows) = max_flow(&g, &capacities, source, sink);
+
+			// The flow is feasible and conserved at every non-terminal vertex.
+			let mut excess = g.ephemeral_vert_map(0i64);
+			for e in g.edges() {
+				let flow = *flows.get(e);
+				assert!(flow <= *capacities.get(e));
+				*excess.get_mut(g.tail(e)) -= flow as i64;
+				*excess.get_mut(g.head(e)) += flow as i64;
+			}
+			for v in g.verts() {
+				if v != source && v != sink {
+					assert_eq!(*excess.get(v), 0);
+				}
+			}
+			assert_eq!(*excess.get(sink), value as i64);
+
+			// The value equals the capacity of a minimum cut, found by brute force
+			// over the sets of vertices containing the source but not the sink.
+			let min_cut = (0..1u32 << (order - 2))
+				.map(|subset| {
+					let cut = |v: usize| v == 0 || (v < order - 1 && subset & (1 << (v - 1)) != 0);
+					edges
+						.iter()
+						.filter(|(tail, head, _)| cut(*tail) && !cut(*head))
+						.map(|(_, _, capacity)| capacity)
+						.sum::<u32>()
+				})
+				.min()
+				.unwrap();
+			assert_eq!(value, min_cut);
+		}
+
+		#[test]
+		fn global_min_cut_is_min_max_flow(order in 2usize..8, edges in vec((0usize..8, 0usize..8, 0u32..10), 0..24)) {
+			let edges: Vec<_> = edges.into_iter().filter(|(tail, head, _)| *tail < order && *head < order).collect();
+			let g = DenseBiAdjacencyList::from_edges(order, edges.iter().map(|(tail, head, _)| (*tail, *head)));
+			let mut weights = g.ephemeral_edge_map(0);
+			for (e, (_, _, weight)) in g.edges().zip(&edges) {
+				*weights.get_mut(e) = *weight;
+			}
+			let (value, side) = g.global_min_cut(&weights);
+
+			// The side is a proper, nonempty subset whose cut has the given value.
+			let side: HashSet<_> = side.into_iter().collect();
+			assert!(!side.is_empty() && side.len() < order);
+			let cut: u32 = g
+				.edges()
+				.filter(|e| side.contains(&g.tail(e)) != side.contains(&g.head(e)))
+				.map(|e| *weights.get(e))
+				.sum();
+			assert_eq!(value, cut);
+
+			// The value is the least maximum flow between any pair of vertices when
+			// each edge may carry flow in either direction.
+			let symmetric = DenseOutAdjacencyList::from_edges(
+				order,
+				edges.iter().flat_map(|(tail, head, _)| vec![(*tail, *head), (*head, *tail)]),
+			);
+			let mut capacities = symmetric.ephemeral_edge_map(0);
+			let symmetric_weights = edges.iter().flat_map(|(_, _, weight)| vec![*weight, *weight]);
+			for (e, weight) in symmetric.edges().zip(symmetric_weights) {
+				*capacities.get_mut(e) = weight;
+			}
+			let verts: Vec<_> = symmetric.verts().collect();
+			let min_max_flow = verts
+				.iter()
+				.flat_map(|s| verts.iter().map(move |t| (*s, *t)))
+				.filter(|(s, t)| s != t)
+				.map(|(s, t)| max_flow(&symmetric, &capacities, s, t).0)
+				.min()
+				.unwrap();
+			assert_eq!(value, min_max_flow);
+		}
+	}
+}
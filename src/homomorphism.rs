@@ -1,7 +1,8 @@
 use std::borrow::Borrow;
+use std::collections::{hash_map::Entry, HashMap};
 
-use super::map::{self, Map};
-use crate::Digraph;
+use super::map::{self, Map, MapMut};
+use crate::{Digraph, ExactOrderDigraph, ExactSizeDigraph, InsertGraph, SparseBiAdjacencyList};
 
 /// Represents a homomorphism between two graphs. A homomorphism is a mapping from vertices of one graph to vertices of the other and a mapping from edges to edges such that these mappings commute. That is, the head and tail a mapped edge are the mapped head and tail of the original edge.
 pub struct Homomorphism<'a, From: Digraph, To: Digraph> {
@@ -35,4 +36,138 @@ impl<'a, From: Digraph, To: Digraph> Homomorphism<'a, From, To> {
 	pub fn map_edge(&self, e: From::Edge) -> To::Edge {
 		*self.edge_map.get(e).borrow()
 	}
+
+	/// Returns a new graph with a vertex for each distinct image of a vertex of
+	/// `from` and an edge for each distinct image of an edge, so vertices and
+	/// edges with the same image are merged.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let (q, homomorphism) = g.quotient(&|_| 0);
+	/// let image = homomorphism.image(&g);
+	/// assert_eq!((image.order(), image.size()), (1, 1));
+	/// ```
+	pub fn image(&self, from: &From) -> SparseBiAdjacencyList {
+		let mut image = SparseBiAdjacencyList::new();
+		let mut verts = HashMap::new();
+		for v in from.verts() {
+			verts
+				.entry(self.map_vert(v))
+				.or_insert_with(|| image.insert_vert());
+		}
+		let mut edges = HashMap::new();
+		for e in from.edges() {
+			if let Entry::Vacant(entry) = edges.entry(self.map_edge(e)) {
+				let (tail, head) = from.endpoints(e);
+				let tail = verts[&self.map_vert(tail)];
+				let head = verts[&self.map_vert(head)];
+				entry.insert(image.insert_edge(tail, head));
+			}
+		}
+		image
+	}
+
+	/// Returns the inverse homomorphism if the vertex and edge mappings are
+	/// bijections from `from` onto `to`, which must be the graphs between which
+	/// this homomorphism maps.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// let (h, homomorphism) = DenseBiAdjacencyList::isomorphic_from(&g);
+	/// let inverse = homomorphism.invert(&g, &h).unwrap();
+	/// assert_eq!(inverse.map_edge(homomorphism.map_edge(e)), e);
+	/// ```
+	pub fn invert<'b>(&self, from: &From, to: &'b To) -> Option<Homomorphism<'b, To, From>>
+	where
+		From: ExactOrderDigraph + ExactSizeDigraph,
+		To: ExactOrderDigraph + ExactSizeDigraph,
+	{
+		// Injective mappings between sets of equal size are bijective.
+		if from.order() != to.order() || from.size() != to.size() {
+			return None;
+		}
+		let mut vert_map = to.ephemeral_vert_map(None);
+		for v in from.verts() {
+			let mut inverse = vert_map.get_mut(self.map_vert(v));
+			if inverse.is_some() {
+				return None;
+			}
+			*inverse = Some(v);
+		}
+		let mut edge_map = to.ephemeral_edge_map(None);
+		for e in from.edges() {
+			let mut inverse = edge_map.get_mut(self.map_edge(e));
+			if inverse.is_some() {
+				return None;
+			}
+			*inverse = Some(e);
+		}
+		Some(Homomorphism::new(
+			map::Unwrap::new(vert_map),
+			map::Unwrap::new(edge_map),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::model::test_graph::*;
+	use crate::{
+		BiGraph, DenseBiAdjacencyList, DenseOutAdjacencyList, Digraph, InsertGraph, OutGraph,
+	};
+	use proptest::proptest;
+	use std::collections::HashMap;
+
+	proptest! {
+		#[test]
+		fn invert_isomorphic_from(g: TestGraph) {
+			let (g_prime, homomorphism) = DenseOutAdjacencyList::isomorphic_from(&g);
+			let inverse = homomorphism.invert(&g, &g_prime).unwrap();
+			assert!(g_prime.is_isomorphic_with_maps(&g, inverse.vert_map(), inverse.edge_map()));
+			for v in g.verts() {
+				assert_eq!(inverse.map_vert(homomorphism.map_vert(v)), v);
+			}
+			for e in g.edges() {
+				assert_eq!(inverse.map_edge(homomorphism.map_edge(e)), e);
+			}
+			for v in g_prime.verts() {
+				assert_eq!(homomorphism.map_vert(inverse.map_vert(v)), v);
+			}
+			for e in g_prime.edges() {
+				assert_eq!(homomorphism.map_edge(inverse.map_edge(e)), e);
+			}
+		}
+
+		#[test]
+		fn image_isomorphic_from(g: TestGraph) {
+			let (g_prime, homomorphism) = DenseOutAdjacencyList::isomorphic_from(&g);
+			let image = homomorphism.image(&g);
+			assert!(g_prime.is_isomorphic(&image));
+		}
+
+		#[test]
+		fn image_quotient(g_test: TestGraph, blocks in 1usize..4) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let indices: HashMap<_, _> = g.verts().enumerate().map(|(i, v)| (v, i % blocks)).collect();
+			let (q, homomorphism) = g.quotient(&|v| indices[&v]);
+			assert!(q.is_isomorphic(&homomorphism.image(&g)));
+		}
+
+		#[test]
+		fn invert_quotient(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let (q, homomorphism) = g.quotient(&|_| 0);
+			assert_eq!(homomorphism.invert(&g, &q).is_some(), g.verts().count() <= 1);
+		}
+	}
 }
@@ -1,4 +1,4 @@
-use crate::{adjacencies::InAdjacencies, DepthFirst, Digraph};
+use crate::{adjacencies::InAdjacencies, BreadthFirst, DepthFirst, Digraph, Neighbors};
 use std::borrow::Borrow;
 
 /// Represents a directed graph in which the in-adjacencies of vertices can be
@@ -21,9 +21,31 @@ pub trait InGraph: Digraph {
 	/// ```
 	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_>;
 
+	/// Returns an iterator over the tails of the in-adjacencies of a vertex.
+	/// Vertices are not deduplicated, so parallel edges yield the same vertex
+	/// repeatedly and a self-loop yields `v` itself.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseInAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.predecessors(v).collect::<Vec<_>>(), vec![u]);
+	/// ```
+	fn predecessors(&self, v: impl Borrow<Self::Vert>) -> Neighbors<'_, Self, InAdjacencies> {
+		Neighbors::new(self, *v.borrow())
+	}
+
 	/// Returns an iterator that performs a depth-first traverals.
 	fn depth_first_in(&self) -> DepthFirst<'_, Self, InAdjacencies> {
-		DepthFirst::new(self)
+		self.depth_first()
+	}
+
+	/// Returns an iterator that performs a breadth-first traversal.
+	fn breadth_first_in(&self) -> BreadthFirst<'_, Self, InAdjacencies> {
+		BreadthFirst::new(self)
 	}
 }
 
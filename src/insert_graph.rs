@@ -1,11 +1,13 @@
 use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt::{self, Display};
 
 use super::{
 	map::{self, Map, MapMut},
 	Digraph,
 };
 
-use crate::Homomorphism;
+use crate::{Homomorphism, OutGraph};
 
 /// Represents a directed graph into which new vertices and edge can be
 /// inserted.
@@ -15,12 +17,226 @@ pub trait InsertGraph: Default + Digraph {
 		Default::default()
 	}
 
+	/// Constructs an empty graph with space reserved for at least the given
+	/// numbers of vertices and edges.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let mut g = DenseOutAdjacencyList::with_capacity(2, 1);
+	/// let u = g.insert_vert();
+	/// let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.edges().count(), 1);
+	/// ```
+	fn with_capacity(verts: usize, edges: usize) -> Self {
+		let mut g = Self::new();
+		g.reserve_verts(verts);
+		g.reserve_edges(edges);
+		g
+	}
+
+	/// Reserves space for at least `additional` more vertices. This is only a
+	/// hint and does nothing by default.
+	fn reserve_verts(&mut self, additional: usize) {
+		let _ = additional;
+	}
+
+	/// Reserves space for at least `additional` more edges. This is only a hint
+	/// and does nothing by default.
+	fn reserve_edges(&mut self, additional: usize) {
+		let _ = additional;
+	}
+
+	/// Removes all vertices and edges from the graph, retaining allocated
+	/// capacity where possible. Vertices and edges inserted afterwards are the
+	/// same as those of a new graph.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let mut g = DenseOutAdjacencyList::path(3);
+	/// g.clear();
+	/// assert_eq!(g.verts().count(), 0);
+	/// assert_eq!(g.edges().count(), 0);
+	/// ```
+	fn clear(&mut self) {
+		*self = Self::new();
+	}
+
 	/// Inserts a new vertex in the graph.
 	fn insert_vert(&mut self) -> Self::Vert;
 
 	/// Inserts a new edge in the graph with a given tail and head.
 	fn insert_edge(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge;
 
+	/// Returns an edge from `tail` to `head`, inserting one only if there is none
+	/// already, so that a graph built only this way has no parallel edges.
+	/// Finding an existing edge takes time linear in the out-degree of `tail`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let e = g.insert_edge_unique(u, v);
+	/// assert_eq!(g.insert_edge_unique(u, v), e);
+	/// assert_eq!(g.edges().count(), 1);
+	/// ```
+	fn insert_edge_unique(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge
+	where
+		Self: OutGraph,
+	{
+		match self.find_edge(tail, head) {
+			Some(e) => e,
+			None => self.insert_edge(tail, head),
+		}
+	}
+
+	/// Constructs a graph with `order` vertices and edges between them given by
+	/// the indices of their endpoints. Edges are inserted in order.
+	///
+	/// # Panics
+	/// Panics if an endpoint is not less than `order`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2)]);
+	/// assert_eq!(g.verts().count(), 3);
+	/// assert_eq!(g.edges().count(), 2);
+	/// ```
+	fn from_edges(order: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+		let edges = edges.into_iter();
+		let mut g = Self::with_capacity(order, edges.size_hint().0);
+		let verts: Vec<_> = (0..order).map(|_| g.insert_vert()).collect();
+		for (tail, head) in edges {
+			assert!(tail < order && head < order, "endpoint out of range");
+			g.insert_edge(verts[tail], verts[head]);
+		}
+		g
+	}
+
+	/// Constructs a path of `order` vertices with an edge from each vertex to the
+	/// next, for `order - 1` edges in total.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = DenseOutAdjacencyList::path(3);
+	/// assert_eq!((g.verts().count(), g.edges().count()), (3, 2));
+	/// ```
+	fn path(order: usize) -> Self {
+		Self::from_edges(order, (1..order).map(|i| (i - 1, i)))
+	}
+
+	/// Constructs a cycle of `order` vertices with an edge from each vertex to the
+	/// next and from the last to the first, for `order` edges in total. A cycle
+	/// of one vertex is a self-loop.
+	fn cycle(order: usize) -> Self {
+		Self::from_edges(order, (0..order).map(|i| (i, (i + 1) % order)))
+	}
+
+	/// Constructs a complete graph of `order` vertices with an edge from each
+	/// vertex to every other, for `order * (order - 1)` edges in total.
+	fn complete(order: usize) -> Self {
+		let edges = (0..order).flat_map(|tail| {
+			(0..order)
+				.filter(move |head| *head != tail)
+				.map(move |head| (tail, head))
+		});
+		Self::from_edges(order, edges)
+	}
+
+	/// Constructs a grid of `rows * columns` vertices in row-major order with an
+	/// edge from each vertex to each of its horizontal and vertical neighbors, for
+	/// `2 * (rows * (columns - 1) + (rows - 1) * columns)` edges in total.
+	fn grid(rows: usize, columns: usize) -> Self {
+		let mut edges = Vec::new();
+		for row in 0..rows {
+			for column in 0..columns {
+				let v = row * columns + column;
+				if column + 1 < columns {
+					edges.push((v, v + 1));
+					edges.push((v + 1, v));
+				}
+				if row + 1 < rows {
+					edges.push((v, v + columns));
+					edges.push((v + columns, v));
+				}
+			}
+		}
+		Self::from_edges(rows * columns, edges)
+	}
+
+	/// Constructs a random graph with `order` vertices in which each of the
+	/// `order * (order - 1)` possible edges between distinct vertices is present
+	/// independently with probability `edge_prob`, so the expected size is
+	/// `edge_prob * order * (order - 1)`. The graph has no self-loops or parallel
+	/// edges. Randomness is drawn from `rng`, which should return uniformly
+	/// distributed values.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let mut state = 1u64;
+	/// let mut rng = || {
+	///     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+	///     state
+	/// };
+	/// let g = DenseOutAdjacencyList::erdos_renyi(10, 1.0, &mut rng);
+	/// assert_eq!(g.edges().count(), 90);
+	/// ```
+	fn erdos_renyi(order: usize, edge_prob: f64, rng: &mut impl FnMut() -> u64) -> Self {
+		let mut g = Self::new();
+		let verts: Vec<_> = (0..order).map(|_| g.insert_vert()).collect();
+		for &tail in &verts {
+			for &head in &verts {
+				// The top 53 bits of a value give a uniform sample from `[0, 1)`.
+				let sample = (rng() >> 11) as f64 / (1u64 << 53) as f64;
+				if tail != head && sample < edge_prob {
+					g.insert_edge(tail, head);
+				}
+			}
+		}
+		g
+	}
+
+	/// Constructs a random graph with `order` vertices and exactly `size` edges
+	/// whose endpoints are chosen uniformly and independently, so self-loops and
+	/// parallel edges are possible. Randomness is drawn from `rng`, which should
+	/// return uniformly distributed values.
+	///
+	/// # Panics
+	/// Panics if `size` is positive but `order` is zero.
+	fn random_sparse(order: usize, size: usize, rng: &mut impl FnMut() -> u64) -> Self {
+		let mut g = Self::new();
+		let verts: Vec<_> = (0..order).map(|_| g.insert_vert()).collect();
+		for _ in 0..size {
+			let tail = verts[(rng() % order as u64) as usize];
+			let head = verts[(rng() % order as u64) as usize];
+			g.insert_edge(tail, head);
+		}
+		g
+	}
+
+	/// Inserts a new vertex in the graph, or returns an error instead of
+	/// panicking if the graph cannot hold any more vertices.
+	fn try_insert_vert(&mut self) -> Result<Self::Vert, CapacityError> {
+		Ok(self.insert_vert())
+	}
+
+	/// Inserts a new edge in the graph with a given tail and head, or returns an
+	/// error instead of panicking if the graph cannot hold any more edges.
+	fn try_insert_edge(
+		&mut self,
+		tail: Self::Vert,
+		head: Self::Vert,
+	) -> Result<Self::Edge, CapacityError> {
+		Ok(self.insert_edge(tail, head))
+	}
+
 	/// Constructs a graph isomorphic to the given graph and returns it along with
 	/// mappings from the given graph's vertices and edges to those in the new
 	/// graph.
@@ -44,3 +260,102 @@ pub trait InsertGraph: Default + Digraph {
 		)
 	}
 }
+
+/// Error indicating that a graph cannot hold any more vertices or edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl Display for CapacityError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "graph capacity exceeded")
+	}
+}
+
+impl Error for CapacityError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::{DenseOutAdjacencyList, ExactSizeDigraph};
+	use proptest::proptest;
+	use std::collections::{HashMap, HashSet};
+
+	proptest! {
+		#[test]
+		fn builders(order in 0usize..30, rows in 0usize..8, columns in 0usize..8) {
+			let path = DenseOutAdjacencyList::path(order);
+			assert_eq!(path.verts().count(), order);
+			assert_eq!(path.edges().count(), order.saturating_sub(1));
+			assert!(path.is_acyclic());
+
+			let cycle = DenseOutAdjacencyList::cycle(order);
+			assert_eq!(cycle.verts().count(), order);
+			assert_eq!(cycle.edges().count(), order);
+			assert_eq!(cycle.strongly_connected_components().len(), order.min(1));
+
+			let complete = DenseOutAdjacencyList::complete(order);
+			let pairs: HashSet<_> = complete.edges().map(|e| complete.endpoints(e)).collect();
+			assert_eq!(pairs.len(), complete.edges().count());
+			assert_eq!(pairs.len(), order * order.saturating_sub(1));
+			assert!(pairs.iter().all(|(tail, head)| tail != head));
+
+			let grid = DenseOutAdjacencyList::grid(rows, columns);
+			assert_eq!(grid.verts().count(), rows * columns);
+			let pairs: HashSet<_> = grid.edges().map(|e| grid.endpoints(e)).collect();
+			assert_eq!(pairs.len(), grid.edges().count());
+			let size = rows * columns.saturating_sub(1) + rows.saturating_sub(1) * columns;
+			assert_eq!(pairs.len(), 2 * size);
+			let positions: HashMap<_, _> = grid.verts().enumerate().map(|(i, v)| (v, i)).collect();
+			for (tail, head) in pairs {
+				let (tail, head) = (positions[&tail], positions[&head]);
+				let (tail_row, tail_column) = (tail / columns, tail % columns);
+				let (head_row, head_column) = (head / columns, head % columns);
+				assert_eq!(tail_row.max(head_row) - tail_row.min(head_row) + tail_column.max(head_column) - tail_column.min(head_column), 1);
+			}
+		}
+
+		#[test]
+		fn erdos_renyi(order in 0usize..20, seed: u64) {
+			let mut rng = rng(seed);
+			let g = DenseOutAdjacencyList::erdos_renyi(order, 1.0, &mut rng);
+			let pairs: HashSet<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			assert_eq!(pairs.len(), g.edges().count());
+			assert_eq!(pairs.len(), order * order.saturating_sub(1));
+			assert!(pairs.iter().all(|(tail, head)| tail != head));
+			let g = DenseOutAdjacencyList::erdos_renyi(order, 0.0, &mut rng);
+			assert_eq!(g.verts().count(), order);
+			assert_eq!(g.edges().count(), 0);
+		}
+
+		#[test]
+		fn random_sparse(order in 1usize..20, size in 0usize..100, seed: u64) {
+			let mut rng = rng(seed);
+			let g = DenseOutAdjacencyList::random_sparse(order, size, &mut rng);
+			assert_eq!(g.verts().count(), order);
+			assert_eq!(g.edges().count(), size);
+		}
+
+		#[test]
+		fn insert_edge_unique(g: TestGraph) {
+			let mut g = DenseOutAdjacencyList::from(&g);
+			let pairs: HashSet<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			for (tail, head) in pairs {
+				let size = g.size();
+				let e = g.insert_edge_unique(tail, head);
+				assert_eq!(g.endpoints(e), (tail, head));
+				assert_eq!(g.insert_edge_unique(tail, head), e);
+				assert_eq!(g.size(), size);
+			}
+			let verts: Vec<_> = g.verts().collect();
+			for &tail in verts.iter().take(5) {
+				for &head in verts.iter().take(5) {
+					let e = g.insert_edge_unique(tail, head);
+					let size = g.size();
+					assert_eq!(g.insert_edge_unique(tail, head), e);
+					assert_eq!(g.size(), size);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,437 @@
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::{map, Homomorphism, Map, MapMut, OutGraph};
+
+/// Adjacency structure of a graph over vertex indices, recording the number of
+/// parallel edges between each ordered pair of vertices.
+struct Indexed {
+	out_degrees: Vec<usize>,
+	in_degrees: Vec<usize>,
+	multiplicities: HashMap<(usize, usize), usize>,
+	/// Distinct vertices adjacent in either direction, excluding the vertex
+	/// itself.
+	neighbors: Vec<Vec<usize>>,
+}
+
+impl Indexed {
+	/// Indexes the vertices of a graph in iteration order, returning them along
+	/// with its adjacency structure.
+	fn new<G: OutGraph + ?Sized>(g: &G) -> (Self, Vec<G::Vert>) {
+		let verts: Vec<_> = g.verts().collect();
+		let order = verts.len();
+		let mut indices = g.ephemeral_vert_map(0usize);
+		for (i, v) in verts.iter().enumerate() {
+			*indices.get_mut(*v) = i;
+		}
+		let mut out_degrees = vec![0; order];
+		let mut in_degrees = vec![0; order];
+		let mut multiplicities = HashMap::new();
+		for (i, v) in verts.iter().enumerate() {
+			for e in g.out_edges(v) {
+				let j = *indices.get(g.head(e)).borrow();
+				out_degrees[i] += 1;
+				in_degrees[j] += 1;
+				*multiplicities.entry((i, j)).or_insert(0) += 1;
+			}
+		}
+		let mut neighbors = vec![Vec::new(); order];
+		for &(i, j) in multiplicities.keys() {
+			if i != j {
+				neighbors[i].push(j);
+				neighbors[j].push(i);
+			}
+		}
+		for neighbors in neighbors.iter_mut() {
+			neighbors.sort_unstable();
+			neighbors.dedup();
+		}
+		let indexed = Indexed {
+			out_degrees,
+			in_degrees,
+			multiplicities,
+			neighbors,
+		};
+		(indexed, verts)
+	}
+
+	fn order(&self) -> usize {
+		self.out_degrees.len()
+	}
+
+	fn multiplicity(&self, i: usize, j: usize) -> usize {
+		self.multiplicities.get(&(i, j)).copied().unwrap_or(0)
+	}
+
+	/// Orders the vertices so that each is adjacent to as many earlier vertices
+	/// as possible, which lets the search prune inconsistent candidates early.
+	fn matching_order(&self) -> Vec<usize> {
+		let order = self.order();
+		let mut ordered = vec![false; order];
+		let mut connections = vec![0usize; order];
+		let mut result = Vec::with_capacity(order);
+		for _ in 0..order {
+			let next = (0..order)
+				.filter(|i| !ordered[*i])
+				.max_by_key(|&i| {
+					let degree = self.out_degrees[i] + self.in_degrees[i];
+					(connections[i], degree, Reverse(i))
+				})
+				.expect("unordered vertex");
+			ordered[next] = true;
+			result.push(next);
+			for &j in self.neighbors[next].iter() {
+				connections[j] += 1;
+			}
+		}
+		result
+	}
+}
+
+/// Kind of vertex mapping sought from one graph into another.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+	/// A bijection preserving the number of edges between every ordered pair.
+	Isomorphism,
+	/// An injection mapping each ordered pair to one with at least as many
+	/// edges between it.
+	Monomorphism,
+}
+
+impl Mode {
+	/// Returns whether a count in the first graph is compatible with the
+	/// corresponding count in the second.
+	fn admits(self, count0: usize, count1: usize) -> bool {
+		match self {
+			Mode::Isomorphism => count0 == count1,
+			Mode::Monomorphism => count0 <= count1,
+		}
+	}
+}
+
+/// State of the VF2 search for a mapping from the vertices of one graph to
+/// those of another.
+struct State<'a> {
+	mode: Mode,
+	g0: &'a Indexed,
+	g1: &'a Indexed,
+	order: Vec<usize>,
+	map0: Vec<Option<usize>>,
+	map1: Vec<Option<usize>>,
+}
+
+impl<'a> State<'a> {
+	fn new(mode: Mode, g0: &'a Indexed, g1: &'a Indexed) -> Self {
+		State {
+			mode,
+			g0,
+			g1,
+			order: g0.matching_order(),
+			map0: vec![None; g0.order()],
+			map1: vec![None; g1.order()],
+		}
+	}
+
+	/// Returns whether mapping `v0` to `v1` is consistent with the vertices
+	/// mapped so far.
+	fn is_feasible(&self, v0: usize, v1: usize) -> bool {
+		let (mode, g0, g1) = (self.mode, self.g0, self.g1);
+		if !mode.admits(g0.out_degrees[v0], g1.out_degrees[v1])
+			|| !mode.admits(g0.in_degrees[v0], g1.in_degrees[v1])
+			|| !mode.admits(g0.multiplicity(v0, v0), g1.multiplicity(v1, v1))
+		{
+			return false;
+		}
+		let consistent = |w0: usize, w1: usize| {
+			mode.admits(g0.multiplicity(v0, w0), g1.multiplicity(v1, w1))
+				&& mode.admits(g0.multiplicity(w0, v0), g1.multiplicity(w1, v1))
+		};
+		// Unmapped neighbors must be mapped to distinct unmapped neighbors.
+		let unmapped0 = g0.neighbors[v0]
+			.iter()
+			.filter(|w0| self.map0[**w0].is_none());
+		let unmapped1 = g1.neighbors[v1]
+			.iter()
+			.filter(|w1| self.map1[**w1].is_none());
+		if !mode.admits(unmapped0.count(), unmapped1.count()) {
+			return false;
+		}
+		let mut forward = g0.neighbors[v0]
+			.iter()
+			.filter_map(|&w0| Some((w0, self.map0[w0]?)));
+		if mode == Mode::Monomorphism {
+			// Extra edges between images are allowed.
+			return forward.all(|(w0, w1)| consistent(w0, w1));
+		}
+		let backward = g1.neighbors[v1]
+			.iter()
+			.filter_map(|&w1| Some((self.map1[w1]?, w1)));
+		forward.chain(backward).all(|(w0, w1)| consistent(w0, w1))
+	}
+
+	fn extend(&mut self, depth: usize) -> bool {
+		let v0 = match self.order.get(depth) {
+			Some(v0) => *v0,
+			None => return true,
+		};
+		// The image of a vertex adjacent to one already mapped must be adjacent to
+		// the image of that vertex.
+		let anchor = self.g0.neighbors[v0].iter().find_map(|&w0| self.map0[w0]);
+		let candidates = match anchor {
+			Some(w1) => self.g1.neighbors[w1].clone(),
+			None => (0..self.g1.order()).collect(),
+		};
+		for v1 in candidates {
+			if self.map1[v1].is_some() || !self.is_feasible(v0, v1) {
+				continue;
+			}
+			self.map0[v0] = Some(v1);
+			self.map1[v1] = Some(v0);
+			if self.extend(depth + 1) {
+				return true;
+			}
+			self.map0[v0] = None;
+			self.map1[v1] = None;
+		}
+		false
+	}
+}
+
+/// Returns whether two graphs are isomorphic using the VF2 algorithm.
+pub(crate) fn is_isomorphic<G0: OutGraph + ?Sized, G1: OutGraph + ?Sized>(
+	g0: &G0,
+	g1: &G1,
+) -> bool {
+	let (g0, _) = Indexed::new(g0);
+	let (g1, _) = Indexed::new(g1);
+	if g0.order() != g1.order() || g0.multiplicities.len() != g1.multiplicities.len() {
+		return false;
+	}
+	// Isomorphic graphs have the same degree sequences.
+	let degrees = |g: &Indexed| {
+		let mut degrees: Vec<_> = (0..g.order())
+			.map(|i| (g.out_degrees[i], g.in_degrees[i]))
+			.collect();
+		degrees.sort_unstable();
+		degrees
+	};
+	if degrees(&g0) != degrees(&g1) {
+		return false;
+	}
+	State::new(Mode::Isomorphism, &g0, &g1).extend(0)
+}
+
+/// Returns an embedding of a pattern into a host graph, that is, an injective
+/// homomorphism, if one exists using the VF2 algorithm.
+pub(crate) fn find_subgraph_isomorphism<'p, P: OutGraph, G: OutGraph>(
+	host: &G,
+	pattern: &'p P,
+) -> Option<Homomorphism<'p, P, G>> {
+	let (indexed_pattern, pattern_verts) = Indexed::new(pattern);
+	let (indexed_host, host_verts) = Indexed::new(host);
+	if indexed_pattern.order() > indexed_host.order() {
+		return None;
+	}
+	let mut state = State::new(Mode::Monomorphism, &indexed_pattern, &indexed_host);
+	if !state.extend(0) {
+		return None;
+	}
+	let mut vert_map = pattern.ephemeral_vert_map(None);
+	for (v, i) in pattern_verts.iter().zip(state.map0) {
+		*vert_map.get_mut(*v) = Some(host_verts[i.expect("vertex mapped")]);
+	}
+	// Assign each pattern edge a distinct host edge between the images of its
+	// endpoints, which exists because multiplicities were compared.
+	let mut edge_map = pattern.ephemeral_edge_map(None);
+	for v in pattern_verts {
+		let tail = vert_map.get(v).borrow().expect("vertex mapped");
+		let mut available = HashMap::<_, Vec<_>>::new();
+		for e in host.out_edges(tail) {
+			available.entry(host.head(e)).or_default().push(e);
+		}
+		for e in pattern.out_edges(v) {
+			let head = vert_map
+				.get(pattern.head(e))
+				.borrow()
+				.expect("vertex mapped");
+			let image = available.get_mut(&head).and_then(Vec::pop);
+			*edge_map.get_mut(e) = Some(image.expect("enough parallel edges"));
+		}
+	}
+	Some(Homomorphism::new(
+		map::Unwrap::new(vert_map),
+		map::Unwrap::new(edge_map),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::model::test_graph::*;
+	use crate::{
+		DenseOutAdjacencyList, Digraph, Homomorphism, InsertGraph, OutGraph, SparseOutAdjacencyList,
+	};
+	use itertools::Itertools;
+	use proptest::{collection::vec, proptest};
+	use std::collections::{HashMap, HashSet};
+
+	/// Returns whether two graphs on `0..order` are isomorphic by trying every
+	/// permutation of the vertices.
+	fn brute_force(order: usize, edges0: &[(usize, usize)], edges1: &[(usize, usize)]) -> bool {
+		let mut sorted1 = edges1.to_vec();
+		sorted1.sort_unstable();
+		(0..order).permutations(order).any(|permutation| {
+			let mut sorted0: Vec<_> = edges0
+				.iter()
+				.map(|(tail, head)| (permutation[*tail], permutation[*head]))
+				.collect();
+			sorted0.sort_unstable();
+			sorted0 == sorted1
+		})
+	}
+
+	/// Returns whether a pattern on `0..pattern_order` embeds into a host on
+	/// `0..host_order` by trying every injection of the vertices.
+	fn brute_force_subgraph(
+		pattern_order: usize,
+		pattern_edges: &[(usize, usize)],
+		host_order: usize,
+		host_edges: &[(usize, usize)],
+	) -> bool {
+		(0..host_order)
+			.permutations(pattern_order)
+			.any(|injection| {
+				let mut remaining = host_edges.to_vec();
+				pattern_edges.iter().all(|(tail, head)| {
+					let image = (injection[*tail], injection[*head]);
+					match remaining.iter().position(|d| *d == image) {
+						Some(i) => {
+							remaining.swap_remove(i);
+							true
+						}
+						None => false,
+					}
+				})
+			})
+	}
+
+	/// Asserts that a homomorphism is injective and preserves endpoints.
+	fn assert_embedding<P: OutGraph, G: OutGraph>(
+		pattern: &P,
+		host: &G,
+		embedding: &Homomorphism<'_, P, G>,
+	) {
+		let verts: HashSet<_> = pattern.verts().map(|v| embedding.map_vert(v)).collect();
+		assert_eq!(verts.len(), pattern.verts().count());
+		let edges: HashSet<_> = pattern.edges().map(|e| embedding.map_edge(e)).collect();
+		assert_eq!(edges.len(), pattern.edges().count());
+		for e in pattern.edges() {
+			let (tail, head) = pattern.endpoints(e);
+			let expected = (embedding.map_vert(tail), embedding.map_vert(head));
+			assert_eq!(host.endpoints(embedding.map_edge(e)), expected);
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn isomorphic_from(g_test: TestGraph) {
+			let g = SparseOutAdjacencyList::from(&g_test);
+			let g_prime = DenseOutAdjacencyList::from(&g);
+			assert!(g.is_isomorphic(&g_prime));
+			assert!(g_prime.is_isomorphic(&g));
+		}
+
+		#[test]
+		fn extra_edge(g_test: TestGraph) {
+			let g = SparseOutAdjacencyList::from(&g_test);
+			let mut g_prime = DenseOutAdjacencyList::from(&g);
+			if let Some(v) = g_prime.verts().next() {
+				g_prime.insert_edge(v, v);
+				assert!(!g.is_isomorphic(&g_prime));
+			}
+		}
+
+		#[test]
+		fn matches_brute_force(
+			order in 1usize..6,
+			edges0 in vec((0usize..6, 0usize..6), 0..10),
+			edges1 in vec((0usize..6, 0usize..6), 0..10),
+		) {
+			let filter = |edges: Vec<(usize, usize)>| -> Vec<_> {
+				edges.into_iter().filter(|(tail, head)| *tail < order && *head < order).collect()
+			};
+			let (edges0, edges1) = (filter(edges0), filter(edges1));
+			let g0 = DenseOutAdjacencyList::from_edges(order, edges0.iter().cloned());
+			let g1 = DenseOutAdjacencyList::from_edges(order, edges1.iter().cloned());
+			assert_eq!(g0.is_isomorphic(&g1), brute_force(order, &edges0, &edges1));
+			// Relabeling the vertices preserves isomorphism.
+			let reversed = edges0.iter().map(|(tail, head)| (order - 1 - tail, order - 1 - head));
+			let g2 = DenseOutAdjacencyList::from_edges(order, reversed);
+			assert!(g0.is_isomorphic(&g2));
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn subgraph_found(g_test: TestGraph, order in 0usize..6, keep in vec(proptest::bool::ANY, 0..64)) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			// Keep a subset of the edges between a few vertices, as the search is
+			// exponential in the worst case.
+			let positions: HashMap<_, _> = g.verts().take(order).enumerate().map(|(i, v)| (v, i)).collect();
+			let edges = g
+				.edges()
+				.filter(|e| positions.contains_key(&g.tail(e)) && positions.contains_key(&g.head(e)))
+				.zip(keep.into_iter().chain(std::iter::repeat(true)))
+				.filter(|(_, keep)| *keep)
+				.map(|(e, _)| (positions[&g.tail(e)], positions[&g.head(e)]));
+			let pattern = DenseOutAdjacencyList::from_edges(positions.len(), edges);
+			let embedding = g.find_subgraph_isomorphism(&pattern).unwrap();
+			assert_embedding(&pattern, &g, &embedding);
+		}
+
+		#[test]
+		fn subgraph_matches_brute_force(
+			pattern_order in 1usize..4,
+			pattern_edges in vec((0usize..4, 0usize..4), 0..5),
+			host_order in 1usize..6,
+			host_edges in vec((0usize..6, 0usize..6), 0..12),
+		) {
+			let pattern_edges: Vec<_> = pattern_edges
+				.into_iter()
+				.filter(|(tail, head)| *tail < pattern_order && *head < pattern_order)
+				.collect();
+			let host_edges: Vec<_> = host_edges
+				.into_iter()
+				.filter(|(tail, head)| *tail < host_order && *head < host_order)
+				.collect();
+			let pattern = DenseOutAdjacencyList::from_edges(pattern_order, pattern_edges.iter().cloned());
+			let host = DenseOutAdjacencyList::from_edges(host_order, host_edges.iter().cloned());
+			let embedding = host.find_subgraph_isomorphism(&pattern);
+			assert_eq!(
+				embedding.is_some(),
+				brute_force_subgraph(pattern_order, &pattern_edges, host_order, &host_edges)
+			);
+			if let Some(embedding) = embedding {
+				assert_embedding(&pattern, &host, &embedding);
+			}
+		}
+	}
+
+	#[test]
+	fn subgraph_too_large() {
+		let host = DenseOutAdjacencyList::from_edges(2, vec![(0, 1)]);
+		let pattern = DenseOutAdjacencyList::from_edges(3, vec![]);
+		assert!(host.find_subgraph_isomorphism(&pattern).is_none());
+	}
+
+	#[test]
+	fn cycles() {
+		let cycle =
+			|order| DenseOutAdjacencyList::from_edges(order, (0..order).map(|i| (i, (i + 1) % order)));
+		// Two disjoint triangles have the same degrees as a hexagon.
+		let triangles =
+			DenseOutAdjacencyList::from_edges(6, (0..6).map(|i| (i, i / 3 * 3 + (i + 1) % 3)));
+		assert!(cycle(6).is_isomorphic(&cycle(6)));
+		assert!(!cycle(6).is_isomorphic(&triangles));
+	}
+}
@@ -4,10 +4,12 @@
 //!
 //! | Type                        | `InsertGraph` | `OutGraph` | `InGraph` | `remove_vert` | `remove_edge` |
 //! |----------------------------:|:-------------:|:----------:|:---------:|:-------------:|:-------------:|
+//! | `BitAdjacencyMatrix`        | **Yes**       | **Yes**    | **Yes**   | No            | No            |
 //! | `DenseEdgeList`             | **Yes**       | No         | No        | No            | No            |
 //! | `DenseInAdjacencyList`      | **Yes**       | No         | **Yes**   | No            | No            |
 //! | `DenseOutAdjacencyList`     | **Yes**       | **Yes**    | No        | No            | No            |
-//! | `DenseBiAdjacencyList`      | **Yes**       | **Yes**    | **Yes**   | No            | No            |
+//! | `DenseBiAdjacencyList`      | **Yes**       | **Yes**    | **Yes**   | No            | **Yes**       |
+//! | `ImmutableBiAdjacencyList`  | No            | **Yes**    | **Yes**   | No            | No            |
 //! | `ImmutableInAdjacencyList`  | No            | No         | **Yes**   | No            | No            |
 //! | `ImmutableOutAdjacencyList` | No            | **Yes**    | No        | No            | No            |
 //! | `SparseEdgeList`            | **Yes**       | No         | No        | No            | **Yes**       |
@@ -21,24 +23,77 @@
 #![feature(map_first_last)]
 #![cfg_attr(sif_index_niche, feature(rustc_attrs))]
 
+// Implements `Clone`, `Copy`, `PartialEq`, `Eq` and `Debug` for an error type
+// generic over a graph whose only field is `vert`. Deriving them would instead
+// require the graph to implement them.
+macro_rules! impl_vert_error {
+	($error:ident) => {
+		impl<G: crate::Digraph + ?Sized> Clone for $error<G> {
+			fn clone(&self) -> Self {
+				*self
+			}
+		}
+
+		impl<G: crate::Digraph + ?Sized> Copy for $error<G> {}
+
+		impl<G: crate::Digraph + ?Sized> PartialEq for $error<G> {
+			fn eq(&self, other: &Self) -> bool {
+				self.vert == other.vert
+			}
+		}
+
+		impl<G: crate::Digraph + ?Sized> Eq for $error<G> {}
+
+		impl<G: crate::Digraph + ?Sized> std::fmt::Debug for $error<G> {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.debug_struct(stringify!($error))
+					.field("vert", &self.vert)
+					.finish()
+			}
+		}
+	};
+}
+
 pub mod adjacencies;
+mod bellman_ford;
+mod bi_graph;
+mod biconnected;
 pub(crate) mod binary_heap;
+mod breadth_first;
+mod centrality;
 mod depth_first;
 mod digraph;
+mod dijkstra;
+pub mod flow;
 mod homomorphism;
 mod in_graph;
 mod insert_graph;
+mod isomorphism;
+mod lowest_common_ancestor;
 pub mod map;
 pub(crate) mod model;
 mod out_graph;
+mod strongly_connected;
+mod topological_sort;
+pub mod two_sat;
+pub(crate) mod union_find;
 
 pub use adjacencies::*;
+pub use bellman_ford::NegativeCycle;
+pub use bi_graph::{BiGraph, IsolatedVertices};
 pub(crate) use binary_heap::BinaryHeap;
+pub use breadth_first::*;
 pub use depth_first::*;
 pub use digraph::{Digraph, ExactOrderDigraph, ExactSizeDigraph};
+pub use dijkstra::Dijkstra;
 pub use homomorphism::*;
-pub use in_graph::InGraph;
-pub use insert_graph::InsertGraph;
+pub use in_graph::{ExactInDegreeDigraph, InGraph};
+pub use insert_graph::{CapacityError, InsertGraph};
+pub use lowest_common_ancestor::{LowestCommonAncestor, NotATree};
 pub use map::{Map, MapMut};
 pub use model::*;
-pub use out_graph::OutGraph;
+pub use out_graph::{ExactOutDegreeDigraph, OutGraph};
+pub use topological_sort::Cycle;
+#[cfg(feature = "rayon")]
+pub(crate) use union_find::ConcurrentUnionFind;
+pub(crate) use union_find::UnionFind;
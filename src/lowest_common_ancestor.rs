@@ -0,0 +1,195 @@
+use std::borrow::Borrow;
+
+use crate::{topological_sort, Digraph, Map, MapMut, OutGraph};
+
+/// Error indicating that a graph is not a rooted tree.
+pub struct NotATree<G: Digraph + ?Sized> {
+	vert: Option<G::Vert>,
+}
+
+impl<G: Digraph + ?Sized> NotATree<G> {
+	pub(crate) fn new(vert: Option<G::Vert>) -> Self {
+		NotATree { vert }
+	}
+
+	/// Returns a vertex which is a second root, has more than one in-edge, or
+	/// lies on or is reachable from a cycle, or `None` if the graph is empty.
+	pub fn vert(&self) -> Option<G::Vert> {
+		self.vert
+	}
+}
+
+impl_vert_error!(NotATree);
+
+/// Precomputed ancestor tables for answering lowest common ancestor queries on
+/// a rooted tree, with edges directed away from the root, in logarithmic time
+/// using binary lifting.
+///
+/// # Examples
+/// ```
+/// # use sif::*;
+/// let g = DenseOutAdjacencyList::from_edges(4, vec![(0, 1), (0, 2), (2, 3)]);
+/// let verts: Vec<_> = g.verts().collect();
+/// let lca = LowestCommonAncestor::new(&g).unwrap();
+/// assert_eq!(lca.lca(verts[1], verts[3]), verts[0]);
+/// assert_eq!(lca.lca(verts[2], verts[3]), verts[2]);
+/// ```
+pub struct LowestCommonAncestor<'a, G: OutGraph + ?Sized + 'a> {
+	depths: G::EphemeralVertMap<'a, usize>,
+	// The `k`th table maps each vertex to its ancestor `2^k` levels up, or to
+	// the root if there is none.
+	ancestors: Vec<G::EphemeralVertMap<'a, G::Vert>>,
+}
+
+impl<'a, G: OutGraph + ?Sized + 'a> LowestCommonAncestor<'a, G> {
+	/// Builds the ancestor tables for a graph, or returns an error if it is not
+	/// a rooted tree, that is, if it does not have a single vertex with no
+	/// in-edges from which every other vertex is reached by a unique edge.
+	pub fn new(g: &'a G) -> Result<Self, NotATree<G>> {
+		let in_degrees = topological_sort::in_degrees(g);
+		if let Some(v) = g.verts().find(|v| *in_degrees.get(*v).borrow() > 1) {
+			return Err(NotATree::new(Some(v)));
+		}
+		let mut roots = g.verts().filter(|v| *in_degrees.get(*v).borrow() == 0);
+		let root = match (roots.next(), roots.next()) {
+			(Some(root), None) => root,
+			(_, Some(v)) => return Err(NotATree::new(Some(v))),
+			// Without a root, every vertex has an in-edge and so lies on or is
+			// reachable from a cycle.
+			(None, None) => return Err(NotATree::new(g.verts().next())),
+		};
+
+		let mut depths = g.ephemeral_vert_map(0);
+		let mut parents = g.ephemeral_vert_map(root);
+		let mut reached = g.ephemeral_vert_map(false);
+		*reached.get_mut(root) = true;
+		let mut stack = vec![root];
+		let mut order = 0usize;
+		while let Some(v) = stack.pop() {
+			order += 1;
+			let depth = *depths.get(v).borrow();
+			for u in g.successors(v) {
+				*reached.get_mut(u) = true;
+				*depths.get_mut(u) = depth + 1;
+				*parents.get_mut(u) = v;
+				stack.push(u);
+			}
+		}
+		// Vertices with a single in-edge which are not reached from the root lie
+		// on or are reachable from a cycle.
+		if let Some(v) = g.verts().find(|v| !*reached.get(*v).borrow()) {
+			return Err(NotATree::new(Some(v)));
+		}
+
+		let mut ancestors = vec![parents];
+		let mut span = 1;
+		while span < order {
+			let previous = ancestors.last().expect("at least one table");
+			let mut next = g.ephemeral_vert_map(root);
+			for v in g.verts() {
+				let ancestor = *previous.get(v).borrow();
+				*next.get_mut(v) = *previous.get(ancestor).borrow();
+			}
+			ancestors.push(next);
+			span *= 2;
+		}
+		Ok(LowestCommonAncestor { depths, ancestors })
+	}
+
+	/// Returns the deepest vertex which is an ancestor of both given vertices,
+	/// where every vertex is considered an ancestor of itself.
+	pub fn lca(&self, u: impl Borrow<G::Vert>, v: impl Borrow<G::Vert>) -> G::Vert {
+		let (mut u, mut v) = (*u.borrow(), *v.borrow());
+		let (mut u_depth, v_depth) = (*self.depths.get(u).borrow(), *self.depths.get(v).borrow());
+		if u_depth < v_depth {
+			std::mem::swap(&mut u, &mut v);
+			u_depth = v_depth;
+		}
+		// Lift the deeper vertex to the depth of the other.
+		let mut lift = u_depth - *self.depths.get(v).borrow();
+		for ancestors in self.ancestors.iter() {
+			if lift & 1 != 0 {
+				u = *ancestors.get(u).borrow();
+			}
+			lift >>= 1;
+		}
+		if u == v {
+			return u;
+		}
+		// Lift both to just below their lowest common ancestor.
+		for ancestors in self.ancestors.iter().rev() {
+			let (u_ancestor, v_ancestor) = (*ancestors.get(u).borrow(), *ancestors.get(v).borrow());
+			if u_ancestor != v_ancestor {
+				u = u_ancestor;
+				v = v_ancestor;
+			}
+		}
+		*self.ancestors[0].get(u).borrow()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DenseOutAdjacencyList, InsertGraph};
+	use proptest::{collection::vec, proptest};
+	use std::collections::HashSet;
+
+	proptest! {
+		#[test]
+		fn lca_matches_naive(parents in vec(0usize..1000, 0..40)) {
+			// Each vertex after the first has an earlier vertex as its parent.
+			let order = parents.len() + 1;
+			let parents: Vec<_> = parents.iter().enumerate().map(|(i, p)| p % (i + 1)).collect();
+			let g = DenseOutAdjacencyList::from_edges(
+				order,
+				parents.iter().enumerate().map(|(i, p)| (*p, i + 1)),
+			);
+			let verts: Vec<_> = g.verts().collect();
+			let lca = LowestCommonAncestor::new(&g).unwrap();
+			let ancestors = |mut v: usize| {
+				let mut ancestors = vec![v];
+				while v > 0 {
+					v = parents[v - 1];
+					ancestors.push(v);
+				}
+				ancestors
+			};
+			for u in 0..order {
+				let u_ancestors: HashSet<_> = ancestors(u).into_iter().collect();
+				for v in 0..order {
+					let expected = ancestors(v).into_iter().find(|a| u_ancestors.contains(a)).unwrap();
+					assert_eq!(lca.lca(verts[u], verts[v]), verts[expected]);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn lca_not_a_tree() {
+		let g = DenseOutAdjacencyList::new();
+		assert_eq!(LowestCommonAncestor::new(&g).err().unwrap().vert(), None);
+
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (2, 1)]);
+		let verts: Vec<_> = g.verts().collect();
+		assert_eq!(
+			LowestCommonAncestor::new(&g).err().unwrap().vert(),
+			Some(verts[1])
+		);
+
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1)]);
+		let verts: Vec<_> = g.verts().collect();
+		assert_eq!(
+			LowestCommonAncestor::new(&g).err().unwrap().vert(),
+			Some(verts[2])
+		);
+
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(1, 2), (2, 1)]);
+		let verts: Vec<_> = g.verts().collect();
+		let cycle = LowestCommonAncestor::new(&g).err().unwrap().vert();
+		assert!(cycle == Some(verts[1]) || cycle == Some(verts[2]));
+
+		let g = DenseOutAdjacencyList::from_edges(2, vec![(0, 1), (1, 0)]);
+		assert!(LowestCommonAncestor::new(&g).is_err());
+	}
+}
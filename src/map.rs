@@ -1,7 +1,10 @@
 //! Module for representing maps from vertices or edges to values.
 
 use std::borrow::Borrow;
-use std::ops::DerefMut;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::model::dense::Key;
 
 /// Represents a mapping from keys to values.
 pub trait Map<K> {
@@ -17,6 +20,16 @@ pub trait Map<K> {
 	fn get<'a>(&'a self, k: K) -> Self::Ref<'a>
 	where
 		Self::Value: 'a;
+
+	/// Returns the index of a key in the vector backing maps of this type, or
+	/// `None` if they are not backed by a vector indexed by key.
+	#[doc(hidden)]
+	fn dense_index(_k: &K) -> Option<usize>
+	where
+		Self: Sized,
+	{
+		None
+	}
 }
 
 impl<K, T, F: Fn(K) -> T> Map<K> for F {
@@ -79,3 +92,269 @@ impl<K, T: Copy, M: Map<K, Value = Option<T>>> Map<K> for Unwrap<M> {
 		UnwrapRef(self.0.get(k), std::marker::PhantomData)
 	}
 }
+
+/// Map adaptor which transforms values by a function. Values are computed on
+/// each access rather than stored.
+///
+/// # Examples
+/// ```
+/// # use sif::{map::MapValues, *};
+/// let costs = MapValues::new(|e: usize| e as u32, |cost: &u32| u64::from(*cost) * 2);
+/// assert_eq!(costs.get(3), 6);
+/// ```
+pub struct MapValues<M, F>(M, F);
+
+impl<M, F> MapValues<M, F> {
+	/// Constructs an adaptor which applies a function to the values of the given
+	/// map.
+	pub fn new(m: M, f: F) -> Self {
+		MapValues(m, f)
+	}
+}
+
+impl<K, T, M: Map<K>, F: Fn(&M::Value) -> T> Map<K> for MapValues<M, F> {
+	type Value = T;
+
+	type Ref<'a>
+	where
+		Self::Value: 'a,
+	= Self::Value;
+
+	fn get<'a>(&'a self, k: K) -> Self::Ref<'a>
+	where
+		Self::Value: 'a,
+	{
+		(self.1)(self.0.get(k).borrow())
+	}
+}
+
+/// Map adaptor which pairs the values of two maps with the same keys. Values
+/// are cloned into a new pair on each access.
+///
+/// # Examples
+/// ```
+/// # use sif::{map::ZipMap, *};
+/// # use std::borrow::Borrow;
+/// let costs = ZipMap::new(|e: usize| e, |e: usize| e % 2 == 0);
+/// let value: (usize, bool) = *costs.get(3).borrow();
+/// assert_eq!(value, (3, false));
+/// ```
+pub struct ZipMap<M0, M1>(M0, M1);
+
+impl<M0, M1> ZipMap<M0, M1> {
+	/// Constructs an adaptor which pairs the values of the given maps.
+	pub fn new(m0: M0, m1: M1) -> Self {
+		ZipMap(m0, m1)
+	}
+}
+
+// The values are required to be `'static` because the outlives bound on `Ref`
+// cannot be expressed for a tuple of generic values.
+impl<K: Copy, T0, T1, M0: Map<K, Value = T0>, M1: Map<K, Value = T1>> Map<K> for ZipMap<M0, M1>
+where
+	T0: Clone + 'static,
+	T1: Clone + 'static,
+{
+	type Value = (T0, T1);
+
+	type Ref<'a>
+	where
+		Self::Value: 'a,
+	= ZipRef<T0, T1>;
+
+	fn get<'a>(&'a self, k: K) -> Self::Ref<'a>
+	where
+		Self::Value: 'a,
+	{
+		ZipRef((
+			self.0.get(k).borrow().clone(),
+			self.1.get(k).borrow().clone(),
+		))
+	}
+}
+
+/// Pair of values materialized by a `ZipMap`.
+pub struct ZipRef<T0, T1>((T0, T1));
+
+impl<T0, T1> Borrow<(T0, T1)> for ZipRef<T0, T1> {
+	fn borrow(&self) -> &(T0, T1) {
+		&self.0
+	}
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Map from dense vertex keys to `bool` values which stores one bit per key.
+/// Unset keys map to `false`. This uses an eighth of the memory of a
+/// `VertMap<bool>` for the dense models, for example when tracking visited
+/// vertices during a traversal.
+///
+/// # Examples
+/// ```
+/// # use sif::{map::BitVertMap, *};
+/// # let mut g = DenseOutAdjacencyList::new();
+/// # let u = g.insert_vert();
+/// # let v = g.insert_vert();
+/// let mut visited = BitVertMap::with_capacity(g.verts().len());
+/// *visited.get_mut(v) = true;
+/// assert!(!visited.get(u));
+/// assert!(visited.get(v));
+/// ```
+pub struct BitVertMap<K> {
+	words: Vec<u64>,
+	_phantom: PhantomData<fn() -> K>,
+}
+
+impl<K> BitVertMap<K> {
+	/// Constructs an empty map with room for the given number of keys.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BitVertMap {
+			words: Vec::with_capacity(capacity / WORD_BITS + 1),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<K: Key> Map<K> for BitVertMap<K> {
+	type Value = bool;
+
+	type Ref<'a>
+	where
+		Self::Value: 'a,
+	= bool;
+
+	fn get<'a>(&'a self, k: K) -> Self::Ref<'a>
+	where
+		Self::Value: 'a,
+	{
+		let index = k.index();
+		let word = self.words.get(index / WORD_BITS);
+		matches!(word, Some(word) if word & (1 << (index % WORD_BITS)) != 0)
+	}
+}
+
+impl<K: Key> MapMut<K> for BitVertMap<K> {
+	type RefMut<'a>
+	where
+		Self::Value: 'a,
+	= BitRefMut<'a>;
+
+	fn get_mut(&mut self, k: K) -> Self::RefMut<'_> {
+		let index = k.index();
+		let word = index / WORD_BITS;
+		if word >= self.words.len() {
+			self.words.resize(word + 1, 0);
+		}
+		let mask = 1 << (index % WORD_BITS);
+		let word = &mut self.words[word];
+		BitRefMut {
+			value: *word & mask != 0,
+			word,
+			mask,
+		}
+	}
+}
+
+/// Mutable reference to a bit in a `BitVertMap`, which is written back when
+/// dropped.
+pub struct BitRefMut<'a> {
+	word: &'a mut u64,
+	mask: u64,
+	value: bool,
+}
+
+impl<'a> Deref for BitRefMut<'a> {
+	type Target = bool;
+
+	fn deref(&self) -> &bool {
+		&self.value
+	}
+}
+
+impl<'a> DerefMut for BitRefMut<'a> {
+	fn deref_mut(&mut self) -> &mut bool {
+		&mut self.value
+	}
+}
+
+impl<'a> Drop for BitRefMut<'a> {
+	fn drop(&mut self) {
+		if self.value {
+			*self.word |= self.mask;
+		} else {
+			*self.word &= !self.mask;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::{DenseOutAdjacencyList, Digraph, InsertGraph};
+	use proptest::proptest;
+
+	proptest! {
+		#[test]
+		fn bit_vert_map(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			assert_vert_map_works_with(
+				g,
+				|g| BitVertMap::with_capacity(g.verts().len()),
+				|v| v.index() % 3 == 0,
+				|g| {
+					g.insert_vert();
+				},
+			);
+		}
+
+		#[test]
+		fn bit_vert_map_clear(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut map = BitVertMap::with_capacity(g.verts().len());
+			for v in g.verts() {
+				*map.get_mut(v) = true;
+			}
+			for v in g.verts() {
+				*map.get_mut(v) = false;
+				assert!(!map.get(v));
+			}
+		}
+
+		#[test]
+		fn map_values(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(None);
+			let mut plain_costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = Some(c);
+				*plain_costs.get_mut(e) = c;
+			}
+			let doubled = MapValues::new(Unwrap::new(costs), |cost: &usize| cost * 2);
+			let manual = |e| *plain_costs.get(e) * 2;
+			for e in g.edges() {
+				assert_eq!(doubled.get(e), manual.get(e));
+			}
+		}
+
+		#[test]
+		fn zip_map(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut capacities = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+				*capacities.get_mut(e) = 100 - c;
+			}
+			let zipped = ZipMap::new(|e| *costs.get(e), |e| *capacities.get(e));
+			for e in g.edges() {
+				let value: (usize, usize) = *zipped.get(e).borrow();
+				assert_eq!(value, (*costs.get(e), *capacities.get(e)));
+			}
+		}
+	}
+}
@@ -0,0 +1,308 @@
+//! Module implementing a bit adjacency matrix.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Display};
+
+use crate::{Digraph, InGraph, InsertGraph, OutGraph};
+
+use super::dense::{self, Key};
+use super::sparse;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[allow(missing_docs)]
+pub type Vert = super::key::DenseVert;
+#[allow(missing_docs)]
+pub type Edge = (Vert, Vert);
+#[allow(missing_docs)]
+pub type Verts<'a> = dense::DomainKeys<'a, Vert>;
+#[allow(missing_docs)]
+pub type VertMap<T> = dense::Map<Vert, T>;
+#[allow(missing_docs)]
+pub type EdgeMap<T> = sparse::Map<Edge, T>;
+#[allow(missing_docs)]
+pub type EphemeralVertMap<'a, T> = dense::EphemeralMap<Vert, T>;
+#[allow(missing_docs)]
+pub type EphemeralEdgeMap<'a, T> = sparse::EphemeralMap<Edge, T>;
+
+/// Bit adjacency matrix directed graph representation. Each possible edge is
+/// represented by a single bit, so edges are identified by their endpoints and
+/// parallel edges cannot be represented: inserting an edge which is already
+/// present has no effect.
+#[derive(Clone, Debug, Default)]
+pub struct BitAdjacencyMatrix {
+	// Mapping from vertices to the bitset of heads of their out-adjacencies.
+	// Rows are only as long as needed to hold their greatest set bit.
+	rows: dense::Domain<Vert, Vec<u64>>,
+	size: usize,
+}
+
+impl BitAdjacencyMatrix {
+	fn contains(&self, tail: Vert, head: Vert) -> bool {
+		let head = head.index();
+		let word = self.rows[tail].get(head / WORD_BITS);
+		matches!(word, Some(word) if word & (1 << (head % WORD_BITS)) != 0)
+	}
+}
+
+/// Iterator over the edges of a bit adjacency matrix.
+#[derive(Clone)]
+pub struct Edges<'a> {
+	matrix: &'a BitAdjacencyMatrix,
+	tails: Verts<'a>,
+	out_edges: Option<OutEdges<'a>>,
+}
+
+impl<'a> Iterator for Edges<'a> {
+	type Item = Edge;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(e) = self.out_edges.as_mut().and_then(Iterator::next) {
+				return Some(e);
+			}
+			let tail = self.tails.next()?;
+			self.out_edges = Some(self.matrix.out_edges(tail));
+		}
+	}
+}
+
+/// Iterator over the out-adjacencies of a vertex in a bit adjacency matrix.
+#[derive(Clone)]
+pub struct OutEdges<'a> {
+	tail: Vert,
+	words: std::slice::Iter<'a, u64>,
+	// Offset of the first bit of `word`.
+	offset: usize,
+	// Bits of the current word which remain to be yielded.
+	word: u64,
+}
+
+impl<'a> Iterator for OutEdges<'a> {
+	type Item = Edge;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.word == 0 {
+			self.word = *self.words.next()?;
+			self.offset += WORD_BITS;
+		}
+		let bit = self.word.trailing_zeros() as usize;
+		self.word &= self.word - 1;
+		Some((self.tail, (self.offset + bit).into()))
+	}
+}
+
+/// Iterator over the in-adjacencies of a vertex in a bit adjacency matrix.
+#[derive(Clone)]
+pub struct InEdges<'a> {
+	matrix: &'a BitAdjacencyMatrix,
+	head: Vert,
+	tails: Verts<'a>,
+}
+
+impl<'a> Iterator for InEdges<'a> {
+	type Item = Edge;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (matrix, head) = (self.matrix, self.head);
+		let tail = self.tails.find(|tail| matrix.contains(*tail, head))?;
+		Some((tail, head))
+	}
+}
+
+impl Digraph for BitAdjacencyMatrix {
+	type Vert = Vert;
+	type Edge = Edge;
+
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		*e.borrow()
+	}
+
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let (tail, head) = *e.borrow();
+		if self.rows.get(tail).is_some() && self.contains(tail, head) {
+			Some((tail, head))
+		} else {
+			None
+		}
+	}
+
+	type Verts<'a> = Verts<'a>;
+	fn verts(&self) -> Self::Verts<'_> {
+		self.rows.keys()
+	}
+
+	type Edges<'a> = Edges<'a>;
+	fn edges(&self) -> Self::Edges<'_> {
+		Edges {
+			matrix: self,
+			tails: self.rows.keys(),
+			out_edges: None,
+		}
+	}
+
+	type VertMap<T: Clone> = VertMap<T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		VertMap::with_capacity(default, self.rows.len())
+	}
+
+	type EdgeMap<T: Clone> = EdgeMap<T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		EdgeMap::with_capacity(default, self.size)
+	}
+
+	type EphemeralVertMap<'a, T: Clone> = EphemeralVertMap<'a, T>;
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		EphemeralVertMap::with_capacity(default, self.rows.len())
+	}
+
+	type EphemeralEdgeMap<'a, T: Clone> = EphemeralEdgeMap<'a, T>;
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		EphemeralEdgeMap::with_capacity(default, self.size)
+	}
+}
+
+impl OutGraph for BitAdjacencyMatrix {
+	type OutEdges<'a> = OutEdges<'a>;
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		let tail = *v.borrow();
+		let mut words = self.rows[tail].iter();
+		let word = words.next().copied().unwrap_or(0);
+		OutEdges {
+			tail,
+			words,
+			offset: 0,
+			word,
+		}
+	}
+
+	fn find_edge(
+		&self,
+		tail: impl Borrow<Self::Vert>,
+		head: impl Borrow<Self::Vert>,
+	) -> Option<Self::Edge> {
+		let (tail, head) = (*tail.borrow(), *head.borrow());
+		if self.contains(tail, head) {
+			Some((tail, head))
+		} else {
+			None
+		}
+	}
+}
+
+impl InGraph for BitAdjacencyMatrix {
+	type InEdges<'a> = InEdges<'a>;
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		InEdges {
+			matrix: self,
+			head: *v.borrow(),
+			tails: self.rows.keys(),
+		}
+	}
+}
+
+impl InsertGraph for BitAdjacencyMatrix {
+	fn insert_vert(&mut self) -> Self::Vert {
+		self.rows.insert_default()
+	}
+
+	fn clear(&mut self) {
+		self.rows.clear();
+		self.size = 0;
+	}
+
+	fn reserve_verts(&mut self, additional: usize) {
+		self.rows.reserve(additional);
+	}
+
+	/// Inserts an edge with a given tail and head, or returns the existing one.
+	fn insert_edge(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge {
+		let row = &mut self.rows[tail];
+		let (word, bit) = (head.index() / WORD_BITS, head.index() % WORD_BITS);
+		if row.len() <= word {
+			row.resize(word + 1, 0);
+		}
+		if row[word] & (1 << bit) == 0 {
+			row[word] |= 1 << bit;
+			self.size += 1;
+		}
+		(tail, head)
+	}
+}
+
+impl_extend!(BitAdjacencyMatrix);
+
+impl<G: Digraph> From<&G> for BitAdjacencyMatrix {
+	fn from(from: &G) -> Self {
+		Self::isomorphic_from(from).0
+	}
+}
+
+impl Display for BitAdjacencyMatrix {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+
+	// Parallel edges collapse into one, so the generic isomorphism test only
+	// applies to simple graphs.
+	proptest! {
+		#[test]
+		fn ismorphic_from(g: TestGraph) {
+			let g = g.simple();
+			let (g_prime, homomorphism) = BitAdjacencyMatrix::isomorphic_from(&g);
+			assert!(g.is_isomorphic_with_maps(&g_prime, homomorphism.vert_map(), homomorphism.edge_map()));
+		}
+
+		#[test]
+		fn invariants(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_all_bi_graph_invariants(&g_prime);
+		}
+
+		#[test]
+		fn parallel_edges(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_eq!(g_prime.edges().count(), g.simple().edges().count());
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				assert!(g_prime.has_edge(Vert::from(tail), Vert::from(head)));
+			}
+		}
+
+		#[test]
+		fn vert_map(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_vert_map_works(g_prime);
+		}
+
+		#[test]
+		fn edge_map(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_edge_map_works(g_prime);
+		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = BitAdjacencyMatrix::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<BitAdjacencyMatrix>(&g.simple());
+		}
+	}
+}
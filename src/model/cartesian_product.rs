@@ -0,0 +1,222 @@
+//! Module implementing the Cartesian product of graphs.
+
+#![allow(type_alias_bounds)]
+
+use std::borrow::Borrow;
+use std::iter::{Chain, Map};
+
+use super::sparse;
+
+use itertools::{Itertools, Product};
+
+use crate::{Digraph, InGraph, OutGraph};
+
+/// Cartesian product of two graphs. Its vertices are pairs of vertices, and
+/// there is an edge from `(u0, u1)` to `(v0, v1)` for each edge from `u0` to
+/// `v0` when `u1 == v1` and for each edge from `u1` to `v1` when `u0 == v0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CartesianProduct<G0, G1>(pub G0, pub G1);
+
+/// Edge of a Cartesian product, arising from an edge of one factor and a fixed
+/// vertex of the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ProductEdge<V0, E0, V1, E1> {
+	/// Edge of the first factor paired with a vertex of the second.
+	First(E0, V1),
+	/// Vertex of the first factor paired with an edge of the second.
+	Second(V0, E1),
+}
+
+#[allow(missing_docs)]
+pub type Vert<G0: Digraph, G1: Digraph> = (G0::Vert, G1::Vert);
+#[allow(missing_docs)]
+pub type Edge<G0: Digraph, G1: Digraph> = ProductEdge<G0::Vert, G0::Edge, G1::Vert, G1::Edge>;
+#[allow(missing_docs)]
+pub type Verts<'a, G0: Digraph, G1: Digraph> = Product<G0::Verts<'a>, G1::Verts<'a>>;
+#[allow(missing_docs)]
+pub type Edges<'a, G0: Digraph, G1: Digraph> = Chain<
+	Map<Product<G0::Edges<'a>, G1::Verts<'a>>, fn((G0::Edge, G1::Vert)) -> Edge<G0, G1>>,
+	Map<Product<G0::Verts<'a>, G1::Edges<'a>>, fn((G0::Vert, G1::Edge)) -> Edge<G0, G1>>,
+>;
+
+#[allow(missing_docs)]
+pub type VertMap<G0: Digraph, G1: Digraph, T> = sparse::Map<Vert<G0, G1>, T>;
+#[allow(missing_docs)]
+pub type EdgeMap<G0: Digraph, G1: Digraph, T> = sparse::Map<Edge<G0, G1>, T>;
+
+/// Iterator over the edges of a Cartesian product incident to a vertex, given
+/// the incident edges of each of its components.
+#[derive(Clone)]
+pub struct IncidentEdges<V0, I0, V1, I1> {
+	v0: V0,
+	first: I0,
+	v1: V1,
+	second: I1,
+}
+
+impl<V0: Copy, I0: Iterator, V1: Copy, I1: Iterator> Iterator for IncidentEdges<V0, I0, V1, I1> {
+	type Item = ProductEdge<V0, I0::Item, V1, I1::Item>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(e0) = self.first.next() {
+			return Some(ProductEdge::First(e0, self.v1));
+		}
+		let e1 = self.second.next()?;
+		Some(ProductEdge::Second(self.v0, e1))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (first_lower, first_upper) = self.first.size_hint();
+		let (second_lower, second_upper) = self.second.size_hint();
+		let upper = match (first_upper, second_upper) {
+			(Some(first_upper), Some(second_upper)) => first_upper.checked_add(second_upper),
+			_ => None,
+		};
+		(first_lower.saturating_add(second_lower), upper)
+	}
+}
+
+#[allow(missing_docs)]
+pub type OutEdges<'a, G0: OutGraph, G1: OutGraph> =
+	IncidentEdges<G0::Vert, G0::OutEdges<'a>, G1::Vert, G1::OutEdges<'a>>;
+#[allow(missing_docs)]
+pub type InEdges<'a, G0: InGraph, G1: InGraph> =
+	IncidentEdges<G0::Vert, G0::InEdges<'a>, G1::Vert, G1::InEdges<'a>>;
+
+impl<G0: Digraph, G1: Digraph> Digraph for CartesianProduct<G0, G1> {
+	type Vert = Vert<G0, G1>;
+	type Edge = Edge<G0, G1>;
+
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		match *e.borrow() {
+			ProductEdge::First(e0, v1) => {
+				let (t0, h0) = self.0.endpoints(e0);
+				((t0, v1), (h0, v1))
+			}
+			ProductEdge::Second(v0, e1) => {
+				let (t1, h1) = self.1.endpoints(e1);
+				((v0, t1), (v0, h1))
+			}
+		}
+	}
+
+	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		match *e.borrow() {
+			ProductEdge::First(e0, v1) => (self.0.tail(e0), v1),
+			ProductEdge::Second(v0, e1) => (v0, self.1.tail(e1)),
+		}
+	}
+
+	fn head(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		match *e.borrow() {
+			ProductEdge::First(e0, v1) => (self.0.head(e0), v1),
+			ProductEdge::Second(v0, e1) => (v0, self.1.head(e1)),
+		}
+	}
+
+	type Verts<'a> = Verts<'a, G0, G1>;
+	fn verts(&self) -> Self::Verts<'_> {
+		self.0.verts().cartesian_product(self.1.verts())
+	}
+
+	type Edges<'a> = Edges<'a, G0, G1>;
+	fn edges(&self) -> Self::Edges<'_> {
+		let first: fn(_) -> _ = |(e0, v1)| ProductEdge::First(e0, v1);
+		let second: fn(_) -> _ = |(v0, e1)| ProductEdge::Second(v0, e1);
+		self
+			.0
+			.edges()
+			.cartesian_product(self.1.verts())
+			.map(first)
+			.chain(self.0.verts().cartesian_product(self.1.edges()).map(second))
+	}
+
+	type VertMap<T: Clone> = VertMap<G0, G1, T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		sparse::Map::new(default)
+	}
+
+	type EdgeMap<T: Clone> = EdgeMap<G0, G1, T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		sparse::Map::new(default)
+	}
+
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		self.vert_map(default)
+	}
+
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		self.edge_map(default)
+	}
+}
+
+impl<G0: OutGraph, G1: OutGraph> OutGraph for CartesianProduct<G0, G1> {
+	type OutEdges<'a> = OutEdges<'a, G0, G1>;
+
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		let (v0, v1) = *v.borrow();
+		IncidentEdges {
+			v0,
+			first: self.0.out_edges(v0),
+			v1,
+			second: self.1.out_edges(v1),
+		}
+	}
+}
+
+impl<G0: InGraph, G1: InGraph> InGraph for CartesianProduct<G0, G1> {
+	type InEdges<'a> = InEdges<'a, G0, G1>;
+
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		let (v0, v1) = *v.borrow();
+		IncidentEdges {
+			v0,
+			first: self.0.in_edges(v0),
+			v1,
+			second: self.1.in_edges(v1),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+	proptest! {
+		#[test]
+		fn order(g0: TestGraph, g1: TestGraph) {
+			let g = CartesianProduct(g0, g1);
+			let mut order = 0usize;
+			for _v in g.verts() {
+				order += 1;
+			}
+			assert_eq!(order, g.0.verts().len() * g.1.verts().len());
+		}
+
+		#[test]
+		fn size(g0: TestGraph, g1: TestGraph) {
+			let g = CartesianProduct(g0, g1);
+			let mut size = 0usize;
+			for _e in g.edges() {
+				size += 1;
+			}
+			assert_eq!(
+				size,
+				g.0.verts().len() * g.1.edges().len() + g.0.edges().len() * g.1.verts().len()
+			);
+		}
+
+		#[test]
+		fn invariants(g0: TestGraph, g1: TestGraph) {
+			assert_all_digraph_invariants(&CartesianProduct(g0, g1));
+		}
+
+		#[test]
+		fn bi_invariants(g0: TestGraph, g1: TestGraph) {
+			let g0_prime = crate::DenseBiAdjacencyList::from(&g0);
+			let g1_prime = crate::DenseBiAdjacencyList::from(&g1);
+			assert_all_bi_graph_invariants(&CartesianProduct(g0_prime, g1_prime));
+		}
+	}
+}
@@ -33,15 +33,50 @@ impl<K: Key, T> Domain<K, T> {
 		&self.values
 	}
 
+	pub fn values_mut(&mut self) -> &mut [T] {
+		&mut self.values
+	}
+
+	pub fn shrink_to_fit(&mut self) {
+		self.values.shrink_to_fit();
+	}
+
 	pub fn len(&self) -> usize {
 		self.values.len()
 	}
 
+	#[cfg(test)]
+	pub fn capacity(&self) -> usize {
+		self.values.capacity()
+	}
+
+	pub fn reserve(&mut self, additional: usize) {
+		self.values.reserve(additional);
+	}
+
+	pub fn clear(&mut self) {
+		self.values.clear();
+	}
+
+	// Returns whether inserting another key would exceed the range of indices.
+	pub fn is_full(&self) -> bool {
+		!super::index::Index::is_valid(self.len())
+	}
+
 	pub fn insert(&mut self, value: T) -> K {
 		let key = self.len().into();
 		self.values.push(value);
 		key
 	}
+
+	pub fn get(&self, k: K) -> Option<&T> {
+		self.values.get(k.index())
+	}
+
+	// The last key takes the place of the removed one.
+	pub fn swap_remove(&mut self, k: K) -> T {
+		self.values.swap_remove(k.index())
+	}
 }
 
 impl<K: Key, T: Default> Domain<K, T> {
@@ -67,7 +102,7 @@ impl<K: Key, T> IndexMut<K> for Domain<K, T> {
 pub struct Map<K, T> {
 	values: Vec<T>,
 	default: T,
-	_phantom: PhantomData<*const K>,
+	_phantom: PhantomData<fn() -> K>,
 }
 
 impl<K: Key, T: Clone> Map<K, T> {
@@ -78,6 +113,20 @@ impl<K: Key, T: Clone> Map<K, T> {
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Resets every key to the default value, retaining the allocated capacity.
+	pub fn clear_to_default(&mut self) {
+		self.values.clear();
+	}
+
+	/// Returns an iterator over keys and their values in ascending order of key
+	/// index. Only the stored values are yielded, which are those for keys up to
+	/// the greatest key accessed mutably. Keys beyond it map to the default value
+	/// and are not yielded, while unset keys before it are yielded with the
+	/// default value.
+	pub fn iter(&self) -> impl Iterator<Item = (K, &T)> + '_ {
+		self.values.iter().enumerate().map(|(i, v)| (K::from(i), v))
+	}
 }
 
 impl<K: Key, T: Clone> crate::Map<K> for Map<K, T> {
@@ -93,6 +142,10 @@ impl<K: Key, T: Clone> crate::Map<K> for Map<K, T> {
 		let index = k.index();
 		self.values.get(index).unwrap_or(&self.default)
 	}
+
+	fn dense_index(k: &K) -> Option<usize> {
+		Some(k.index())
+	}
 }
 
 impl<K: Key, T: Clone> crate::MapMut<K> for Map<K, T> {
@@ -109,9 +162,52 @@ impl<K: Key, T: Clone> crate::MapMut<K> for Map<K, T> {
 	}
 }
 
-// TODO: We can implement this more efficiently because it can assume valid
-// indices (or panic).
-pub type EphemeralMap<K, T> = Map<K, T>;
+/// Map over a fixed set of keys, all of whose indices are less than the
+/// capacity it is constructed with. Unlike `Map`, every value is allocated up
+/// front, so accesses never resize. Accessing a key outside of that range
+/// panics.
+#[derive(Clone, Debug)]
+pub struct EphemeralMap<K, T> {
+	values: Vec<T>,
+	_phantom: PhantomData<fn() -> K>,
+}
+
+impl<K: Key, T: Clone> EphemeralMap<K, T> {
+	pub fn with_capacity(default: T, capacity: usize) -> EphemeralMap<K, T> {
+		EphemeralMap {
+			values: vec![default; capacity],
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<K: Key, T: Clone> crate::Map<K> for EphemeralMap<K, T> {
+	type Value = T;
+	type Ref<'a>
+	where
+		T: 'a,
+	= &'a T;
+	fn get<'a>(&'a self, k: K) -> Self::Ref<'a>
+	where
+		T: 'a,
+	{
+		&self.values[k.index()]
+	}
+
+	fn dense_index(k: &K) -> Option<usize> {
+		Some(k.index())
+	}
+}
+
+impl<K: Key, T: Clone> crate::MapMut<K> for EphemeralMap<K, T> {
+	type RefMut<'a>
+	where
+		T: 'a,
+	= &'a mut T;
+	fn get_mut(&mut self, k: K) -> Self::RefMut<'_> {
+		&mut self.values[k.index()]
+	}
+}
 
 #[cfg(test)]
 mod tests {
@@ -149,5 +245,58 @@ mod tests {
 		for key in domain.keys() {
 			assert_eq!(key, domain[key]);
 		}
+
+		assert_eq!(domain.swap_remove(0), 0);
+		assert_eq!(domain.len(), 1);
+		assert_eq!(domain[0], 1);
+		assert_domain_invariants(&domain);
+	}
+
+	#[test]
+	fn map_clear_to_default() {
+		use crate::{Map, MapMut};
+		let mut map = super::Map::with_capacity(7, 4);
+		for key in 0..10 {
+			*map.get_mut(key) = key;
+		}
+		map.clear_to_default();
+		for key in 0..20 {
+			assert_eq!(*map.get(key), 7);
+		}
+		*map.get_mut(3) = 3;
+		assert_eq!(*map.get(3), 3);
+		assert_eq!(*map.get(2), 7);
+	}
+
+	#[test]
+	fn map_iter() {
+		use crate::MapMut;
+		let mut map = super::Map::with_capacity(0, 4);
+		assert_eq!(map.iter().next(), None);
+		*map.get_mut(4) = 40;
+		*map.get_mut(1) = 10;
+		let entries: Vec<_> = map.iter().map(|(k, v)| (k, *v)).collect();
+		assert_eq!(entries, vec![(0, 0), (1, 10), (2, 0), (3, 0), (4, 40)]);
+	}
+
+	#[test]
+	fn ephemeral_map() {
+		use crate::{Map, MapMut};
+		let mut map = super::EphemeralMap::with_capacity(7, 4);
+		for key in 0..4 {
+			assert_eq!(*map.get(key), 7);
+			*map.get_mut(key) = key;
+		}
+		for key in 0..4 {
+			assert_eq!(*map.get(key), key);
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn ephemeral_map_out_of_range() {
+		use crate::MapMut;
+		let mut map = super::EphemeralMap::with_capacity(7, 4);
+		*map.get_mut(4) = 4;
 	}
 }
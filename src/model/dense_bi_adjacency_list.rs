@@ -1,6 +1,7 @@
 //! Module implementing a dense bi-adjacency list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use crate::{Digraph, InGraph, InsertGraph, OutGraph};
 
@@ -28,7 +29,7 @@ pub type OutEdges<'a> = std::iter::Cloned<std::slice::Iter<'a, Edge>>;
 pub type InEdges<'a> = std::iter::Cloned<std::slice::Iter<'a, Edge>>;
 
 /// Dense bi-adjacency list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DenseBiAdjacencyList {
 	verts: dense::Domain<Vert, (Vec<Edge>, Vec<Edge>)>,
 	edges: dense::Domain<Edge, (Vert, Vert)>,
@@ -42,6 +43,11 @@ impl Digraph for DenseBiAdjacencyList {
 		self.edges[*e.borrow()]
 	}
 
+	// After a removal, the key of the last edge is out of range.
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -98,21 +104,69 @@ impl InsertGraph for DenseBiAdjacencyList {
 		self.verts[head].1.push(e);
 		e
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
+}
+
+impl DenseBiAdjacencyList {
+	/// Removes an edge. To keep edge keys dense, the most recently inserted edge
+	/// takes over the key of the removed edge, so edge keys are not stable across
+	/// removal.
+	pub fn remove_edge(&mut self, e: Edge) {
+		let last = Edge::from(self.edges.len() - 1);
+		let (tail, head) = self.edges.swap_remove(e);
+		swap_remove_item(&mut self.verts[tail].0, e);
+		swap_remove_item(&mut self.verts[head].1, e);
+		if last != e {
+			let (tail, head) = self.edges[e];
+			replace_item(&mut self.verts[tail].0, last, e);
+			replace_item(&mut self.verts[head].1, last, e);
+		}
+	}
 }
 
+fn swap_remove_item(edges: &mut Vec<Edge>, e: Edge) {
+	let position = edges.iter().position(|d| *d == e);
+	edges.swap_remove(position.expect("edge is adjacent"));
+}
+
+fn replace_item(edges: &mut [Edge], from: Edge, to: Edge) {
+	let position = edges.iter().position(|d| *d == from);
+	edges[position.expect("edge is adjacent")] = to;
+}
+
+impl_extend!(DenseBiAdjacencyList);
+
 impl<G: Digraph> From<&G> for DenseBiAdjacencyList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Display for DenseBiAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::model::test_graph::*;
+	use crate::ExactSizeDigraph;
 	use proptest::proptest;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &DenseBiAdjacencyList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = DenseBiAdjacencyList::isomorphic_from(&g);
@@ -125,6 +179,19 @@ mod tests {
 			assert_all_bi_graph_invariants(&g_prime);
 		}
 
+		#[test]
+		fn remove_edge(g: TestGraph) {
+			let mut g_prime = DenseBiAdjacencyList::from(&g);
+			while let Some(e) = g_prime.edges().next() {
+				let size = g_prime.size();
+				let last = g_prime.edges().next_back().unwrap();
+				g_prime.remove_edge(e);
+				assert_eq!(g_prime.size(), size - 1);
+				assert_eq!(g_prime.try_endpoints(last), None);
+				assert_all_bi_graph_invariants(&g_prime);
+			}
+		}
+
 		#[test]
 		fn vert_map(g: TestGraph) {
 			let g_prime = DenseBiAdjacencyList::from(&g);
@@ -136,5 +203,22 @@ mod tests {
 			let g_prime = DenseBiAdjacencyList::from(&g);
 			assert_edge_map_works(g_prime);
 		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = DenseBiAdjacencyList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = DenseBiAdjacencyList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<DenseBiAdjacencyList>(&g);
+		}
 	}
 }
@@ -1,6 +1,7 @@
 //! Module implementing a dense edge list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use super::dense;
 
@@ -24,7 +25,7 @@ pub type EphemeralVertMap<'a, T> = dense::EphemeralMap<Vert, T>;
 pub type EphemeralEdgeMap<'a, T> = dense::EphemeralMap<Edge, T>;
 
 /// Dense edge list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DenseEdgeList {
 	verts: dense::Domain<Vert>,
 	edges: dense::Domain<Edge, (Vert, Vert)>,
@@ -38,6 +39,11 @@ impl Digraph for DenseEdgeList {
 		self.edges[*e.borrow()]
 	}
 
+	// After a clear, every previous edge is out of range.
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -77,14 +83,29 @@ impl InsertGraph for DenseEdgeList {
 	fn insert_edge(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge {
 		self.edges.insert((tail, head))
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
+impl_extend!(DenseEdgeList);
+
 impl<G: Digraph> From<&G> for DenseEdgeList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Display for DenseEdgeList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -92,6 +113,11 @@ mod tests {
 	use proptest::proptest;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &DenseEdgeList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = DenseEdgeList::isomorphic_from(&g);
@@ -115,5 +141,22 @@ mod tests {
 			let g_prime = DenseEdgeList::from(&g);
 			assert_edge_map_works(g_prime);
 		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = DenseEdgeList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = DenseEdgeList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<DenseEdgeList>(&g);
+		}
 	}
 }
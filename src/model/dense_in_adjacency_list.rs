@@ -1,6 +1,7 @@
 //! Module implementing a dense in-adjacency list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use crate::{Digraph, InGraph, InsertGraph};
 
@@ -26,7 +27,7 @@ pub type EphemeralEdgeMap<'a, T> = dense::EphemeralMap<Edge, T>;
 pub type InEdges<'a> = std::iter::Cloned<std::slice::Iter<'a, Edge>>;
 
 /// Dense in-adjacency list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DenseInAdjacencyList {
 	verts: dense::Domain<Vert, Vec<Edge>>,
 	edges: dense::Domain<Edge, (Vert, Vert)>,
@@ -40,6 +41,11 @@ impl Digraph for DenseInAdjacencyList {
 		self.edges[*e.borrow()]
 	}
 
+	// After a clear, every previous edge is out of range.
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -88,14 +94,29 @@ impl InsertGraph for DenseInAdjacencyList {
 		self.verts[head].push(e);
 		e
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
+impl_extend!(DenseInAdjacencyList);
+
 impl<G: Digraph> From<&G> for DenseInAdjacencyList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Display for DenseInAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -103,6 +124,11 @@ mod tests {
 	use proptest::proptest;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &DenseInAdjacencyList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = DenseInAdjacencyList::isomorphic_from(&g);
@@ -126,5 +152,22 @@ mod tests {
 			let g_prime = DenseInAdjacencyList::from(&g);
 			assert_edge_map_works(g_prime);
 		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = DenseInAdjacencyList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = DenseInAdjacencyList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<DenseInAdjacencyList>(&g);
+		}
 	}
 }
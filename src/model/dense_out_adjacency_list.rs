@@ -1,6 +1,7 @@
 //! Module implementing a dense out-adjacency list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use crate::{Digraph, InsertGraph, OutGraph};
 
@@ -26,7 +27,7 @@ pub type EphemeralEdgeMap<'a, T> = dense::EphemeralMap<Edge, T>;
 pub type OutEdges<'a> = std::iter::Cloned<std::slice::Iter<'a, Edge>>;
 
 /// Dense out-adjacency list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DenseOutAdjacencyList {
 	verts: dense::Domain<Vert, Vec<Edge>>,
 	edges: dense::Domain<Edge, (Vert, Vert)>,
@@ -40,6 +41,11 @@ impl Digraph for DenseOutAdjacencyList {
 		self.edges[*e.borrow()]
 	}
 
+	// After a clear, every previous edge is out of range.
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -88,21 +94,47 @@ impl InsertGraph for DenseOutAdjacencyList {
 		self.verts[tail].push(e);
 		e
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
+impl_extend!(DenseOutAdjacencyList);
+
 impl<G: Digraph> From<&G> for DenseOutAdjacencyList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Display for DenseOutAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::model::test_graph::*;
 	use proptest::proptest;
 
+	#[test]
+	#[should_panic(expected = "endpoint out of range")]
+	fn from_edges_out_of_range() {
+		DenseOutAdjacencyList::from_edges(2, vec![(0, 2)]);
+	}
+
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &DenseOutAdjacencyList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = DenseOutAdjacencyList::isomorphic_from(&g);
@@ -115,6 +147,11 @@ mod tests {
 			assert_all_out_graph_invariants(&g_prime);
 		}
 
+		#[test]
+		fn from_edges(g: TestGraph) {
+			assert_from_edges_works::<DenseOutAdjacencyList>(&g);
+		}
+
 		#[test]
 		fn vert_map(g: TestGraph) {
 			let g_prime = DenseOutAdjacencyList::from(&g);
@@ -126,5 +163,22 @@ mod tests {
 			let g_prime = DenseOutAdjacencyList::from(&g);
 			assert_edge_map_works(g_prime);
 		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = DenseOutAdjacencyList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = DenseOutAdjacencyList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<DenseOutAdjacencyList>(&g);
+		}
 	}
 }
@@ -0,0 +1,238 @@
+//! Module implementing a view of a subgraph selected by predicates.
+
+use std::borrow::Borrow;
+
+use crate::{Digraph, InGraph, OutGraph};
+
+/// View of the subgraph of a graph containing the vertices satisfying a vertex
+/// predicate and the edges satisfying an edge predicate whose endpoints are
+/// both present. Vertices and edges are shared with the underlying graph.
+pub struct Filtered<'g, G: ?Sized, VP, EP> {
+	graph: &'g G,
+	vert_predicate: VP,
+	edge_predicate: EP,
+}
+
+impl<'g, G: Digraph + ?Sized, VP: Fn(G::Vert) -> bool, EP: Fn(G::Edge) -> bool>
+	Filtered<'g, G, VP, EP>
+{
+	/// Constructs a view of the subgraph selected by the given predicates.
+	pub fn new(graph: &'g G, vert_predicate: VP, edge_predicate: EP) -> Self {
+		Filtered {
+			graph,
+			vert_predicate,
+			edge_predicate,
+		}
+	}
+
+	/// Returns the underlying graph.
+	pub fn inner(&self) -> &'g G {
+		self.graph
+	}
+
+	/// Returns whether a vertex of the underlying graph is present.
+	pub fn contains_vert(&self, v: G::Vert) -> bool {
+		(self.vert_predicate)(v)
+	}
+
+	/// Returns whether an edge of the underlying graph is present.
+	pub fn contains_edge(&self, e: G::Edge) -> bool {
+		if !(self.edge_predicate)(e) {
+			return false;
+		}
+		let (tail, head) = self.graph.endpoints(e);
+		self.contains_vert(tail) && self.contains_vert(head)
+	}
+}
+
+/// Iterator over the vertices of a filtered graph.
+pub struct FilteredVerts<'a, 'g, G: ?Sized, VP, EP, I> {
+	filtered: &'a Filtered<'g, G, VP, EP>,
+	iter: I,
+}
+
+impl<'a, 'g, G: ?Sized, VP, EP, I: Clone> Clone for FilteredVerts<'a, 'g, G, VP, EP, I> {
+	fn clone(&self) -> Self {
+		FilteredVerts {
+			filtered: self.filtered,
+			iter: self.iter.clone(),
+		}
+	}
+}
+
+impl<'a, 'g, G, VP, EP, I> Iterator for FilteredVerts<'a, 'g, G, VP, EP, I>
+where
+	G: Digraph + ?Sized,
+	VP: Fn(G::Vert) -> bool,
+	EP: Fn(G::Edge) -> bool,
+	I: Iterator<Item = G::Vert>,
+{
+	type Item = G::Vert;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let filtered = self.filtered;
+		self.iter.find(|v| filtered.contains_vert(*v))
+	}
+}
+
+/// Iterator over the edges of a filtered graph.
+pub struct FilteredEdges<'a, 'g, G: ?Sized, VP, EP, I> {
+	filtered: &'a Filtered<'g, G, VP, EP>,
+	iter: I,
+}
+
+impl<'a, 'g, G: ?Sized, VP, EP, I: Clone> Clone for FilteredEdges<'a, 'g, G, VP, EP, I> {
+	fn clone(&self) -> Self {
+		FilteredEdges {
+			filtered: self.filtered,
+			iter: self.iter.clone(),
+		}
+	}
+}
+
+impl<'a, 'g, G, VP, EP, I> Iterator for FilteredEdges<'a, 'g, G, VP, EP, I>
+where
+	G: Digraph + ?Sized,
+	VP: Fn(G::Vert) -> bool,
+	EP: Fn(G::Edge) -> bool,
+	I: Iterator<Item = G::Edge>,
+{
+	type Item = G::Edge;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let filtered = self.filtered;
+		self.iter.find(|e| filtered.contains_edge(*e))
+	}
+}
+
+impl<'g, G, VP, EP> Digraph for Filtered<'g, G, VP, EP>
+where
+	G: Digraph + ?Sized,
+	VP: Fn(G::Vert) -> bool,
+	EP: Fn(G::Edge) -> bool,
+{
+	type Vert = G::Vert;
+	type Edge = G::Edge;
+
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		self.graph.endpoints(e)
+	}
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let e = *e.borrow();
+		let (tail, head) = self.graph.try_endpoints(e)?;
+		if (self.edge_predicate)(e) && self.contains_vert(tail) && self.contains_vert(head) {
+			Some((tail, head))
+		} else {
+			None
+		}
+	}
+	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.graph.tail(e)
+	}
+	fn head(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.graph.head(e)
+	}
+
+	type Verts<'a> = FilteredVerts<'a, 'g, G, VP, EP, G::Verts<'a>>;
+	fn verts(&self) -> Self::Verts<'_> {
+		FilteredVerts {
+			filtered: self,
+			iter: self.graph.verts(),
+		}
+	}
+
+	type Edges<'a> = FilteredEdges<'a, 'g, G, VP, EP, G::Edges<'a>>;
+	fn edges(&self) -> Self::Edges<'_> {
+		FilteredEdges {
+			filtered: self,
+			iter: self.graph.edges(),
+		}
+	}
+
+	type VertMap<T: Clone> = G::EphemeralVertMap<'g, T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		self.graph.ephemeral_vert_map(default)
+	}
+
+	type EdgeMap<T: Clone> = G::EphemeralEdgeMap<'g, T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		self.graph.ephemeral_edge_map(default)
+	}
+
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		self.vert_map(default)
+	}
+
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		self.edge_map(default)
+	}
+}
+
+impl<'g, G, VP, EP> OutGraph for Filtered<'g, G, VP, EP>
+where
+	G: OutGraph + ?Sized,
+	VP: Fn(G::Vert) -> bool,
+	EP: Fn(G::Edge) -> bool,
+{
+	type OutEdges<'a> = FilteredEdges<'a, 'g, G, VP, EP, G::OutEdges<'a>>;
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		FilteredEdges {
+			filtered: self,
+			iter: self.graph.out_edges(v),
+		}
+	}
+}
+
+impl<'g, G, VP, EP> InGraph for Filtered<'g, G, VP, EP>
+where
+	G: InGraph + ?Sized,
+	VP: Fn(G::Vert) -> bool,
+	EP: Fn(G::Edge) -> bool,
+{
+	type InEdges<'a> = FilteredEdges<'a, 'g, G, VP, EP, G::InEdges<'a>>;
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		FilteredEdges {
+			filtered: self,
+			iter: self.graph.in_edges(v),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::DenseBiAdjacencyList;
+	use proptest::proptest;
+	use std::collections::HashSet;
+
+	proptest! {
+		#[test]
+		fn identity_is_isomorphic(g: TestGraph) {
+			let filtered = Filtered::new(&g, |_| true, |_| true);
+			assert!(g.is_isomorphic_with_maps(&filtered, &|v| v, &|e| e));
+		}
+
+		#[test]
+		fn invariants(g: TestGraph) {
+			let g_prime = DenseBiAdjacencyList::from(&g);
+			let verts: HashSet<_> = g_prime.verts().step_by(3).collect();
+			let edges: HashSet<_> = g_prime.edges().step_by(2).collect();
+			let filtered = Filtered::new(&g_prime, |v| !verts.contains(&v), |e| edges.contains(&e));
+			assert_all_bi_graph_invariants(&filtered);
+		}
+
+		#[test]
+		fn contents(g: TestGraph) {
+			let filtered = Filtered::new(&g, |v| v % 3 != 0, |e| e % 2 == 0);
+			let verts: HashSet<_> = filtered.verts().collect();
+			assert_eq!(verts, g.verts().filter(|v| v % 3 != 0).collect());
+			let edges: HashSet<_> = filtered.edges().collect();
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				let expected = e % 2 == 0 && verts.contains(&tail) && verts.contains(&head);
+				assert_eq!(edges.contains(&e), expected);
+			}
+		}
+	}
+}
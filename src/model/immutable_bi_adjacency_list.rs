@@ -0,0 +1,225 @@
+//! Module implementing an immutable bi-adjacency list.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Display};
+
+use itertools::{Itertools, MapInto};
+use std::ops::Range;
+
+use crate::{
+	map::{self, Map, MapMut},
+	Digraph, Homomorphism, InGraph, OutGraph,
+};
+
+use super::dense::{self, Key};
+
+#[allow(missing_docs)]
+pub type Vert = super::key::DenseVert;
+#[allow(missing_docs)]
+pub type Edge = super::key::DenseEdge;
+#[allow(missing_docs)]
+pub type Verts<'a> = dense::DomainKeys<'a, Vert>;
+#[allow(missing_docs)]
+pub type Edges<'a> = dense::DomainKeys<'a, Edge>;
+#[allow(missing_docs)]
+pub type VertMap<T> = dense::EphemeralMap<Vert, T>;
+#[allow(missing_docs)]
+pub type EdgeMap<T> = dense::EphemeralMap<Edge, T>;
+#[allow(missing_docs)]
+pub type EphemeralVertMap<'a, T> = VertMap<T>;
+#[allow(missing_docs)]
+pub type EphemeralEdgeMap<'a, T> = EdgeMap<T>;
+#[allow(missing_docs)]
+pub type OutEdges<'a> = MapInto<Range<usize>, Edge>;
+#[allow(missing_docs)]
+pub type InEdges<'a> = std::iter::Cloned<std::slice::Iter<'a, Edge>>;
+
+#[derive(Clone, Debug)]
+/// Immutable bi-adjacency list directed graph representation.
+pub struct ImmutableBiAdjacencyList {
+	// Mapping from vertices to the first edge with it as the tail. Edges are
+	// numbered in order of their tails. This also has an extra element mapped to
+	// the size of the graph to facilitate lookups.
+	outs: dense::Domain<Vert, Edge>,
+	// Mapping from edges to their heads.
+	heads: dense::Domain<Edge, Vert>,
+	// Mapping from vertices to the offset in `in_edges` of the first edge with it
+	// as the head. This also has an extra element mapped to the size of the
+	// graph.
+	ins: dense::Domain<Vert, usize>,
+	// Mapping from edges to their tails.
+	tails: dense::Domain<Edge, Vert>,
+	// Permutation of the edges ordered by their heads.
+	in_edges: Vec<Edge>,
+}
+
+impl ImmutableBiAdjacencyList {
+	fn _out_edges(&self, v: Vert) -> OutEdges<'_> {
+		let start = self.outs[v].index();
+		let end = self.outs[(v.index() + 1).into()].index();
+		(start..end).map_into::<Edge>()
+	}
+
+	fn _in_edges(&self, v: Vert) -> InEdges<'_> {
+		let start = self.ins[v];
+		let end = self.ins[(v.index() + 1).into()];
+		self.in_edges[start..end].iter().cloned()
+	}
+}
+
+impl Digraph for ImmutableBiAdjacencyList {
+	type Vert = Vert;
+	type Edge = Edge;
+
+	#[inline]
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		let e = *e.borrow();
+		(self.tails[e], self.heads[e])
+	}
+
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let e = *e.borrow();
+		Some((*self.tails.get(e)?, *self.heads.get(e)?))
+	}
+
+	#[inline]
+	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.tails[*e.borrow()]
+	}
+
+	#[inline]
+	fn head(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.heads[*e.borrow()]
+	}
+
+	type Verts<'a> = Verts<'a>;
+	fn verts(&self) -> Self::Verts<'_> {
+		(0..self.outs.len() - 1).map_into::<Vert>()
+	}
+
+	type Edges<'a> = Edges<'a>;
+	fn edges(&self) -> Self::Edges<'_> {
+		self.heads.keys()
+	}
+
+	type VertMap<T: Clone> = VertMap<T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		VertMap::with_capacity(default, self.outs.len() - 1)
+	}
+
+	type EdgeMap<T: Clone> = EdgeMap<T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		EdgeMap::with_capacity(default, self.heads.len())
+	}
+
+	type EphemeralVertMap<'a, T: Clone> = EphemeralVertMap<'a, T>;
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		self.vert_map(default)
+	}
+
+	type EphemeralEdgeMap<'a, T: Clone> = EphemeralEdgeMap<'a, T>;
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		self.edge_map(default)
+	}
+}
+
+impl OutGraph for ImmutableBiAdjacencyList {
+	type OutEdges<'a> = OutEdges<'a>;
+
+	#[inline]
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		self._out_edges(*v.borrow())
+	}
+}
+
+impl InGraph for ImmutableBiAdjacencyList {
+	type InEdges<'a> = InEdges<'a>;
+
+	#[inline]
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		self._in_edges(*v.borrow())
+	}
+}
+
+impl ImmutableBiAdjacencyList {
+	/// Constructs a graph isomorphic to the given graph and returns it along with
+	/// mappings from the given graph's vertices and edges to those in the new
+	/// graph.
+	fn isomorphic_from<G: OutGraph + InGraph>(from: &G) -> (Self, Homomorphism<'_, G, Self>) {
+		let mut vmap = from.ephemeral_vert_map(None);
+		for (order, v) in from.verts().enumerate() {
+			*vmap.get_mut(v) = Some(order.into());
+		}
+		let mut emap = from.ephemeral_edge_map(None);
+		let mut outs = dense::Domain::default();
+		let mut heads = dense::Domain::default();
+		let mut tails = dense::Domain::default();
+		for tail in from.verts() {
+			outs.insert(heads.len().into());
+			let tail_prime = vmap.get(tail).borrow().expect("tail in verts");
+			for e in from.out_edges(tail) {
+				let head = from.head(e);
+				let e_prime = heads.len().into();
+				*emap.get_mut(e) = Some(e_prime);
+				heads.insert(vmap.get(head).borrow().expect("head in verts"));
+				tails.insert(tail_prime);
+			}
+		}
+		outs.insert(heads.len().into());
+		let mut ins = dense::Domain::default();
+		let mut in_edges = Vec::with_capacity(heads.len());
+		for head in from.verts() {
+			ins.insert(in_edges.len());
+			for e in from.in_edges(head) {
+				in_edges.push(emap.get(e).borrow().expect("edge in edges"));
+			}
+		}
+		ins.insert(in_edges.len());
+		let g = ImmutableBiAdjacencyList {
+			outs,
+			heads,
+			ins,
+			tails,
+			in_edges,
+		};
+		(
+			g,
+			Homomorphism::new(map::Unwrap::new(vmap), map::Unwrap::new(emap)),
+		)
+	}
+}
+
+impl<G: OutGraph + InGraph> From<&G> for ImmutableBiAdjacencyList {
+	fn from(from: &G) -> Self {
+		Self::isomorphic_from(from).0
+	}
+}
+
+impl Display for ImmutableBiAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+
+	proptest! {
+		#[test]
+		fn isomorphic_from(g: TestGraph) {
+			let g_bi = crate::DenseBiAdjacencyList::from(&g);
+			let (g_prime, homomorphism) = ImmutableBiAdjacencyList::isomorphic_from(&g_bi);
+			assert!(g_bi.is_isomorphic_with_maps(&g_prime, homomorphism.vert_map(), homomorphism.edge_map()));
+		}
+
+		#[test]
+		fn invariants(g: TestGraph) {
+			let g_bi = crate::DenseBiAdjacencyList::from(&g);
+			let g_prime = ImmutableBiAdjacencyList::from(&g_bi);
+			assert_all_bi_graph_invariants(&g_prime);
+		}
+	}
+}
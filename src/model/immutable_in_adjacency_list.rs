@@ -1,6 +1,7 @@
 //! Module implementing an immutable in-adjacency list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use itertools::{Itertools, MapInto};
 use std::ops::Range;
@@ -31,7 +32,7 @@ pub type EphemeralEdgeMap<'a, T> = EdgeMap<T>;
 #[allow(missing_docs)]
 pub type InEdges<'a> = MapInto<Range<usize>, Edge>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 /// Immutable in-adjacency list directed graph representation.
 pub struct ImmutableInAdjacencyList {
 	// Mapping from vertices to the first edge with it as the tail. This also
@@ -71,6 +72,12 @@ impl Digraph for ImmutableInAdjacencyList {
 		self._endpoints(*e.borrow())
 	}
 
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let e = *e.borrow();
+		let tail = *self.tails.get(e)?;
+		Some((tail, self._head(e)))
+	}
+
 	#[inline]
 	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
 		self._tail(*e.borrow())
@@ -157,6 +164,12 @@ impl<G: InGraph> From<&G> for ImmutableInAdjacencyList {
 	}
 }
 
+impl Display for ImmutableInAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,6 +1,7 @@
 //! Module implementing an immutable out-adjacency list.
 
 use std::borrow::Borrow;
+use std::fmt::{self, Display};
 
 use itertools::{Itertools, MapInto};
 use std::ops::Range;
@@ -31,7 +32,7 @@ pub type EphemeralEdgeMap<'a, T> = EdgeMap<T>;
 #[allow(missing_docs)]
 pub type OutEdges<'a> = MapInto<Range<usize>, Edge>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 /// Immutable out-adjacency list directed graph representation.
 pub struct ImmutableOutAdjacencyList {
 	// Mapping from vertices to the first edge with it as the tail. This also
@@ -71,6 +72,12 @@ impl Digraph for ImmutableOutAdjacencyList {
 		self._endpoints(*e.borrow())
 	}
 
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let e = *e.borrow();
+		let head = *self.heads.get(e)?;
+		Some((self._tail(e), head))
+	}
+
 	#[inline]
 	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
 		self._tail(*e.borrow())
@@ -149,6 +156,39 @@ impl ImmutableOutAdjacencyList {
 			Homomorphism::new(map::Unwrap::new(vmap), map::Unwrap::new(emap)),
 		)
 	}
+
+	/// Constructs a graph with `order` vertices and edges between them given by
+	/// the indices of their endpoints, without building an intermediate graph.
+	/// The edges must be grouped by ascending tail, and the edges with each tail
+	/// keep their relative order.
+	///
+	/// # Panics
+	/// Panics if an endpoint is not less than `order`. In debug builds, also
+	/// panics if the edges are not sorted by tail.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = ImmutableOutAdjacencyList::from_sorted_edges(3, vec![(0, 1), (0, 2), (2, 1)]);
+	/// assert_eq!(g.verts().count(), 3);
+	/// assert_eq!(g.edges().count(), 3);
+	/// ```
+	pub fn from_sorted_edges(order: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+		let mut outs = dense::Domain::default();
+		let mut heads = dense::Domain::default();
+		for (tail, head) in edges {
+			assert!(tail < order && head < order, "endpoint out of range");
+			debug_assert!(tail + 1 >= outs.len(), "edges not sorted by tail");
+			while outs.len() <= tail {
+				outs.insert(heads.len().into());
+			}
+			heads.insert(head.into());
+		}
+		while outs.len() <= order {
+			outs.insert(heads.len().into());
+		}
+		ImmutableOutAdjacencyList { outs, heads }
+	}
 }
 
 impl<G: OutGraph> From<&G> for ImmutableOutAdjacencyList {
@@ -157,10 +197,17 @@ impl<G: OutGraph> From<&G> for ImmutableOutAdjacencyList {
 	}
 }
 
+impl Display for ImmutableOutAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::model::test_graph::*;
+	use crate::InsertGraph;
 	use proptest::proptest;
 
 	proptest! {
@@ -171,6 +218,19 @@ mod tests {
 			assert!(g_out.is_isomorphic_with_maps(&g_prime, homomorphism.vert_map(), homomorphism.edge_map()));
 		}
 
+		#[test]
+		fn from_sorted_edges(g: TestGraph) {
+			let mut pairs: Vec<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			pairs.sort_by_key(|(tail, _)| *tail);
+			let order = g.verts().count();
+			let g_out = crate::DenseOutAdjacencyList::from_edges(order, pairs.iter().cloned());
+			let (g_expected, _) = ImmutableOutAdjacencyList::isomorphic_from(&g_out);
+			let g_prime = ImmutableOutAdjacencyList::from_sorted_edges(order, pairs);
+			assert_eq!(g_prime.outs.values(), g_expected.outs.values());
+			assert_eq!(g_prime.heads.values(), g_expected.heads.values());
+			assert_all_out_graph_invariants(&g_prime);
+		}
+
 		#[test]
 		fn invariants(g: TestGraph) {
 			let g_out = crate::DenseOutAdjacencyList::from(&g);
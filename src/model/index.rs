@@ -1,23 +1,47 @@
+use std::convert::TryFrom;
+
+// Integer type underlying indices, widened by the `index_u64` feature for
+// graphs with more than `u32::MAX` vertices or edges.
+#[cfg(not(feature = "index_u64"))]
+type Repr = u32;
+#[cfg(feature = "index_u64")]
+type Repr = u64;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(sif_index_niche, repr(transparent))]
-#[cfg_attr(sif_index_niche, rustc_layout_scalar_valid_range_end(4294967294))] // `std::u32::MAX - 1`
-pub struct Index(u32);
+#[cfg_attr(
+	all(sif_index_niche, not(feature = "index_u64")),
+	rustc_layout_scalar_valid_range_end(4294967294)
+)] // `std::u32::MAX - 1`
+#[cfg_attr(
+	all(sif_index_niche, feature = "index_u64"),
+	rustc_layout_scalar_valid_range_end(18446744073709551614)
+)] // `std::u64::MAX - 1`
+pub struct Index(Repr);
 
 impl From<usize> for Index {
 	#[cfg(sif_index_niche)]
 	fn from(value: usize) -> Self {
-		if value >= std::u32::MAX as usize {
-			panic!("index out of range");
-		}
-		unsafe { Key(value as u32) }
+		assert!(Index::is_valid(value), "index out of range");
+		unsafe { Key(value as Repr) }
 	}
 	#[cfg(not(sif_index_niche))]
 	fn from(value: usize) -> Self {
-		Index(value as u32)
+		assert!(Index::is_valid(value), "index out of range");
+		Index(value as Repr)
 	}
 }
 
 impl Index {
+	// Returns whether a value is representable as an index. With the niche, the
+	// largest value of the underlying integer is reserved.
+	pub fn is_valid(value: usize) -> bool {
+		match Repr::try_from(value) {
+			Ok(value) => !cfg!(sif_index_niche) || value < Repr::MAX,
+			Err(_) => false,
+		}
+	}
+
 	pub fn index(&self) -> usize {
 		self.0 as usize
 	}
@@ -31,4 +55,27 @@ mod tests {
 		use std::mem::sizeof;
 		assert_eq!(sizeof::<Option<super::index>>(), sizeof::<super::Index>());
 	}
+
+	#[cfg(not(feature = "index_u64"))]
+	#[test]
+	fn boundary() {
+		let value = u32::MAX as usize - 1;
+		assert_eq!(super::Index::from(value).index(), value);
+		assert!(!super::Index::is_valid(u32::MAX as usize + 1));
+	}
+
+	#[cfg(not(feature = "index_u64"))]
+	#[test]
+	#[should_panic(expected = "index out of range")]
+	fn overflow() {
+		// This used to silently truncate to zero.
+		let _ = super::Index::from(u32::MAX as usize + 1);
+	}
+
+	#[cfg(feature = "index_u64")]
+	#[test]
+	fn beyond_u32() {
+		let value = u32::MAX as usize + 1;
+		assert_eq!(super::Index::from(value).index(), value);
+	}
 }
@@ -1,5 +1,23 @@
 use super::{dense, index::Index, sparse};
 
+// Number of times a sparse key's slot has been reissued, distinguishing keys
+// which reuse the slot of a removed one. Without the `key_generations`
+// feature, it is empty, so sparse keys are no larger than dense ones but a
+// reused slot is reissued with an equal key.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Generation(#[cfg(feature = "key_generations")] u32);
+
+impl Generation {
+	#[cfg(feature = "key_generations")]
+	fn next(self) -> Self {
+		Generation(self.0.wrapping_add(1))
+	}
+	#[cfg(not(feature = "key_generations"))]
+	fn next(self) -> Self {
+		self
+	}
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct DenseVert(Index);
 
@@ -31,23 +49,31 @@ impl dense::Key for DenseEdge {
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct SparseVert(Index);
+pub struct SparseVert(Index, Generation);
 
 impl From<usize> for SparseVert {
 	fn from(index: usize) -> Self {
-		SparseVert(index.into())
+		SparseVert(index.into(), Generation::default())
 	}
 }
 
-impl sparse::Key for SparseVert {}
+impl sparse::Key for SparseVert {
+	fn next_generation(self) -> Self {
+		SparseVert(self.0, self.1.next())
+	}
+}
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct SparseEdge(Index);
+pub struct SparseEdge(Index, Generation);
 
 impl From<usize> for SparseEdge {
 	fn from(index: usize) -> Self {
-		SparseEdge(index.into())
+		SparseEdge(index.into(), Generation::default())
 	}
 }
 
-impl sparse::Key for SparseEdge {}
+impl sparse::Key for SparseEdge {
+	fn next_generation(self) -> Self {
+		SparseEdge(self.0, self.1.next())
+	}
+}
@@ -1,12 +1,78 @@
-mod dense;
+use std::fmt;
+
+use crate::Digraph;
+
+// Implements `Extend` for an `InsertGraph` by inserting an edge for each pair
+// of vertices in order.
+macro_rules! impl_extend {
+	($graph:ty $(where $param:ident: $bound:path)?) => {
+		impl$(<$param: $bound>)? Extend<(
+			<$graph as crate::Digraph>::Vert,
+			<$graph as crate::Digraph>::Vert,
+		)> for $graph
+		{
+			fn extend<I>(&mut self, iter: I)
+			where
+				I: IntoIterator<Item = (<Self as crate::Digraph>::Vert, <Self as crate::Digraph>::Vert)>,
+			{
+				for (tail, head) in iter {
+					crate::InsertGraph::insert_edge(self, tail, head);
+				}
+			}
+		}
+	};
+}
+
+// Implements the capacity methods of `InsertGraph` for a model whose vertices
+// and edges are stored in `verts` and `edges` domains. Expands to items of the
+// `InsertGraph` impl.
+macro_rules! impl_domain_capacity {
+	() => {
+		fn reserve_verts(&mut self, additional: usize) {
+			self.verts.reserve(additional);
+		}
+
+		fn reserve_edges(&mut self, additional: usize) {
+			self.edges.reserve(additional);
+		}
+
+		fn try_insert_vert(&mut self) -> Result<Self::Vert, crate::CapacityError> {
+			if self.verts.is_full() {
+				return Err(crate::CapacityError);
+			}
+			Ok(self.insert_vert())
+		}
+
+		fn try_insert_edge(
+			&mut self,
+			tail: Self::Vert,
+			head: Self::Vert,
+		) -> Result<Self::Edge, crate::CapacityError> {
+			if self.edges.is_full() {
+				return Err(crate::CapacityError);
+			}
+			Ok(self.insert_edge(tail, head))
+		}
+	};
+}
+
+pub mod bit_adjacency_matrix;
+pub mod cartesian_product;
+pub(crate) mod dense;
 pub mod dense_bi_adjacency_list;
 pub mod dense_edge_list;
 pub mod dense_in_adjacency_list;
 pub mod dense_out_adjacency_list;
+pub mod filtered;
+pub mod immutable_bi_adjacency_list;
 pub mod immutable_in_adjacency_list;
 pub mod immutable_out_adjacency_list;
 pub(crate) mod index;
 mod key;
+pub mod reversed;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod simple_graph;
 mod sparse;
 pub mod sparse_bi_adjacency_list;
 pub mod sparse_edge_list;
@@ -17,13 +83,73 @@ pub mod tensor_product;
 #[cfg(test)]
 pub mod test_graph;
 
+pub use bit_adjacency_matrix::BitAdjacencyMatrix;
+pub use cartesian_product::CartesianProduct;
 pub use dense_bi_adjacency_list::DenseBiAdjacencyList;
 pub use dense_edge_list::DenseEdgeList;
 pub use dense_in_adjacency_list::DenseInAdjacencyList;
 pub use dense_out_adjacency_list::DenseOutAdjacencyList;
+pub use filtered::Filtered;
+pub use immutable_bi_adjacency_list::ImmutableBiAdjacencyList;
 pub use immutable_in_adjacency_list::ImmutableInAdjacencyList;
 pub use immutable_out_adjacency_list::ImmutableOutAdjacencyList;
+pub use reversed::Reversed;
+pub use simple_graph::SimpleGraph;
 pub use sparse_bi_adjacency_list::SparseBiAdjacencyList;
-pub use sparse_edge_list::SparseEdgeList;
+pub use sparse_edge_list::{ParseEdgeListError, SparseEdgeList};
 pub use sparse_in_adjacency_list::SparseInAdjacencyList;
 pub use sparse_out_adjacency_list::SparseOutAdjacencyList;
+
+/// Writes the order and size of a graph, for implementing `Display`.
+pub(crate) fn fmt_summary(g: &impl Digraph, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	write!(
+		f,
+		"graph of order {} and size {}",
+		g.verts().count(),
+		g.edges().count()
+	)
+}
+
+/// Writes the vertices and edges of a graph in sorted order, for implementing
+/// `Debug` on graphs whose storage iterates in an unstable order.
+pub(crate) fn fmt_sorted(name: &str, g: &impl Digraph, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	let mut verts: Vec<_> = g.verts().collect();
+	verts.sort_unstable();
+	let mut edges: Vec<_> = g.edges().map(|e| (e, g.endpoints(e))).collect();
+	edges.sort_unstable();
+	f.debug_struct(name)
+		.field("verts", &verts)
+		.field("edges", &edges)
+		.finish()
+}
+
+// Compile-time checks that graphs and their maps can be shared across threads.
+fn _assert_send_sync<T: Send + Sync>() {}
+fn _assert_models_send_sync() {
+	_assert_send_sync::<BitAdjacencyMatrix>();
+	_assert_send_sync::<DenseBiAdjacencyList>();
+	_assert_send_sync::<DenseEdgeList>();
+	_assert_send_sync::<DenseInAdjacencyList>();
+	_assert_send_sync::<DenseOutAdjacencyList>();
+	_assert_send_sync::<ImmutableBiAdjacencyList>();
+	_assert_send_sync::<ImmutableInAdjacencyList>();
+	_assert_send_sync::<ImmutableOutAdjacencyList>();
+	_assert_send_sync::<SparseBiAdjacencyList>();
+	_assert_send_sync::<SparseEdgeList>();
+	_assert_send_sync::<SparseInAdjacencyList>();
+	_assert_send_sync::<SparseOutAdjacencyList>();
+	_assert_send_sync::<dense_out_adjacency_list::VertMap<usize>>();
+	_assert_send_sync::<dense_out_adjacency_list::EdgeMap<usize>>();
+	_assert_send_sync::<dense_out_adjacency_list::EphemeralVertMap<'static, usize>>();
+	_assert_send_sync::<sparse_bi_adjacency_list::VertMap<usize>>();
+	_assert_send_sync::<sparse_bi_adjacency_list::EdgeMap<usize>>();
+	_assert_send_sync::<sparse_bi_adjacency_list::EphemeralVertMap<'static, usize>>();
+	use dense_out_adjacency_list::Vert;
+	_assert_send_sync::<
+		tensor_product::VertMap<DenseOutAdjacencyList, DenseOutAdjacencyList, usize>,
+	>();
+	_assert_send_sync::<crate::map::BitVertMap<Vert>>();
+	_assert_send_sync::<
+		crate::BinaryHeap<Vert, usize, dense::EphemeralMap<Vert, Option<index::Index>>>,
+	>();
+}
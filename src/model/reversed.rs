@@ -0,0 +1,132 @@
+//! Module implementing a view of a graph with its edges reversed.
+
+use std::borrow::Borrow;
+
+use crate::{Digraph, InGraph, OutGraph};
+
+/// View of a graph with the direction of every edge reversed. Vertices and
+/// edges are shared with the underlying graph.
+pub struct Reversed<'g, G: ?Sized>(&'g G);
+
+impl<'g, G: ?Sized> Reversed<'g, G> {
+	/// Constructs a view of a graph with its edges reversed.
+	pub fn new(g: &'g G) -> Self {
+		Reversed(g)
+	}
+
+	/// Returns the underlying graph.
+	pub fn inner(&self) -> &'g G {
+		self.0
+	}
+}
+
+impl<'g, G: Digraph + ?Sized> Digraph for Reversed<'g, G> {
+	type Vert = G::Vert;
+	type Edge = G::Edge;
+
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		let (tail, head) = self.0.endpoints(e);
+		(head, tail)
+	}
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		let (tail, head) = self.0.try_endpoints(e)?;
+		Some((head, tail))
+	}
+	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.0.head(e)
+	}
+	fn head(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.0.tail(e)
+	}
+
+	type Verts<'a> = G::Verts<'a>;
+	fn verts(&self) -> Self::Verts<'_> {
+		self.0.verts()
+	}
+
+	type Edges<'a> = G::Edges<'a>;
+	fn edges(&self) -> Self::Edges<'_> {
+		self.0.edges()
+	}
+
+	type VertMap<T: Clone> = G::EphemeralVertMap<'g, T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		self.0.ephemeral_vert_map(default)
+	}
+
+	type EdgeMap<T: Clone> = G::EphemeralEdgeMap<'g, T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		self.0.ephemeral_edge_map(default)
+	}
+
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		self.vert_map(default)
+	}
+
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		self.edge_map(default)
+	}
+}
+
+impl<'g, G: InGraph + ?Sized> OutGraph for Reversed<'g, G> {
+	type OutEdges<'a> = G::InEdges<'a>;
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		self.0.in_edges(v)
+	}
+}
+
+impl<'g, G: OutGraph + ?Sized> InGraph for Reversed<'g, G> {
+	type InEdges<'a> = G::OutEdges<'a>;
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		self.0.out_edges(v)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+
+	proptest! {
+		#[test]
+		fn invariants(g: TestGraph) {
+			assert_all_digraph_invariants(&g.reversed());
+		}
+
+		#[test]
+		fn bi_invariants(g: TestGraph) {
+			let g_prime = crate::DenseBiAdjacencyList::from(&g);
+			assert_all_bi_graph_invariants(&g_prime.reversed());
+		}
+
+		#[test]
+		fn endpoints(g: TestGraph) {
+			let reversed = g.reversed();
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				assert_eq!(reversed.endpoints(e), (head, tail));
+			}
+		}
+
+		#[test]
+		fn depth_first_out(g: TestGraph) {
+			use crate::DepthFirstEvent::*;
+			let g_prime = crate::DenseInAdjacencyList::from(&g);
+			let reversed = g_prime.reversed();
+			let mut opened = Vec::new();
+			for event in reversed.depth_first_out() {
+				if let OpenEdge(e) = event {
+					opened.push(e);
+				}
+			}
+			let mut expected = Vec::new();
+			for event in g_prime.depth_first_in() {
+				if let OpenEdge(e) = event {
+					expected.push(e);
+				}
+			}
+			assert_eq!(opened, expected);
+		}
+	}
+}
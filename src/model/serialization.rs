@@ -0,0 +1,128 @@
+//! Module implementing serialization of graphs as edge lists. Only the
+//! structure of a graph is preserved, not its vertex and edge keys.
+//!
+//! The order of a graph is read before any of its edges, so a short input
+//! could otherwise claim billions of isolated vertices. Deserialization
+//! rejects orders above `MAX_ORDER` before inserting anything.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DenseOutAdjacencyList, Digraph, InsertGraph, SparseBiAdjacencyList};
+
+// Largest order accepted when deserializing a graph.
+const MAX_ORDER: usize = 1 << 24;
+
+// Serialized form of a graph, in which vertices are numbered densely in the
+// order they are iterated.
+#[derive(Serialize, Deserialize)]
+struct EdgeList {
+	order: usize,
+	edges: Vec<(usize, usize)>,
+}
+
+impl EdgeList {
+	fn from_graph<G: Digraph>(g: &G) -> Self {
+		EdgeList {
+			order: g.verts().count(),
+			edges: g.to_edge_pairs(),
+		}
+	}
+
+	fn into_graph<G: InsertGraph, E: de::Error>(self) -> Result<G, E> {
+		if self.order > MAX_ORDER {
+			return Err(E::custom("order out of range"));
+		}
+		let mut g = G::new();
+		let mut verts = Vec::new();
+		for _ in 0..self.order {
+			verts.push(g.try_insert_vert().map_err(E::custom)?);
+		}
+		for (tail, head) in self.edges {
+			match (verts.get(tail), verts.get(head)) {
+				(Some(tail), Some(head)) => g.insert_edge(*tail, *head),
+				_ => return Err(E::custom("edge endpoint out of range")),
+			};
+		}
+		Ok(g)
+	}
+}
+
+impl Serialize for SparseBiAdjacencyList {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		EdgeList::from_graph(self).serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for SparseBiAdjacencyList {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		EdgeList::deserialize(deserializer)?.into_graph()
+	}
+}
+
+impl Serialize for DenseOutAdjacencyList {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		EdgeList::from_graph(self).serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for DenseOutAdjacencyList {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		EdgeList::deserialize(deserializer)?.into_graph()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use proptest::proptest;
+	use std::collections::HashMap;
+
+	// Asserts that a graph round-trips to an isomorphic graph. Deserialized keys
+	// are assigned in the order in which the original was iterated.
+	fn assert_round_trip<G>(g: &G)
+	where
+		G: InsertGraph + Serialize + for<'de> Deserialize<'de>,
+		G::Vert: From<usize>,
+		G::Edge: From<usize>,
+	{
+		let json = serde_json::to_string(g).unwrap();
+		let g_prime: G = serde_json::from_str(&json).unwrap();
+		let verts: HashMap<_, _> = g.verts().enumerate().map(|(i, v)| (v, i)).collect();
+		let edges: HashMap<_, _> = g.edges().enumerate().map(|(i, e)| (e, i)).collect();
+		assert!(g.is_isomorphic_with_maps(
+			&g_prime,
+			&|v| G::Vert::from(verts[&v]),
+			&|e| G::Edge::from(edges[&e])
+		));
+	}
+
+	proptest! {
+		#[test]
+		fn sparse_bi_adjacency_list(g: TestGraph) {
+			assert_round_trip(&SparseBiAdjacencyList::from(&g));
+		}
+
+		#[test]
+		fn dense_out_adjacency_list(g: TestGraph) {
+			assert_round_trip(&DenseOutAdjacencyList::from(&g));
+		}
+	}
+
+	#[test]
+	fn endpoint_out_of_range() {
+		let result: Result<DenseOutAdjacencyList, _> =
+			serde_json::from_str(r#"{"order": 1, "edges": [[0, 1]]}"#);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn order_out_of_range() {
+		let result: Result<SparseBiAdjacencyList, _> =
+			serde_json::from_str(r#"{"order": 4000000000, "edges": []}"#);
+		assert!(result.is_err());
+		let json = format!(r#"{{"order": {}, "edges": []}}"#, MAX_ORDER + 1);
+		let result: Result<DenseOutAdjacencyList, _> = serde_json::from_str(&json);
+		assert!(result.is_err());
+	}
+}
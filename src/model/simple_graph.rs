@@ -0,0 +1,255 @@
+//! Module implementing a wrapper which prevents parallel edges.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+
+use crate::{CapacityError, Digraph, InGraph, InsertGraph, OutGraph};
+
+/// Graph which has at most one edge from any vertex to another. Inserting an
+/// edge whose endpoints are already connected returns the existing edge rather
+/// than inserting a parallel one, which is found in constant expected time
+/// using an index of edges by their endpoints.
+///
+/// # Examples
+/// ```
+/// # use sif::*;
+/// let mut g = SimpleGraph::<DenseOutAdjacencyList>::new();
+/// let u = g.insert_vert();
+/// let v = g.insert_vert();
+/// let e = g.insert_edge(u, v);
+/// assert_eq!(g.insert_edge(u, v), e);
+/// assert_eq!(g.edges().count(), 1);
+/// ```
+#[derive(Clone)]
+pub struct SimpleGraph<G: Digraph> {
+	graph: G,
+	edges: HashMap<(G::Vert, G::Vert), G::Edge>,
+}
+
+impl<G: Digraph + Default> Default for SimpleGraph<G> {
+	fn default() -> Self {
+		SimpleGraph {
+			graph: G::default(),
+			edges: HashMap::new(),
+		}
+	}
+}
+
+impl<G: Digraph> SimpleGraph<G> {
+	/// Returns the underlying graph.
+	pub fn inner(&self) -> &G {
+		&self.graph
+	}
+
+	/// Returns the underlying graph, consuming the wrapper.
+	pub fn into_inner(self) -> G {
+		self.graph
+	}
+
+	/// Returns the edge from `tail` to `head`, if there is one.
+	pub fn find_edge(
+		&self,
+		tail: impl Borrow<G::Vert>,
+		head: impl Borrow<G::Vert>,
+	) -> Option<G::Edge> {
+		self.edges.get(&(*tail.borrow(), *head.borrow())).copied()
+	}
+}
+
+impl<G: Digraph> Digraph for SimpleGraph<G> {
+	type Vert = G::Vert;
+	type Edge = G::Edge;
+
+	fn endpoints(&self, e: impl Borrow<Self::Edge>) -> (Self::Vert, Self::Vert) {
+		self.graph.endpoints(e)
+	}
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.graph.try_endpoints(e)
+	}
+	fn tail(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.graph.tail(e)
+	}
+	fn head(&self, e: impl Borrow<Self::Edge>) -> Self::Vert {
+		self.graph.head(e)
+	}
+
+	type Verts<'a> = G::Verts<'a>;
+	fn verts(&self) -> Self::Verts<'_> {
+		self.graph.verts()
+	}
+
+	type Edges<'a> = G::Edges<'a>;
+	fn edges(&self) -> Self::Edges<'_> {
+		self.graph.edges()
+	}
+
+	type VertMap<T: Clone> = G::VertMap<T>;
+	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
+		self.graph.vert_map(default)
+	}
+
+	type EdgeMap<T: Clone> = G::EdgeMap<T>;
+	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
+		self.graph.edge_map(default)
+	}
+
+	type EphemeralVertMap<'a, T: Clone> = G::EphemeralVertMap<'a, T>;
+	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
+		self.graph.ephemeral_vert_map(default)
+	}
+
+	type EphemeralEdgeMap<'a, T: Clone> = G::EphemeralEdgeMap<'a, T>;
+	fn ephemeral_edge_map<T: Clone>(&self, default: T) -> Self::EphemeralEdgeMap<'_, T> {
+		self.graph.ephemeral_edge_map(default)
+	}
+}
+
+impl<G: OutGraph> OutGraph for SimpleGraph<G> {
+	type OutEdges<'a> = G::OutEdges<'a>;
+	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_> {
+		self.graph.out_edges(v)
+	}
+}
+
+impl<G: InGraph> InGraph for SimpleGraph<G> {
+	type InEdges<'a> = G::InEdges<'a>;
+	fn in_edges(&self, v: impl Borrow<Self::Vert>) -> Self::InEdges<'_> {
+		self.graph.in_edges(v)
+	}
+}
+
+impl<G: InsertGraph> InsertGraph for SimpleGraph<G> {
+	fn with_capacity(verts: usize, edges: usize) -> Self {
+		SimpleGraph {
+			graph: G::with_capacity(verts, edges),
+			edges: HashMap::with_capacity(edges),
+		}
+	}
+
+	fn reserve_verts(&mut self, additional: usize) {
+		self.graph.reserve_verts(additional);
+	}
+
+	fn reserve_edges(&mut self, additional: usize) {
+		self.graph.reserve_edges(additional);
+		self.edges.reserve(additional);
+	}
+
+	fn clear(&mut self) {
+		self.graph.clear();
+		self.edges.clear();
+	}
+
+	fn insert_vert(&mut self) -> Self::Vert {
+		self.graph.insert_vert()
+	}
+
+	fn insert_edge(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge {
+		let graph = &mut self.graph;
+		*self
+			.edges
+			.entry((tail, head))
+			.or_insert_with(|| graph.insert_edge(tail, head))
+	}
+
+	fn try_insert_vert(&mut self) -> Result<Self::Vert, CapacityError> {
+		self.graph.try_insert_vert()
+	}
+
+	fn try_insert_edge(
+		&mut self,
+		tail: Self::Vert,
+		head: Self::Vert,
+	) -> Result<Self::Edge, CapacityError> {
+		if let Some(e) = self.find_edge(tail, head) {
+			return Ok(e);
+		}
+		let e = self.graph.try_insert_edge(tail, head)?;
+		self.edges.insert((tail, head), e);
+		Ok(e)
+	}
+}
+
+impl_extend!(SimpleGraph<G> where G: InsertGraph);
+
+// The index is omitted since its order is unstable and it is determined by the
+// graph.
+impl<G: Digraph + Debug> Debug for SimpleGraph<G> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("SimpleGraph").field(&self.graph).finish()
+	}
+}
+
+impl<G: Digraph + Display> Display for SimpleGraph<G> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.graph.fmt(f)
+	}
+}
+
+impl<G: InsertGraph, H: Digraph> From<&H> for SimpleGraph<G> {
+	fn from(from: &H) -> Self {
+		Self::isomorphic_from(from).0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::{
+		DenseBiAdjacencyList, DenseOutAdjacencyList, ExactSizeDigraph, SparseOutAdjacencyList,
+	};
+	use proptest::proptest;
+	use std::collections::HashSet;
+
+	proptest! {
+		#[test]
+		fn invariants(g: TestGraph) {
+			let g_prime = SimpleGraph::<DenseBiAdjacencyList>::from(&g);
+			assert_all_bi_graph_invariants(&g_prime);
+		}
+
+		#[test]
+		fn size(g: TestGraph) {
+			let pairs: HashSet<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			let g_prime = SimpleGraph::<DenseOutAdjacencyList>::from(&g);
+			assert_eq!(g_prime.size(), pairs.len());
+			assert!(g_prime.is_isomorphic(&DenseOutAdjacencyList::from(&g.simple())));
+			for e in g_prime.edges() {
+				let (tail, head) = g_prime.endpoints(e);
+				assert_eq!(g_prime.find_edge(tail, head), Some(e));
+			}
+		}
+
+		#[test]
+		fn vert_map(g: TestGraph) {
+			assert_vert_map_works(SimpleGraph::<DenseOutAdjacencyList>::from(&g));
+		}
+
+		#[test]
+		fn edge_map(g: TestGraph) {
+			assert_edge_map_works(SimpleGraph::<DenseOutAdjacencyList>::from(&g));
+		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			assert_clear_works(SimpleGraph::<DenseOutAdjacencyList>::from(&g));
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			assert_clone_works(&SimpleGraph::<DenseOutAdjacencyList>::from(&g));
+		}
+
+		#[test]
+		fn from_edges(g: TestGraph) {
+			assert_from_edges_works::<SimpleGraph<SparseOutAdjacencyList>>(&g);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<SimpleGraph<SparseOutAdjacencyList>>(&g.simple());
+		}
+	}
+}
@@ -2,7 +2,14 @@ use std::collections::{hash_map, HashMap};
 use std::hash::Hash;
 use std::ops::{Index, IndexMut};
 
-pub trait Key: Clone + Copy + Eq + Hash + From<usize> {}
+pub trait Key: Clone + Copy + Eq + Hash + From<usize> {
+	// Returns a key for the same slot which differs from this one, so that
+	// copies of a removed key are not mistaken for the key which reuses its
+	// slot. Keys without generations return themselves.
+	fn next_generation(self) -> Self {
+		self
+	}
+}
 
 #[derive(Clone, Debug)]
 pub struct Domain<K, T = ()> {
@@ -32,8 +39,28 @@ impl<K: Key, T> Domain<K, T> {
 		self.values.len()
 	}
 
+	#[cfg(test)]
+	pub fn capacity(&self) -> usize {
+		self.values.capacity()
+	}
+
+	pub fn reserve(&mut self, additional: usize) {
+		self.values.reserve(additional);
+	}
+
+	// Every key is freed rather than forgotten, so that their slots are
+	// reissued with a new generation.
+	pub fn clear(&mut self) {
+		self.free.extend(self.values.drain().map(|(key, _)| key));
+	}
+
+	// Returns whether inserting another key would exceed the range of indices.
+	pub fn is_full(&self) -> bool {
+		self.free.is_empty() && !super::index::Index::is_valid(self.next)
+	}
+
 	pub fn insert(&mut self, value: T) -> K {
-		let key = self.free.pop().unwrap_or_else(|| {
+		let key = self.free.pop().map(K::next_generation).unwrap_or_else(|| {
 			let next = self.next + 1;
 			std::mem::replace(&mut self.next, next).into()
 		});
@@ -42,6 +69,22 @@ impl<K: Key, T> Domain<K, T> {
 		key
 	}
 
+	// Releases unused capacity. Keys are not renumbered, and freed keys are
+	// retained even at the end of the range so that their slots are reissued
+	// with a new generation.
+	pub fn shrink_to_fit(&mut self) {
+		self.free.shrink_to_fit();
+		self.values.shrink_to_fit();
+	}
+
+	pub fn get(&self, key: K) -> Option<&T> {
+		self.values.get(&key)
+	}
+
+	pub fn values_mut(&mut self) -> hash_map::ValuesMut<'_, K, T> {
+		self.values.values_mut()
+	}
+
 	pub fn remove(&mut self, key: K) -> T {
 		let result = self.values.remove(&key).expect("key in domain");
 		self.free.push(key);
@@ -58,7 +101,7 @@ impl<K: Key, T: Default> Domain<K, T> {
 impl<K: Key, T> Index<K> for Domain<K, T> {
 	type Output = T;
 	fn index(&self, k: K) -> &Self::Output {
-		&self.values[&k]
+		self.values.get(&k).expect("key in domain")
 	}
 }
 
@@ -90,6 +133,26 @@ impl<K, T> Map<K, T> {
 	}
 }
 
+impl<K: Copy, T> Map<K, T> {
+	/// Returns an iterator over the keys which have been explicitly set, that is,
+	/// accessed mutably, along with their values. Keys which have only ever
+	/// mapped to the default value are not included, and the iteration order is
+	/// unspecified.
+	pub fn entries(&self) -> impl Iterator<Item = (K, &T)> + '_ {
+		self.values.iter().map(|(k, v)| (*k, v))
+	}
+
+	/// Returns the number of keys which have been explicitly set.
+	pub fn set_len(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Resets every key to the default value, retaining the allocated capacity.
+	pub fn clear_to_default(&mut self) {
+		self.values.clear();
+	}
+}
+
 impl<K: Eq + Hash, T> crate::Map<K> for Map<K, T> {
 	type Value = T;
 	type Ref<'a>
@@ -161,4 +224,35 @@ mod tests {
 		assert_eq!(domain.len(), 0);
 		assert_domain_invariants(&domain);
 	}
+
+	#[test]
+	fn map_entries() {
+		use crate::{Map, MapMut};
+		let mut map = super::Map::new(0);
+		assert_eq!(map.set_len(), 0);
+		for key in [3, 1, 4, 1, 5] {
+			*map.get_mut(key) += key;
+		}
+		assert_eq!(*map.get(9), 0);
+		let mut entries: Vec<_> = map.entries().map(|(k, v)| (k, *v)).collect();
+		entries.sort_unstable();
+		assert_eq!(entries, vec![(1, 2), (3, 3), (4, 4), (5, 5)]);
+		assert_eq!(map.set_len(), 4);
+	}
+
+	#[test]
+	fn map_clear_to_default() {
+		use crate::{Map, MapMut};
+		let mut map = super::Map::new(7);
+		for key in 0..10 {
+			*map.get_mut(key) = key;
+		}
+		map.clear_to_default();
+		for key in 0..20 {
+			assert_eq!(*map.get(key), 7);
+		}
+		*map.get_mut(3) = 3;
+		assert_eq!(*map.get(3), 3);
+		assert_eq!(*map.get(2), 7);
+	}
 }
@@ -2,6 +2,7 @@
 
 use std::borrow::Borrow;
 use std::collections::{hash_set, HashSet};
+use std::fmt::{self, Debug, Display};
 
 use crate::{Digraph, InGraph, InsertGraph, OutGraph};
 
@@ -29,7 +30,7 @@ pub type OutEdges<'a> = std::iter::Cloned<hash_set::Iter<'a, Edge>>;
 pub type InEdges<'a> = std::iter::Cloned<hash_set::Iter<'a, Edge>>;
 
 /// Sparse bi-adjacency list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SparseBiAdjacencyList {
 	verts: sparse::Domain<Vert, (HashSet<Edge>, HashSet<Edge>)>,
 	edges: sparse::Domain<Edge, (Vert, Vert)>,
@@ -43,6 +44,10 @@ impl Digraph for SparseBiAdjacencyList {
 		self.edges[*e.borrow()]
 	}
 
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -101,10 +106,19 @@ impl InsertGraph for SparseBiAdjacencyList {
 		debug_assert!(in_inserted);
 		e
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
 impl SparseBiAdjacencyList {
-	/// Removes an edge.
+	/// Removes an edge. Using its key afterwards panics, unless a later edge has
+	/// reused its slot. With the `key_generations` feature, edges inserted later
+	/// always have keys distinct from the removed edge's.
 	pub fn remove_edge(&mut self, e: Edge) {
 		let (tail, head) = self.edges.remove(e);
 		let out_removed = self.verts[tail].0.remove(&e);
@@ -113,7 +127,40 @@ impl SparseBiAdjacencyList {
 		debug_assert!(in_removed);
 	}
 
-	/// Removes a vertex and all adjacent edges.
+	/// Releases unused capacity, such as after removing many vertices and
+	/// edges. Keys are not renumbered, so existing vertices and edges remain
+	/// valid.
+	pub fn shrink_to_fit(&mut self) {
+		for (out_edges, in_edges) in self.verts.values_mut() {
+			out_edges.shrink_to_fit();
+			in_edges.shrink_to_fit();
+		}
+		self.verts.shrink_to_fit();
+		self.edges.shrink_to_fit();
+	}
+
+	/// Removes every edge for which `keep` returns `false`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = SparseBiAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// g.insert_edge(v, u);
+	/// g.retain_edges(|f| f == e);
+	/// assert_eq!(g.edges().collect::<Vec<_>>(), vec![e]);
+	/// ```
+	pub fn retain_edges(&mut self, mut keep: impl FnMut(Edge) -> bool) {
+		let removed: Vec<_> = self.edges().filter(|e| !keep(*e)).collect();
+		for e in removed {
+			self.remove_edge(e);
+		}
+	}
+
+	/// Removes a vertex and all adjacent edges. As with `remove_edge`, their keys
+	/// are only guaranteed not to be reissued with the `key_generations` feature.
 	pub fn remove_vert(&mut self, v: Vert) {
 		let (out_edges, in_edges) = self.verts.remove(v);
 		for e in out_edges {
@@ -136,12 +183,26 @@ impl SparseBiAdjacencyList {
 	}
 }
 
+impl_extend!(SparseBiAdjacencyList);
+
 impl<G: Digraph> From<&G> for SparseBiAdjacencyList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Debug for SparseBiAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_sorted("SparseBiAdjacencyList", self, f)
+	}
+}
+
+impl Display for SparseBiAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -149,6 +210,11 @@ mod tests {
 	use proptest::proptest;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &SparseBiAdjacencyList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = SparseBiAdjacencyList::isomorphic_from(&g);
@@ -173,6 +239,23 @@ mod tests {
 			assert_edge_map_works(g_prime);
 		}
 
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = SparseBiAdjacencyList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = SparseBiAdjacencyList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<SparseBiAdjacencyList>(&g);
+		}
+
 		#[test]
 		fn remove_vert(g: TestGraph) {
 			let mut g_prime = SparseBiAdjacencyList::from(&g);
@@ -184,15 +267,92 @@ mod tests {
 			}
 		}
 
+		#[test]
+		fn retain_edges(g: TestGraph) {
+			let g_prime = assert_retain_edges_works(SparseBiAdjacencyList::from(&g), |g, keep| g.retain_edges(keep));
+			assert_all_bi_graph_invariants(&g_prime);
+		}
+
+		#[test]
+		fn shrink_to_fit(g: TestGraph) {
+			let prune = |g: &mut SparseBiAdjacencyList| {
+				let edges: Vec<_> = g.edges().step_by(2).collect();
+				for e in edges {
+					g.remove_edge(e);
+				}
+				let verts: Vec<_> = g.verts().step_by(3).collect();
+				for v in verts {
+					g.remove_vert(v);
+				}
+			};
+			let g_shrunk = assert_shrink_to_fit_works(
+				SparseBiAdjacencyList::from(&g),
+				prune,
+				SparseBiAdjacencyList::shrink_to_fit,
+			);
+			assert_all_bi_graph_invariants(&g_shrunk);
+		}
+
 		#[test]
 		fn remove_edge(g: TestGraph) {
 			let mut g_prime = SparseBiAdjacencyList::from(&g);
 			let mut removed = HashSet::new();
 			while let Some(e) = g_prime.edges().next() {
+				let endpoints = g_prime.endpoints(e);
+				assert_eq!(g_prime.try_endpoints(e), Some(endpoints));
 				g_prime.remove_edge(e);
+				assert_eq!(g_prime.try_endpoints(e), None);
 				assert!(removed.insert(e));
 				assert_all_bi_graph_invariants(&g_prime);
 			}
 		}
+
+		#[test]
+		#[cfg(feature = "key_generations")]
+		fn reused_keys_are_distinct(g: TestGraph) {
+			let mut g_prime = SparseBiAdjacencyList::from(&g);
+			let removed_edges: Vec<_> = g_prime.edges().collect();
+			let removed_verts: Vec<_> = g_prime.verts().collect();
+			for v in removed_verts.iter() {
+				g_prime.remove_vert(*v);
+			}
+			// Reinserting reuses every slot, but with keys distinct from the removed.
+			let verts: Vec<_> = removed_verts.iter().map(|_| g_prime.insert_vert()).collect();
+			let edges: Vec<_> = removed_edges.iter().map(|_| g_prime.insert_edge(verts[0], verts[0])).collect();
+			for v in removed_verts.iter() {
+				assert!(!verts.contains(v));
+			}
+			for e in removed_edges.iter() {
+				assert!(!edges.contains(e));
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "key_generations")]
+	#[should_panic(expected = "key in domain")]
+	fn stale_edge() {
+		let mut g = SparseBiAdjacencyList::new();
+		let v = g.insert_vert();
+		let e = g.insert_edge(v, v);
+		g.remove_edge(e);
+		let reused = g.insert_edge(v, v);
+		assert_ne!(e, reused);
+		assert_eq!(g.try_endpoints(e), None);
+		g.endpoints(e);
+	}
+
+	#[test]
+	#[cfg(feature = "key_generations")]
+	#[should_panic(expected = "key in domain")]
+	fn stale_edge_after_clear() {
+		let mut g = SparseBiAdjacencyList::new();
+		let v = g.insert_vert();
+		let e = g.insert_edge(v, v);
+		g.clear();
+		let v = g.insert_vert();
+		let reused = g.insert_edge(v, v);
+		assert_ne!(e, reused);
+		g.endpoints(e);
 	}
 }
@@ -1,6 +1,9 @@
 //! Module implementing a sparse edge list.
 
 use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
 
 use crate::{Digraph, InsertGraph};
 
@@ -24,7 +27,7 @@ pub type EphemeralVertMap<'a, T> = dense::EphemeralMap<Vert, T>;
 pub type EphemeralEdgeMap<'a, T> = sparse::EphemeralMap<Edge, T>;
 
 /// Sparse edge list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SparseEdgeList {
 	verts: dense::Domain<Vert>,
 	edges: sparse::Domain<Edge, (Vert, Vert)>,
@@ -38,6 +41,10 @@ impl Digraph for SparseEdgeList {
 		self.edges[*e.borrow()]
 	}
 
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -77,40 +84,188 @@ impl InsertGraph for SparseEdgeList {
 	fn insert_edge(&mut self, tail: Self::Vert, head: Self::Vert) -> Self::Edge {
 		self.edges.insert((tail, head))
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
 impl SparseEdgeList {
-	/// Removes an edge.
+	/// Removes an edge. Using its key afterwards panics, unless a later edge has
+	/// reused its slot. With the `key_generations` feature, edges inserted later
+	/// always have keys distinct from the removed edge's.
 	pub fn remove_edge(&mut self, e: Edge) {
 		self.edges.remove(e);
 	}
+
+	/// Releases unused capacity, such as after removing many edges. Keys are
+	/// not renumbered, so existing vertices and edges remain valid.
+	pub fn shrink_to_fit(&mut self) {
+		self.verts.shrink_to_fit();
+		self.edges.shrink_to_fit();
+	}
+}
+
+impl SparseEdgeList {
+	/// Parses a graph from lines of `tail head` vertex indices separated by
+	/// whitespace or commas. Blank lines and everything following a `#` are
+	/// ignored. Indices are labels rather than positions: a vertex is inserted
+	/// the first time its index is referenced, so unreferenced indices do not
+	/// create vertices.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = SparseEdgeList::from_edge_list("# A path\n0 1\n1, 2\n").unwrap();
+	/// assert_eq!(g.verts().count(), 3);
+	/// assert_eq!(g.edges().count(), 2);
+	/// let error = SparseEdgeList::from_edge_list("0 1\n2\n").err().unwrap();
+	/// assert_eq!(error.line(), 2);
+	/// ```
+	pub fn from_edge_list(input: &str) -> Result<Self, ParseEdgeListError> {
+		let mut g = SparseEdgeList::new();
+		let mut verts = HashMap::new();
+		for (i, line) in input.lines().enumerate() {
+			let error = ParseEdgeListError { line: i + 1 };
+			let line = line.split('#').next().unwrap_or_default();
+			let mut indices = line
+				.split(|c: char| c.is_whitespace() || c == ',')
+				.filter(|token| !token.is_empty())
+				.map(|token| token.parse::<usize>().map_err(|_| error));
+			let (tail, head) = match (indices.next(), indices.next(), indices.next()) {
+				(None, _, _) => continue,
+				(Some(tail), Some(head), None) => (tail?, head?),
+				_ => return Err(error),
+			};
+			let mut vert = |index| match verts.get(&index) {
+				Some(&v) => Ok(v),
+				None => {
+					let v = g.try_insert_vert().map_err(|_| error)?;
+					verts.insert(index, v);
+					Ok(v)
+				}
+			};
+			let (tail, head) = (vert(tail)?, vert(head)?);
+			g.try_insert_edge(tail, head).map_err(|_| error)?;
+		}
+		Ok(g)
+	}
+}
+
+/// Error indicating that a line of an edge list could not be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseEdgeListError {
+	line: usize,
+}
+
+impl ParseEdgeListError {
+	/// Returns the one-based number of the offending line.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+}
+
+impl Display for ParseEdgeListError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invalid edge on line {}", self.line)
+	}
 }
 
+impl Error for ParseEdgeListError {}
+
+impl_extend!(SparseEdgeList);
+
 impl<G: Digraph> From<&G> for SparseEdgeList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Debug for SparseEdgeList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_sorted("SparseEdgeList", self, f)
+	}
+}
+
+impl Display for SparseEdgeList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
+	use super::dense::Key;
 	use super::*;
 	use crate::model::test_graph::*;
+	use crate::DenseEdgeList;
 	use proptest::proptest;
+	use std::fmt::Write;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &SparseEdgeList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = SparseEdgeList::isomorphic_from(&g);
 			assert!(g.is_isomorphic_with_maps(&g_prime, homomorphism.vert_map(), homomorphism.edge_map()));
 		}
 
+		#[test]
+		fn shrink_to_fit(g: TestGraph) {
+			let prune = |g: &mut SparseEdgeList| {
+				let edges: Vec<_> = g.edges().step_by(2).collect();
+				for e in edges {
+					g.remove_edge(e);
+				}
+			};
+			let g_shrunk = assert_shrink_to_fit_works(
+				SparseEdgeList::from(&g),
+				prune,
+				SparseEdgeList::shrink_to_fit,
+			);
+			assert_all_digraph_invariants(&g_shrunk);
+		}
+
 		#[test]
 		fn invariants(g: TestGraph) {
 			let g_prime = SparseEdgeList::from(&g);
 			assert_all_digraph_invariants(&g_prime);
 		}
 
+		#[test]
+		fn from_edge_list(g: TestGraph) {
+			// Reference every vertex in order first so that the keys round-trip.
+			let g = DenseEdgeList::from(&g);
+			let mut g_loops = DenseEdgeList::new();
+			let verts: Vec<_> = g.verts().map(|_| g_loops.insert_vert()).collect();
+			for &v in &verts {
+				g_loops.insert_edge(v, v);
+			}
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				g_loops.insert_edge(verts[tail.index()], verts[head.index()]);
+			}
+			let g = g_loops;
+			let mut input = String::from("# Exported edge list\n\n");
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				writeln!(input, "{}, {}", tail.index(), head.index()).unwrap();
+			}
+			let g_prime = SparseEdgeList::from_edge_list(&input).unwrap();
+			assert!(g.is_isomorphic_with_maps(
+				&g_prime,
+				&|v: crate::dense_edge_list::Vert| Vert::from(v.index()),
+				&|e: crate::dense_edge_list::Edge| Edge::from(e.index())
+			));
+		}
+
 		#[test]
 		fn vert_map(g: TestGraph) {
 			let g_prime = SparseEdgeList::from(&g);
@@ -122,5 +277,40 @@ mod tests {
 			let g_prime = SparseEdgeList::from(&g);
 			assert_edge_map_works(g_prime);
 		}
+
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = SparseEdgeList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = SparseEdgeList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<SparseEdgeList>(&g);
+		}
+	}
+
+	#[test]
+	fn from_edge_list_error() {
+		let error = SparseEdgeList::from_edge_list("0 1\n\n1 x\n")
+			.err()
+			.unwrap();
+		assert_eq!(error.line(), 3);
+		let error = SparseEdgeList::from_edge_list("0 1 2\n").err().unwrap();
+		assert_eq!(error.line(), 1);
+	}
+
+	#[test]
+	fn from_edge_list_sparse_indices() {
+		let input = format!("0 {}\n{} 0\n", usize::MAX, 4_000_000_000usize);
+		let g = SparseEdgeList::from_edge_list(&input).unwrap();
+		assert_eq!(g.verts().count(), 3);
+		assert_eq!(g.edges().count(), 2);
 	}
 }
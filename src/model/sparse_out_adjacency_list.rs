@@ -2,6 +2,7 @@
 
 use std::borrow::Borrow;
 use std::collections::{hash_set, HashSet};
+use std::fmt::{self, Debug, Display};
 
 use crate::{Digraph, InsertGraph, OutGraph};
 
@@ -27,7 +28,7 @@ pub type EphemeralEdgeMap<'a, T> = sparse::EphemeralMap<Edge, T>;
 pub type OutEdges<'a> = std::iter::Cloned<hash_set::Iter<'a, Edge>>;
 
 /// Sparse out-adjacency list directed graph representation.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SparseOutAdjacencyList {
 	verts: dense::Domain<Vert, HashSet<Edge>>,
 	edges: sparse::Domain<Edge, (Vert, Vert)>,
@@ -41,6 +42,10 @@ impl Digraph for SparseOutAdjacencyList {
 		self.edges[*e.borrow()]
 	}
 
+	fn try_endpoints(&self, e: impl Borrow<Self::Edge>) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = Verts<'a>;
 	fn verts(&self) -> Self::Verts<'_> {
 		self.verts.keys()
@@ -90,23 +95,76 @@ impl InsertGraph for SparseOutAdjacencyList {
 		debug_assert!(inserted);
 		e
 	}
+
+	fn clear(&mut self) {
+		self.verts.clear();
+		self.edges.clear();
+	}
+
+	impl_domain_capacity!();
 }
 
 impl SparseOutAdjacencyList {
-	/// Removes an edge.
+	/// Removes an edge. Using its key afterwards panics, unless a later edge has
+	/// reused its slot. With the `key_generations` feature, edges inserted later
+	/// always have keys distinct from the removed edge's.
 	pub fn remove_edge(&mut self, e: Edge) {
 		let (tail, _) = self.edges.remove(e);
 		let removed = self.verts[tail].remove(&e);
 		debug_assert!(removed);
 	}
+
+	/// Releases unused capacity, such as after removing many edges. Keys are
+	/// not renumbered, so existing vertices and edges remain valid.
+	pub fn shrink_to_fit(&mut self) {
+		for out_edges in self.verts.values_mut() {
+			out_edges.shrink_to_fit();
+		}
+		self.verts.shrink_to_fit();
+		self.edges.shrink_to_fit();
+	}
+
+	/// Removes every edge for which `keep` returns `false`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = SparseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// g.insert_edge(v, u);
+	/// g.retain_edges(|f| f == e);
+	/// assert_eq!(g.edges().collect::<Vec<_>>(), vec![e]);
+	/// ```
+	pub fn retain_edges(&mut self, mut keep: impl FnMut(Edge) -> bool) {
+		let removed: Vec<_> = self.edges().filter(|e| !keep(*e)).collect();
+		for e in removed {
+			self.remove_edge(e);
+		}
+	}
 }
 
+impl_extend!(SparseOutAdjacencyList);
+
 impl<G: Digraph> From<&G> for SparseOutAdjacencyList {
 	fn from(from: &G) -> Self {
 		Self::isomorphic_from(from).0
 	}
 }
 
+impl Debug for SparseOutAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_sorted("SparseOutAdjacencyList", self, f)
+	}
+}
+
+impl Display for SparseOutAdjacencyList {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		super::fmt_summary(self, f)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -114,6 +172,11 @@ mod tests {
 	use proptest::proptest;
 
 	proptest! {
+		#[test]
+		fn with_capacity(g: TestGraph) {
+			assert_with_capacity_works(&g, |g: &SparseOutAdjacencyList| (g.verts.capacity(), g.edges.capacity()));
+		}
+
 		#[test]
 		fn ismorphic_from(g: TestGraph) {
 			let (g_prime, homomorphism) = SparseOutAdjacencyList::isomorphic_from(&g);
@@ -138,6 +201,50 @@ mod tests {
 			assert_edge_map_works(g_prime);
 		}
 
+		#[test]
+		fn clear(g: TestGraph) {
+			let g_prime = SparseOutAdjacencyList::from(&g);
+			assert_clear_works(g_prime);
+		}
+
+		#[test]
+		fn clone(g: TestGraph) {
+			let g_prime = SparseOutAdjacencyList::from(&g);
+			assert_clone_works(&g_prime);
+		}
+
+		#[test]
+		fn from_edges(g: TestGraph) {
+			assert_from_edges_works::<SparseOutAdjacencyList>(&g);
+		}
+
+		#[test]
+		fn fmt(g: TestGraph) {
+			assert_fmt_works::<SparseOutAdjacencyList>(&g);
+		}
+
+		#[test]
+		fn retain_edges(g: TestGraph) {
+			let g_prime = assert_retain_edges_works(SparseOutAdjacencyList::from(&g), |g, keep| g.retain_edges(keep));
+			assert_all_out_graph_invariants(&g_prime);
+		}
+
+		#[test]
+		fn shrink_to_fit(g: TestGraph) {
+			let prune = |g: &mut SparseOutAdjacencyList| {
+				let edges: Vec<_> = g.edges().step_by(2).collect();
+				for e in edges {
+					g.remove_edge(e);
+				}
+			};
+			let g_shrunk = assert_shrink_to_fit_works(
+				SparseOutAdjacencyList::from(&g),
+				prune,
+				SparseOutAdjacencyList::shrink_to_fit,
+			);
+			assert_all_out_graph_invariants(&g_shrunk);
+		}
+
 		#[test]
 		fn remove_edge(g: TestGraph) {
 			let mut g_prime = SparseOutAdjacencyList::from(&g);
@@ -148,5 +255,17 @@ mod tests {
 				assert_all_out_graph_invariants(&g_prime);
 			}
 		}
+
+		#[test]
+		fn structurally_eq(g: TestGraph) {
+			let g_prime = SparseOutAdjacencyList::from(&g);
+			let mut g_other = SparseOutAdjacencyList::from(&g);
+			assert!(g_prime.structurally_eq(&g_other));
+			if let Some(e) = g_other.edges().next() {
+				g_other.remove_edge(e);
+				assert!(!g_prime.structurally_eq(&g_other));
+				assert!(!g_other.structurally_eq(&g_prime));
+			}
+		}
 	}
 }
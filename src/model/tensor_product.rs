@@ -3,12 +3,15 @@
 #![allow(type_alias_bounds)]
 
 use std::borrow::Borrow;
-
-use super::sparse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
 
 use itertools::{Itertools, Product};
 
-use crate::{Digraph, InGraph, OutGraph};
+use super::sparse;
+
+use crate::{Digraph, InGraph, Map, MapMut, OutGraph};
 
 #[allow(missing_docs)]
 pub type Vert<G0: Digraph, G1: Digraph> = (G0::Vert, G1::Vert);
@@ -19,12 +22,169 @@ pub type Verts<'a, G0: Digraph, G1: Digraph> = Product<G0::Verts<'a>, G1::Verts<
 #[allow(missing_docs)]
 pub type Edges<'a, G0: Digraph, G1: Digraph> = Product<G0::Edges<'a>, G1::Edges<'a>>;
 
-// TODO: Ideally, we would like to leverage density when both factor graphs have
-// dense mappings.
 #[allow(missing_docs)]
-pub type VertMap<G0: Digraph, G1: Digraph, T> = sparse::Map<Vert<G0, G1>, T>;
+pub type VertMap<G0: Digraph, G1: Digraph, T> =
+	ProductMap<G0::VertMap<()>, G1::VertMap<()>, G0::Vert, G1::Vert, T>;
 #[allow(missing_docs)]
-pub type EdgeMap<G0: Digraph, G1: Digraph, T> = sparse::Map<Edge<G0, G1>, T>;
+pub type EdgeMap<G0: Digraph, G1: Digraph, T> =
+	ProductMap<G0::EdgeMap<()>, G1::EdgeMap<()>, G0::Edge, G1::Edge, T>;
+
+/// Map from pairs of keys to values. When the maps `M0` and `M1` of both
+/// factors are vectors indexed by key, as for the dense models, values are
+/// stored densely. Otherwise, and for products with an empty factor, pairs are
+/// hashed.
+#[derive(Clone, Debug)]
+pub enum ProductMap<M0, M1, K0, K1, T> {
+	/// Map for products of two dense factors.
+	Dense(DenseProductMap<M0, M1, K0, K1, T>),
+	/// Map for products with a sparse factor.
+	Sparse(sparse::Map<(K0, K1), T>),
+}
+
+impl<M0, M1, K0, K1, T> ProductMap<M0, M1, K0, K1, T>
+where
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	fn new(
+		default: T,
+		mut keys0: impl Iterator<Item = K0>,
+		mut keys1: impl Iterator<Item = K1>,
+	) -> Self {
+		let dense0 = keys0.next().and_then(|k0| M0::dense_index(&k0)).is_some();
+		let dense1 = keys1.next().and_then(|k1| M1::dense_index(&k1)).is_some();
+		if dense0 && dense1 {
+			ProductMap::Dense(DenseProductMap {
+				values: Vec::new(),
+				stride: 1 + keys1.count(),
+				sparse: HashMap::new(),
+				default,
+				_phantom: PhantomData,
+			})
+		} else {
+			ProductMap::Sparse(sparse::Map::new(default))
+		}
+	}
+}
+
+impl<M0, M1, K0, K1, T> Map<(K0, K1)> for ProductMap<M0, M1, K0, K1, T>
+where
+	K0: Copy + Eq + Hash,
+	K1: Copy + Eq + Hash,
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	type Value = T;
+	type Ref<'a>
+	where
+		T: 'a,
+	= &'a T;
+	fn get<'a>(&'a self, k: (K0, K1)) -> Self::Ref<'a>
+	where
+		T: 'a,
+	{
+		match self {
+			ProductMap::Dense(map) => map.get(k),
+			ProductMap::Sparse(map) => map.get(k),
+		}
+	}
+}
+
+impl<M0, M1, K0, K1, T: Clone> MapMut<(K0, K1)> for ProductMap<M0, M1, K0, K1, T>
+where
+	K0: Copy + Eq + Hash,
+	K1: Copy + Eq + Hash,
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	type RefMut<'a>
+	where
+		T: 'a,
+	= &'a mut T;
+	fn get_mut(&mut self, k: (K0, K1)) -> Self::RefMut<'_> {
+		match self {
+			ProductMap::Dense(map) => map.get_mut(k),
+			ProductMap::Sparse(map) => map.get_mut(k),
+		}
+	}
+}
+
+/// Map from pairs of keys of two dense factors to values. The value for a pair
+/// of keys with indices `i0` and `i1` is stored in a flat vector at
+/// `i0 * stride + i1`, where the stride is the order or size of the second
+/// factor when the map is constructed. Pairs involving keys inserted later
+/// fall back to a hash map.
+#[derive(Clone, Debug)]
+pub struct DenseProductMap<M0, M1, K0, K1, T> {
+	values: Vec<T>,
+	stride: usize,
+	sparse: HashMap<(K0, K1), T>,
+	default: T,
+	_phantom: PhantomData<fn() -> (M0, M1)>,
+}
+
+impl<M0, M1, K0, K1, T> DenseProductMap<M0, M1, K0, K1, T>
+where
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	fn index(&self, (k0, k1): &(K0, K1)) -> Option<usize> {
+		let i1 = M1::dense_index(k1).filter(|&i1| i1 < self.stride)?;
+		let i0 = M0::dense_index(k0)?;
+		i0.checked_mul(self.stride)?.checked_add(i1)
+	}
+}
+
+impl<M0, M1, K0, K1, T> Map<(K0, K1)> for DenseProductMap<M0, M1, K0, K1, T>
+where
+	K0: Copy + Eq + Hash,
+	K1: Copy + Eq + Hash,
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	type Value = T;
+	type Ref<'a>
+	where
+		T: 'a,
+	= &'a T;
+	fn get<'a>(&'a self, k: (K0, K1)) -> Self::Ref<'a>
+	where
+		T: 'a,
+	{
+		let value = match self.index(&k) {
+			Some(index) => self.values.get(index),
+			None => self.sparse.get(&k),
+		};
+		value.unwrap_or(&self.default)
+	}
+}
+
+impl<M0, M1, K0, K1, T: Clone> MapMut<(K0, K1)> for DenseProductMap<M0, M1, K0, K1, T>
+where
+	K0: Copy + Eq + Hash,
+	K1: Copy + Eq + Hash,
+	M0: Map<K0>,
+	M1: Map<K1>,
+{
+	type RefMut<'a>
+	where
+		T: 'a,
+	= &'a mut T;
+	fn get_mut(&mut self, k: (K0, K1)) -> Self::RefMut<'_> {
+		match self.index(&k) {
+			Some(index) => {
+				if index >= self.values.len() {
+					self.values.resize(index + 1, self.default.clone());
+				}
+				&mut self.values[index]
+			}
+			None => {
+				let default = &self.default;
+				self.sparse.entry(k).or_insert_with(|| default.clone())
+			}
+		}
+	}
+}
 
 #[allow(missing_docs)]
 pub type OutEdges<'a, G0: OutGraph, G1: OutGraph> = Product<G0::OutEdges<'a>, G1::OutEdges<'a>>;
@@ -64,12 +224,12 @@ impl<G0: Digraph, G1: Digraph> Digraph for (G0, G1) {
 
 	type VertMap<T: Clone> = VertMap<G0, G1, T>;
 	fn vert_map<T: Clone>(&self, default: T) -> Self::VertMap<T> {
-		sparse::Map::new(default)
+		ProductMap::new(default, self.0.verts(), self.1.verts())
 	}
 
 	type EdgeMap<T: Clone> = EdgeMap<G0, G1, T>;
 	fn edge_map<T: Clone>(&self, default: T) -> Self::EdgeMap<T> {
-		sparse::Map::new(default)
+		ProductMap::new(default, self.0.edges(), self.1.edges())
 	}
 
 	fn ephemeral_vert_map<T: Clone>(&self, default: T) -> Self::EphemeralVertMap<'_, T> {
@@ -106,7 +266,28 @@ impl<G0: InGraph, G1: InGraph> InGraph for (G0, G1) {
 mod tests {
 	use super::*;
 	use crate::model::test_graph::*;
+	use crate::{DenseBiAdjacencyList, InsertGraph, SparseBiAdjacencyList};
 	use proptest::proptest;
+
+	// Asserts that vertex and edge maps retain their values as a vertex and a
+	// self-loop are inserted into each factor in turn.
+	fn assert_product_maps_work<G0, G1>(g: (G0, G1))
+	where
+		G0: InsertGraph + Clone,
+		G1: InsertGraph + Clone,
+	{
+		assert_vert_map_works_with(g.clone(), |g| g.vert_map(None), Some, |g| {
+			g.1.insert_vert();
+			g.0.insert_vert();
+		});
+		assert_edge_map_works_with(g, |g| g.edge_map(None), Some, |g| {
+			let v1 = g.1.insert_vert();
+			g.1.insert_edge(v1, v1);
+			let v0 = g.0.insert_vert();
+			g.0.insert_edge(v0, v0);
+		});
+	}
+
 	proptest! {
 		#[test]
 		fn order(g0: TestGraph, g1: TestGraph) {
@@ -139,5 +320,69 @@ mod tests {
 			let g1_prime = crate::DenseBiAdjacencyList::from(&g1);
 			assert_all_bi_graph_invariants(&(g0_prime, g1_prime));
 		}
+
+		#[test]
+		fn dense_maps_work(g0: TestGraph, g1: TestGraph) {
+			let dense = |g| DenseBiAdjacencyList::from(g);
+			assert_product_maps_work((dense(&g0), dense(&g1)));
+		}
+
+		#[test]
+		fn mixed_maps_work(g0: TestGraph, g1: TestGraph) {
+			let dense = |g| DenseBiAdjacencyList::from(g);
+			assert_product_maps_work((g0.clone(), dense(&g1)));
+			assert_product_maps_work((dense(&g1), g0));
+		}
+	}
+
+	#[test]
+	fn dense_maps_avoid_hashing() {
+		let mut g0 = DenseBiAdjacencyList::new();
+		let mut g1 = DenseBiAdjacencyList::new();
+		for _ in 0..3 {
+			let v0 = g0.insert_vert();
+			g0.insert_edge(v0, v0);
+			let v1 = g1.insert_vert();
+			g1.insert_edge(v1, v1);
+		}
+		let g = (g0, g1);
+		let mut vert_map = g.vert_map(0);
+		for v in g.verts() {
+			*vert_map.get_mut(v) += 1;
+		}
+		match vert_map {
+			ProductMap::Dense(map) => {
+				assert_eq!(map.values.len(), 9);
+				assert!(map.sparse.is_empty());
+			}
+			ProductMap::Sparse(_) => panic!("expected a dense map"),
+		}
+		let mut edge_map = g.edge_map(0);
+		for e in g.edges() {
+			*edge_map.get_mut(e) += 1;
+		}
+		match edge_map {
+			ProductMap::Dense(map) => {
+				assert_eq!(map.values.len(), 9);
+				assert!(map.sparse.is_empty());
+			}
+			ProductMap::Sparse(_) => panic!("expected a dense map"),
+		}
+	}
+
+	#[test]
+	fn mixed_maps_hash_pairs() {
+		let mut g0 = SparseBiAdjacencyList::new();
+		let mut g1 = DenseBiAdjacencyList::new();
+		let v0 = g0.insert_vert();
+		g0.insert_edge(v0, v0);
+		let v1 = g1.insert_vert();
+		g1.insert_edge(v1, v1);
+		let g = (g0, g1);
+		assert!(matches!(g.vert_map(0), ProductMap::Sparse(_)));
+		assert!(matches!(g.edge_map(0), ProductMap::Sparse(_)));
+		let g = (g.1, g.0);
+		assert!(matches!(g.vert_map(0), ProductMap::Sparse(_)));
+		assert!(matches!(g.edge_map(0), ProductMap::Sparse(_)));
 	}
 }
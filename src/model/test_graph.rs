@@ -4,7 +4,11 @@ use crate::{
 	Digraph, InGraph, InsertGraph, OutGraph,
 };
 
-use std::{borrow::Borrow, collections::HashSet};
+use std::{
+	borrow::Borrow,
+	collections::HashSet,
+	fmt::{Debug, Display},
+};
 
 use proptest::{
 	arbitrary::Arbitrary,
@@ -20,6 +24,38 @@ pub struct TestGraph {
 	edges: Vec<(Vert, Vert)>,
 }
 
+impl TestGraph {
+	/// Returns an acyclic graph by orienting every edge from its lesser to its
+	/// greater endpoint and dropping self-loops.
+	pub fn acyclic(&self) -> TestGraph {
+		let edges = self
+			.edges
+			.iter()
+			.filter(|(tail, head)| tail != head)
+			.map(|(tail, head)| (*tail.min(head), *tail.max(head)))
+			.collect();
+		TestGraph {
+			order: self.order,
+			edges,
+		}
+	}
+
+	/// Returns a simple graph by dropping parallel edges.
+	pub fn simple(&self) -> TestGraph {
+		let mut seen = HashSet::new();
+		let edges = self
+			.edges
+			.iter()
+			.filter(|edge| seen.insert(**edge))
+			.cloned()
+			.collect();
+		TestGraph {
+			order: self.order,
+			edges,
+		}
+	}
+}
+
 impl Digraph for TestGraph {
 	type Vert = Vert;
 	type Edge = Edge;
@@ -28,6 +64,13 @@ impl Digraph for TestGraph {
 		self.edges[*e.borrow()]
 	}
 
+	fn try_endpoints(
+		&self,
+		e: impl std::borrow::Borrow<Self::Edge>,
+	) -> Option<(Self::Vert, Self::Vert)> {
+		self.edges.get(*e.borrow()).copied()
+	}
+
 	type Verts<'a> = std::ops::Range<Vert>;
 	fn verts(&self) -> Self::Verts<'_> {
 		0..self.order
@@ -87,6 +130,11 @@ impl InsertGraph for TestGraph {
 		self.edges.push((tail, head));
 		e
 	}
+
+	fn clear(&mut self) {
+		self.order = 0;
+		self.edges.clear();
+	}
 }
 
 fn assert_endpoints_works(g: &impl Digraph) {
@@ -97,6 +145,11 @@ fn assert_endpoints_works(g: &impl Digraph) {
 		assert!(verts.contains(&head), "head is a valid vertex");
 		assert_eq!(g.tail(e), tail, "tail is the first endpoint");
 		assert_eq!(g.head(e), head, "head is the second endpoint");
+		assert_eq!(
+			g.try_endpoints(e),
+			Some((tail, head)),
+			"edge is in the graph"
+		);
 	}
 }
 
@@ -140,6 +193,36 @@ fn assert_ephemeral_edge_map_works(g: &impl Digraph) {
 	}
 }
 
+fn assert_maps_from_fn_work(g: &impl Digraph) {
+	// Compare against writing each value manually.
+	let map = g.vert_map_from_fn(None, Some);
+	let mut expected = g.vert_map(None);
+	for v in g.verts() {
+		*expected.get_mut(v) = Some(v);
+	}
+	for v in g.verts() {
+		assert_eq!(*map.get(v).borrow(), *expected.get(v).borrow());
+	}
+	let map = g.edge_map_from_fn(None, Some);
+	let mut expected = g.edge_map(None);
+	for e in g.edges() {
+		*expected.get_mut(e) = Some(e);
+	}
+	for e in g.edges() {
+		assert_eq!(*map.get(e).borrow(), *expected.get(e).borrow());
+	}
+}
+
+fn assert_edge_pairs_round_trip(g: &impl Digraph) {
+	let verts: Vec<_> = g.verts().collect();
+	let pairs = g.to_edge_pairs();
+	for (e, (tail, head)) in g.edges().zip(pairs.iter()) {
+		assert_eq!(g.endpoints(e), (verts[*tail], verts[*head]));
+	}
+	let g_prime = TestGraph::from_edges(verts.len(), pairs.iter().cloned());
+	assert_eq!(g_prime.to_edge_pairs(), pairs);
+}
+
 fn assert_out_edges_works(g: &impl OutGraph) {
 	let mut set = std::collections::HashSet::new();
 	for v in g.verts() {
@@ -180,6 +263,8 @@ pub fn assert_all_digraph_invariants(g: &impl Digraph) {
 	assert_edges_works(g);
 	assert_ephemeral_vert_map_works(g);
 	assert_ephemeral_edge_map_works(g);
+	assert_maps_from_fn_work(g);
+	assert_edge_pairs_round_trip(g);
 }
 
 pub fn assert_all_out_graph_invariants(g: &impl OutGraph) {
@@ -198,39 +283,222 @@ pub fn assert_all_bi_graph_invariants(g: &(impl OutGraph + InGraph)) {
 	assert_in_graph_invariants(g);
 }
 
-pub fn assert_vert_map_works(mut g: impl InsertGraph) {
+pub fn assert_vert_map_works<G: InsertGraph>(g: G) {
 	// Build an identity mapping.
-	let mut map = g.vert_map(None);
-	for v in g.verts() {
-		assert_eq!(*map.get(v).borrow(), None);
-		*map.get_mut(v) = Some(v);
+	assert_vert_map_works_with(g, |g| g.vert_map(None), Some, |g| {
+		g.insert_vert();
+	});
+}
+
+/// Asserts that a map constructed by `new_map`, whose values are initially the
+/// default, retains the values given by `value` as `modify` inserts vertices.
+pub fn assert_vert_map_works_with<G: Digraph, M: MapMut<G::Vert>>(
+	mut g: G,
+	new_map: impl FnOnce(&G) -> M,
+	value: impl Fn(G::Vert) -> M::Value,
+	modify: impl FnOnce(&mut G),
+) where
+	M::Value: Default + PartialEq + Debug,
+{
+	let mut map = new_map(&g);
+	let verts: HashSet<_> = g.verts().collect();
+	for v in verts.iter().cloned() {
+		assert_eq!(*map.get(v).borrow(), M::Value::default());
+		*map.get_mut(v) = value(v);
 	}
 	// Modify the graph.
-	let v_prime = g.insert_vert();
-	assert_eq!(*map.get(v_prime).borrow(), None);
-	*map.get_mut(v_prime) = Some(v_prime);
+	modify(&mut g);
+	for v in g.verts().filter(|v| !verts.contains(v)) {
+		assert_eq!(*map.get(v).borrow(), M::Value::default());
+		*map.get_mut(v) = value(v);
+	}
 	// Verify the set values are retained.
 	for v in g.verts() {
-		assert_eq!(*map.get(v).borrow(), Some(v));
+		assert_eq!(*map.get(v).borrow(), value(v));
 	}
 }
 
-pub fn assert_edge_map_works(mut g: impl InsertGraph) {
+pub fn assert_edge_map_works<G: InsertGraph>(g: G) {
 	// Build an identity mapping.
-	let mut map = g.edge_map(None);
-	for e in g.edges() {
-		assert_eq!(*map.get(e).borrow(), None);
-		*map.get_mut(e) = Some(e);
+	assert_edge_map_works_with(g, |g| g.edge_map(None), Some, |g| {
+		let v = g.insert_vert();
+		g.insert_edge(v, v);
+	});
+}
+
+/// Asserts that a map constructed by `new_map`, whose values are initially the
+/// default, retains the values given by `value` as `modify` inserts edges.
+pub fn assert_edge_map_works_with<G: Digraph, M: MapMut<G::Edge>>(
+	mut g: G,
+	new_map: impl FnOnce(&G) -> M,
+	value: impl Fn(G::Edge) -> M::Value,
+	modify: impl FnOnce(&mut G),
+) where
+	M::Value: Default + PartialEq + Debug,
+{
+	let mut map = new_map(&g);
+	let edges: HashSet<_> = g.edges().collect();
+	for e in edges.iter().cloned() {
+		assert_eq!(*map.get(e).borrow(), M::Value::default());
+		*map.get_mut(e) = value(e);
 	}
 	// Modify the graph.
-	let v_prime = g.insert_vert();
-	let e_prime = g.insert_edge(v_prime, v_prime);
-	assert_eq!(*map.get(e_prime).borrow(), None);
-	*map.get_mut(e_prime) = Some(e_prime);
+	modify(&mut g);
+	for e in g.edges().filter(|e| !edges.contains(e)) {
+		assert_eq!(*map.get(e).borrow(), M::Value::default());
+		*map.get_mut(e) = value(e);
+	}
 	// Verify the set values are retained.
 	for e in g.edges() {
-		assert_eq!(*map.get(e).borrow(), Some(e));
+		assert_eq!(*map.get(e).borrow(), value(e));
+	}
+}
+
+/// Asserts that `from_edges` and `extend` each build the same graph as
+/// inserting vertices and edges one at a time. For dense models, this includes
+/// the order of edges.
+pub fn assert_from_edges_works<G>(g: &TestGraph)
+where
+	G: InsertGraph + Extend<(G::Vert, G::Vert)>,
+{
+	let pairs: Vec<_> = g.edges().map(|e| g.endpoints(e)).collect();
+	let mut manual = G::new();
+	let verts: Vec<_> = g.verts().map(|_| manual.insert_vert()).collect();
+	for (tail, head) in pairs.iter() {
+		manual.insert_edge(verts[*tail], verts[*head]);
+	}
+	let from_edges = G::from_edges(verts.len(), pairs.iter().cloned());
+	assert!(from_edges.structurally_eq(&manual));
+	let mut extended = G::new();
+	for _ in g.verts() {
+		extended.insert_vert();
+	}
+	extended.extend(pairs.iter().map(|(tail, head)| (verts[*tail], verts[*head])));
+	assert!(extended.structurally_eq(&manual));
+}
+
+/// Returns a deterministic pseudorandom number generator for the random graph
+/// generators, seeded with `seed`.
+pub fn rng(seed: u64) -> impl FnMut() -> u64 {
+	let mut state = seed;
+	move || {
+		state = state
+			.wrapping_mul(6364136223846793005)
+			.wrapping_add(1442695040888963407);
+		state
+	}
+}
+
+/// Asserts that clearing a graph leaves it equivalent to a new one and that
+/// its previous edges are no longer found.
+pub fn assert_clear_works<G: InsertGraph>(mut g: G) {
+	let stale: Vec<_> = g.edges().collect();
+	g.clear();
+	assert_eq!(g.verts().count(), 0);
+	assert_eq!(g.edges().count(), 0);
+	for e in stale {
+		assert_eq!(g.try_endpoints(e), None, "cleared edge is not in the graph");
 	}
+	// Rebuilding should work as in a new graph. Sparse models may reissue keys
+	// with new generations, so the keys need not match a new graph's.
+	let u = g.insert_vert();
+	let v = g.insert_vert();
+	let edges = [g.insert_edge(u, v), g.insert_edge(v, v)];
+	assert_eq!(g.verts().count(), 2);
+	assert_eq!(g.edges().count(), 2);
+	assert_eq!(g.endpoints(edges[0]), (u, v));
+	assert_eq!(g.endpoints(edges[1]), (v, v));
+}
+
+/// Asserts that shrinking a pruned graph preserves its keys and structure and
+/// that keys inserted afterwards are distinct from those before. Returns the
+/// shrunk graph so that the model's invariants can be checked.
+pub fn assert_shrink_to_fit_works<G: InsertGraph + Clone>(
+	mut g: G,
+	prune: impl FnOnce(&mut G),
+	shrink: impl FnOnce(&mut G),
+) -> G {
+	prune(&mut g);
+	let expected = g.clone();
+	shrink(&mut g);
+	assert!(g.structurally_eq(&expected));
+	let v = g.insert_vert();
+	let e = g.insert_edge(v, v);
+	assert!(!expected.verts().any(|u| u == v));
+	assert!(!expected.edges().any(|d| d == e));
+	g
+}
+
+/// Asserts that retaining every other edge visits each edge once and keeps
+/// exactly those for which `keep` returned true. Returns the pruned graph so
+/// that the model's invariants can be checked.
+pub fn assert_retain_edges_works<G: Digraph>(
+	mut g: G,
+	retain: impl FnOnce(&mut G, &mut dyn FnMut(G::Edge) -> bool),
+) -> G {
+	let size = g.edges().count();
+	let kept: HashSet<_> = g.edges().step_by(2).collect();
+	let mut visited = HashSet::new();
+	retain(&mut g, &mut |e| {
+		assert!(visited.insert(e), "edges are visited once");
+		kept.contains(&e)
+	});
+	assert_eq!(visited.len(), size);
+	assert_eq!(g.edges().collect::<HashSet<_>>(), kept);
+	g
+}
+
+/// Asserts that a graph constructed with capacity for the vertices and edges
+/// of another does not reallocate while they are inserted, and that reserving
+/// another edge grows its edge capacity. `capacities` returns the vertex and
+/// edge capacities of a graph.
+pub fn assert_with_capacity_works<G: InsertGraph>(
+	g: &TestGraph,
+	capacities: impl Fn(&G) -> (usize, usize),
+) {
+	let (order, size) = (g.verts().count(), g.edges().count());
+	let mut g_prime = G::with_capacity(order, size);
+	let initial = capacities(&g_prime);
+	assert!(initial.0 >= order && initial.1 >= size);
+	let verts: Vec<_> = g.verts().map(|_| g_prime.insert_vert()).collect();
+	for e in g.edges() {
+		let (tail, head) = g.endpoints(e);
+		g_prime.insert_edge(verts[tail], verts[head]);
+	}
+	assert_eq!(capacities(&g_prime), initial);
+	assert!(g_prime.structurally_eq(&G::isomorphic_from(g).0));
+	g_prime.reserve_edges(1);
+	assert!(capacities(&g_prime).1 > size);
+}
+
+/// Asserts that a clone of a graph is structurally equal to it and that
+/// inserting into the clone leaves the original unchanged.
+pub fn assert_clone_works<G: InsertGraph + Clone>(g: &G) {
+	let mut clone = g.clone();
+	assert!(clone.structurally_eq(g));
+	let pairs: Vec<_> = g.edges().map(|e| g.endpoints(e)).collect();
+	let order = g.verts().count();
+	let v = clone.insert_vert();
+	clone.insert_edge(v, v);
+	assert_eq!(g.verts().count(), order);
+	assert_eq!(g.edges().map(|e| g.endpoints(e)).collect::<Vec<_>>(), pairs);
+	assert!(!clone.structurally_eq(g));
+}
+
+/// Asserts that graphs with the same structure are formatted identically and
+/// that their summaries give their order and size.
+pub fn assert_fmt_works<G: InsertGraph + Debug + Display>(from: &impl Digraph) {
+	let (g, _) = G::isomorphic_from(from);
+	let (h, _) = G::isomorphic_from(from);
+	assert_eq!(format!("{:?}", g), format!("{:?}", h));
+	assert_eq!(
+		g.to_string(),
+		format!(
+			"graph of order {} and size {}",
+			from.verts().count(),
+			from.edges().count()
+		)
+	);
 }
 
 #[cfg(test)]
@@ -261,6 +529,11 @@ mod tests {
 			assert_edge_map_works(g);
 		}
 
+		#[test]
+		fn clear(g: TestGraph) {
+			assert_clear_works(g);
+		}
+
 		#[test]
 		fn ephemeral_edge_map(g: TestGraph) {
 			assert_ephemeral_edge_map_works(&g);
@@ -1,8 +1,18 @@
 use super::map::{Map, MapMut};
 use crate::BinaryHeap;
-use crate::{adjacencies::OutAdjacencies, DepthFirst, Digraph};
+use crate::{
+	adjacencies::OutAdjacencies, bellman_ford, centrality, isomorphism, strongly_connected,
+	topological_sort, BreadthFirst, Cycle, DepthFirst, DepthFirstEvent, Digraph, Filtered,
+	Homomorphism, NegativeCycle, Neighbors,
+};
+use crate::{
+	sparse_bi_adjacency_list, sparse_out_adjacency_list, InsertGraph, SparseBiAdjacencyList,
+	SparseOutAdjacencyList,
+};
 use std::borrow::Borrow;
-use std::ops::Add;
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
+use std::ops::{Add, Sub};
 
 /// Represents a directed graph in which the out-adjacencies of vertices can be
 /// iterated.
@@ -24,104 +34,2222 @@ pub trait OutGraph: Digraph {
 	/// ```
 	fn out_edges(&self, v: impl Borrow<Self::Vert>) -> Self::OutEdges<'_>;
 
+	/// Returns an iterator over the heads of the out-adjacencies of a vertex.
+	/// Vertices are not deduplicated, so parallel edges yield the same vertex
+	/// repeatedly and a self-loop yields `v` itself.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(u, u);
+	/// let mut successors: Vec<_> = g.successors(u).collect();
+	/// successors.sort();
+	/// assert_eq!(successors, vec![u, v, v]);
+	/// ```
+	fn successors(&self, v: impl Borrow<Self::Vert>) -> Neighbors<'_, Self, OutAdjacencies> {
+		Neighbors::new(self, *v.borrow())
+	}
+
+	/// Returns the set of distinct heads of the out-adjacencies of a vertex.
+	fn successors_unique(&self, v: impl Borrow<Self::Vert>) -> HashSet<Self::Vert> {
+		self.successors(v).collect()
+	}
+
+	/// Returns an edge from `tail` to `head`, if one exists. If there are
+	/// several such edges, any one of them may be returned.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let tail = g.insert_vert();
+	/// # let head = g.insert_vert();
+	/// assert_eq!(g.find_edge(tail, head), None);
+	/// let e = g.insert_edge(tail, head);
+	/// assert_eq!(g.find_edge(tail, head), Some(e));
+	/// assert_eq!(g.find_edge(head, tail), None);
+	/// ```
+	fn find_edge(
+		&self,
+		tail: impl Borrow<Self::Vert>,
+		head: impl Borrow<Self::Vert>,
+	) -> Option<Self::Edge> {
+		let head = *head.borrow();
+		self.out_edges(tail).find(|e| self.head(e) == head)
+	}
+
+	/// Returns whether there is an edge from `tail` to `head`.
+	fn has_edge(&self, tail: impl Borrow<Self::Vert>, head: impl Borrow<Self::Vert>) -> bool {
+		self.find_edge(tail, head).is_some()
+	}
+
 	/// Returns an iterator that performs a depth-first traverals.
 	fn depth_first_out(&self) -> DepthFirst<'_, Self, OutAdjacencies> {
-		DepthFirst::new(self)
+		self.depth_first()
+	}
+
+	/// Returns an iterator that performs a breadth-first traversal.
+	fn breadth_first_out(&self) -> BreadthFirst<'_, Self, OutAdjacencies> {
+		BreadthFirst::new(self)
+	}
+
+	/// Returns the vertices ordered such that the tail of every edge precedes its
+	/// head, or an error if the graph contains a cycle.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let tail = g.insert_vert();
+	/// # let head = g.insert_vert();
+	/// let e = g.insert_edge(tail, head);
+	/// assert_eq!(g.topological_sort().unwrap(), vec![tail, head]);
+	/// g.insert_edge(head, tail);
+	/// assert!(g.topological_sort().is_err());
+	/// ```
+	fn topological_sort(&self) -> Result<Vec<Self::Vert>, Cycle<Self>> {
+		topological_sort::topological_sort(self)
+	}
+
+	/// Returns the vertices grouped into generations, or an error if the graph
+	/// contains a cycle. The first generation contains the vertices with no
+	/// in-adjacencies, and each later generation contains the vertices whose
+	/// predecessors all lie in earlier generations, so every edge leads to a
+	/// strictly later generation.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, w);
+	/// g.insert_edge(v, w);
+	/// assert_eq!(g.topological_generations().unwrap(), vec![vec![u, v], vec![w]]);
+	/// ```
+	fn topological_generations(&self) -> Result<Vec<Vec<Self::Vert>>, Cycle<Self>> {
+		topological_sort::topological_generations(self)
+	}
+
+	/// Returns the number of distinct paths from `source` to `target`, or an error
+	/// if the graph contains a cycle. There is exactly one, empty, path from a
+	/// vertex to itself. Counts saturate at `u128::MAX`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// g.insert_edge(u, w);
+	/// assert_eq!(g.count_paths(u, w), Ok(2));
+	/// assert_eq!(g.count_paths(w, u), Ok(0));
+	/// assert_eq!(g.count_paths(w, w), Ok(1));
+	/// ```
+	fn count_paths(&self, source: Self::Vert, target: Self::Vert) -> Result<u128, Cycle<Self>> {
+		let order = self.topological_sort()?;
+		let mut counts = self.ephemeral_vert_map(0u128);
+		*counts.get_mut(source) = 1;
+		for v in order {
+			let count = *counts.get(v).borrow();
+			if count == 0 {
+				continue;
+			}
+			for e in self.out_edges(v) {
+				let mut head_count = counts.get_mut(self.head(e));
+				*head_count = head_count.saturating_add(count);
+			}
+		}
+		let count = *counts.get(target).borrow();
+		Ok(count)
+	}
+
+	/// Returns whether the graph contains no directed cycles. The traversal stops
+	/// as soon as a cycle is found.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let v = g.insert_vert();
+	/// assert!(g.is_acyclic());
+	/// g.insert_edge(v, v);
+	/// assert!(!g.is_acyclic());
+	/// ```
+	fn is_acyclic(&self) -> bool {
+		!self
+			.depth_first_out()
+			.any(|event| matches!(event, DepthFirstEvent::BackEdge(_)))
+	}
+
+	/// Returns the edges of a directed cycle in the graph, if one exists. The head
+	/// of each edge is the tail of the next, and the head of the last edge is the
+	/// tail of the first.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// assert_eq!(g.find_cycle(), None);
+	/// let b = g.insert_edge(v, u);
+	/// assert_eq!(g.find_cycle(), Some(vec![a, b]));
+	/// ```
+	fn find_cycle(&self) -> Option<Vec<Self::Edge>> {
+		// The open edges form the path from the root of the current tree.
+		let mut path = Vec::new();
+		for event in self.depth_first_out() {
+			match event {
+				DepthFirstEvent::OpenEdge(e) => path.push(e),
+				DepthFirstEvent::CloseEdge(_) => {
+					path.pop();
+				}
+				DepthFirstEvent::BackEdge(e) => {
+					let ancestor = self.head(e);
+					let start = path
+						.iter()
+						.position(|d| self.tail(d) == ancestor)
+						.unwrap_or(path.len());
+					let mut cycle = path.split_off(start);
+					cycle.push(e);
+					return Some(cycle);
+				}
+				_ => {}
+			}
+		}
+		None
+	}
+
+	/// Returns a set of edges whose removal leaves the graph acyclic, found using
+	/// the greedy heuristic of Eades, Lin, and Smyth. Vertices are ordered by
+	/// repeatedly moving sinks to the back, sources to the front, and otherwise
+	/// the vertex maximizing out-degree minus in-degree to the front, and the
+	/// edges leading backward in that order, including self-loops, are returned.
+	/// The set is not necessarily minimum but is empty for an acyclic graph.
+	/// Vertices are bucketed by that degree difference, so this takes linear
+	/// time.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(u, v);
+	/// assert!(g.feedback_arc_set().is_empty());
+	/// let e = g.insert_edge(v, u);
+	/// assert_eq!(g.feedback_arc_set(), vec![e]);
+	/// ```
+	fn feedback_arc_set(&self) -> Vec<Self::Edge> {
+		// Degrees count edges among the remaining vertices, ignoring self-loops.
+		let mut out_degrees = self.ephemeral_vert_map(0usize);
+		let mut in_degrees = self.ephemeral_vert_map(0usize);
+		let mut predecessors = self.ephemeral_vert_map(Vec::new());
+		for e in self.edges() {
+			let (tail, head) = self.endpoints(e);
+			if tail != head {
+				*out_degrees.get_mut(tail) += 1;
+				*in_degrees.get_mut(head) += 1;
+				predecessors.get_mut(head).push(tail);
+			}
+		}
+		let mut sinks: Vec<_> = self
+			.verts()
+			.filter(|v| *out_degrees.get(*v).borrow() == 0)
+			.collect();
+		let mut sources: Vec<_> = self
+			.verts()
+			.filter(|v| *in_degrees.get(*v).borrow() == 0)
+			.collect();
+		// Other vertices are bucketed by out-degree minus in-degree, offset by the
+		// greatest in-degree. Buckets are updated lazily, so an entry is stale if
+		// its vertex has since been removed or moved to another bucket.
+		let max_degree = |degrees: &Self::EphemeralVertMap<'_, usize>| {
+			self.verts().map(|v| *degrees.get(v).borrow()).max()
+		};
+		let offset = max_degree(&in_degrees).unwrap_or(0);
+		let bucket = |out_degree: usize, in_degree: usize| out_degree + offset - in_degree;
+		let mut buckets = vec![Vec::new(); offset + max_degree(&out_degrees).unwrap_or(0) + 1];
+		for v in self.verts() {
+			buckets[bucket(*out_degrees.get(v).borrow(), *in_degrees.get(v).borrow())].push(v);
+		}
+		let mut top = buckets.len() - 1;
+		let mut remaining_count = self.verts().count();
+		let mut removed = self.ephemeral_vert_map(false);
+		let (mut front, mut back) = (Vec::new(), Vec::new());
+		while remaining_count > 0 {
+			// A vertex may be queued as both a sink and a source.
+			let v = if let Some(v) = sinks.pop() {
+				if *removed.get(v).borrow() {
+					continue;
+				}
+				back.push(v);
+				v
+			} else if let Some(v) = sources.pop() {
+				if *removed.get(v).borrow() {
+					continue;
+				}
+				front.push(v);
+				v
+			} else {
+				// Every remaining vertex has an entry in its bucket, at or below the top.
+				let v = loop {
+					match buckets[top].pop() {
+						Some(v)
+							if !*removed.get(v).borrow()
+								&& bucket(*out_degrees.get(v).borrow(), *in_degrees.get(v).borrow()) == top =>
+						{
+							break v
+						}
+						Some(_) => {}
+						None => top -= 1,
+					}
+				};
+				front.push(v);
+				v
+			};
+			*removed.get_mut(v) = true;
+			remaining_count -= 1;
+			// Degrees only decrease, so queued vertices remain sinks or sources.
+			for u in self.successors(v) {
+				if u != v && !*removed.get(u).borrow() {
+					let mut in_degree = in_degrees.get_mut(u);
+					*in_degree -= 1;
+					if *in_degree == 0 {
+						sources.push(u);
+					}
+					let b = bucket(*out_degrees.get(u).borrow(), *in_degree);
+					buckets[b].push(u);
+					top = top.max(b);
+				}
+			}
+			for &u in predecessors.get(v).borrow().iter() {
+				if !*removed.get(u).borrow() {
+					let mut out_degree = out_degrees.get_mut(u);
+					*out_degree -= 1;
+					if *out_degree == 0 {
+						sinks.push(u);
+					}
+					buckets[bucket(*out_degree, *in_degrees.get(u).borrow())].push(u);
+				}
+			}
+		}
+
+		let mut positions = self.ephemeral_vert_map(0usize);
+		for (i, v) in front.into_iter().chain(back.into_iter().rev()).enumerate() {
+			*positions.get_mut(v) = i;
+		}
+		self
+			.edges()
+			.filter(|e| {
+				let (tail, head) = self.endpoints(e);
+				*positions.get(tail).borrow() >= *positions.get(head).borrow()
+			})
+			.collect()
+	}
+
+	/// Returns the discovery and finish times of each vertex in a depth-first
+	/// traversal, counted by a single clock which advances whenever a vertex is
+	/// discovered or finished. The intervals between them are either disjoint or
+	/// nested, and a vertex is a descendant of another exactly when its interval
+	/// lies within the other's.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let (discovered, finished) = g.dfs_times();
+	/// assert_eq!((*discovered.get(u), *finished.get(u)), (0, 3));
+	/// assert_eq!((*discovered.get(v), *finished.get(v)), (1, 2));
+	/// ```
+	fn dfs_times(
+		&self,
+	) -> (
+		Self::EphemeralVertMap<'_, usize>,
+		Self::EphemeralVertMap<'_, usize>,
+	) {
+		let mut discovered = self.default_ephemeral_vert_map();
+		let mut finished = self.default_ephemeral_vert_map();
+		let mut clock = 0;
+		let mut root = None;
+		for event in self.depth_first_out() {
+			let (times, v) = match event {
+				DepthFirstEvent::StartTree(v) => {
+					root = Some(v);
+					(&mut discovered, v)
+				}
+				DepthFirstEvent::OpenEdge(e) => (&mut discovered, self.head(e)),
+				DepthFirstEvent::CloseEdge(e) => (&mut finished, self.head(e)),
+				DepthFirstEvent::EndTree => (&mut finished, root.take().expect("tree started")),
+				_ => continue,
+			};
+			*times.get_mut(v) = clock;
+			clock += 1;
+		}
+		(discovered, finished)
+	}
+
+	/// Returns a closed walk which traverses every edge exactly once, or `None` if
+	/// no such walk exists, using Hierholzer's algorithm. A walk exists exactly
+	/// when every vertex has equal in- and out-degree and the edges are all
+	/// connected.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// assert_eq!(g.eulerian_circuit(), None);
+	/// let b = g.insert_edge(v, u);
+	/// assert_eq!(g.eulerian_circuit(), Some(vec![a, b]));
+	/// ```
+	fn eulerian_circuit(&self) -> Option<Vec<Self::Edge>> {
+		let in_degrees = topological_sort::in_degrees(self);
+		let mut edge_count = 0;
+		for v in self.verts() {
+			let out_degree = self.out_edges(v).count();
+			if out_degree != *in_degrees.get(v).borrow() {
+				return None;
+			}
+			edge_count += out_degree;
+		}
+		let start = match self.edges().next() {
+			Some(e) => self.tail(e),
+			None => return Some(Vec::new()),
+		};
+
+		// The unused out-adjacencies of each vertex visited so far.
+		let mut remaining = self.ephemeral_vert_map(None);
+		let mut stack = vec![(start, None)];
+		let mut circuit = Vec::with_capacity(edge_count);
+		while let Some(&(v, edge)) = stack.last() {
+			let next = remaining
+				.get_mut(v)
+				.get_or_insert_with(|| self.out_edges(v))
+				.next();
+			match next {
+				Some(e) => stack.push((self.head(e), Some(e))),
+				None => {
+					stack.pop();
+					circuit.extend(edge);
+				}
+			}
+		}
+		circuit.reverse();
+
+		// With balanced degrees, the circuit misses edges only if they are
+		// disconnected from the start.
+		if circuit.len() == edge_count {
+			Some(circuit)
+		} else {
+			None
+		}
 	}
 
-	/// Returns a map from target vertices to the total cost of the shortest path from the given source and the last edge in that path. Assumes `d + costs.get(e) >= d` for every edge `e` in the graph and `d: D`.
-	fn dijkstra<C: Clone, D: Clone + Ord>(
-		&self,
-		costs: &impl Map<Self::Edge, Value = C>,
-		source: Self::Vert,
-		zero: D,
-	) -> Self::EphemeralVertMap<'_, Option<D>>
-	where
-		D: Add<C, Output = D>,
-	{
-		let mut queue = BinaryHeap::new(self.ephemeral_vert_map(None));
-		let mut distances = self.ephemeral_vert_map(None);
-		queue.try_decrease(source, zero);
-		while let Some((v, d)) = queue.pop() {
-			*distances.get_mut(v) = Some(d.clone());
-			for e in self.out_edges(v) {
-				let u = self.head(e);
-				if distances.get(u).borrow().is_none() {
-					queue.try_decrease(u, d.clone() + costs.get(e).borrow().clone());
+	/// Returns the line graph, which has a vertex for each edge of this graph and
+	/// an edge from `d` to `e` whenever the head of `d` is the tail of `e`, along
+	/// with the map from edges to their vertices in the line graph. A self-loop
+	/// becomes a vertex with a self-loop.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let d = g.insert_edge(u, v);
+	/// let e = g.insert_edge(v, u);
+	/// let (line_graph, verts) = g.line_graph();
+	/// let (d, e) = (verts.get(d).unwrap(), verts.get(e).unwrap());
+	/// assert!(line_graph.has_edge(d, e) && line_graph.has_edge(e, d));
+	/// assert!(!line_graph.has_edge(d, d));
+	/// ```
+	fn line_graph(
+		&self,
+	) -> (
+		SparseOutAdjacencyList,
+		Self::EphemeralEdgeMap<'_, Option<sparse_out_adjacency_list::Vert>>,
+	) {
+		let mut line_graph = SparseOutAdjacencyList::new();
+		let mut verts = self.ephemeral_edge_map(None);
+		for e in self.edges() {
+			*verts.get_mut(e) = Some(line_graph.insert_vert());
+		}
+		for d in self.edges() {
+			let tail = verts.get(d).borrow().expect("edge has vertex");
+			for e in self.out_edges(self.head(d)) {
+				let head = verts.get(e).borrow().expect("edge has vertex");
+				line_graph.insert_edge(tail, head);
+			}
+		}
+		(line_graph, verts)
+	}
+
+	/// Returns a spanning forest of the graph found by breadth-first traversals
+	/// from each vertex not yet reached, in the order of `verts`, along with a
+	/// map from vertices to their parents in the forest, or `None` for the root
+	/// of each tree. The forest has a vertex for each vertex of this graph and
+	/// an edge from each parent to its child.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, u);
+	/// g.insert_edge(w, v);
+	/// let (forest, parents) = g.bfs_forest();
+	/// assert_eq!((forest.verts().count(), forest.edges().count()), (3, 1));
+	/// assert_eq!((*parents.get(u), *parents.get(v), *parents.get(w)), (None, Some(u), None));
+	/// ```
+	fn bfs_forest(
+		&self,
+	) -> (
+		SparseOutAdjacencyList,
+		Self::EphemeralVertMap<'_, Option<Self::Vert>>,
+	) {
+		let mut forest = SparseOutAdjacencyList::new();
+		let mut forest_verts = self.ephemeral_vert_map(None);
+		let mut parents = self.ephemeral_vert_map(None);
+		for root in self.verts() {
+			if forest_verts.get(root).borrow().is_some() {
+				continue;
+			}
+			*forest_verts.get_mut(root) = Some(forest.insert_vert());
+			let mut queue = VecDeque::new();
+			queue.push_back(root);
+			while let Some(v) = queue.pop_front() {
+				let tail = forest_verts
+					.get(v)
+					.borrow()
+					.expect("queued vertex is in forest");
+				for u in self.successors(v) {
+					if forest_verts.get(u).borrow().is_none() {
+						let head = forest.insert_vert();
+						forest.insert_edge(tail, head);
+						*forest_verts.get_mut(u) = Some(head);
+						*parents.get_mut(u) = Some(v);
+						queue.push_back(u);
+					}
+				}
+			}
+		}
+		(forest, parents)
+	}
+
+	/// Returns the subgraph induced by the selected vertices, which contains
+	/// those vertices and every edge with both endpoints among them, along with
+	/// the map from selected vertices to their vertices in the subgraph. Unlike
+	/// `Filtered`, the subgraph is independent of this graph.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let (subgraph, verts) = g.induced_subgraph(&|x| x != w);
+	/// assert_eq!((subgraph.verts().count(), subgraph.edges().count()), (2, 1));
+	/// assert!(subgraph.has_edge(verts.get(u).unwrap(), verts.get(v).unwrap()));
+	/// assert_eq!(*verts.get(w), None);
+	/// ```
+	fn induced_subgraph(
+		&self,
+		verts: &impl Map<Self::Vert, Value = bool>,
+	) -> (
+		SparseBiAdjacencyList,
+		Self::EphemeralVertMap<'_, Option<sparse_bi_adjacency_list::Vert>>,
+	) {
+		let mut subgraph = SparseBiAdjacencyList::new();
+		let mut vmap = self.ephemeral_vert_map(None);
+		for v in self.verts() {
+			if *verts.get(v).borrow() {
+				*vmap.get_mut(v) = Some(subgraph.insert_vert());
+			}
+		}
+		for e in self.edges() {
+			let (tail, head) = self.endpoints(e);
+			if let (Some(tail), Some(head)) = (*vmap.get(tail).borrow(), *vmap.get(head).borrow()) {
+				subgraph.insert_edge(tail, head);
+			}
+		}
+		(subgraph, vmap)
+	}
+
+	/// Returns the strongly connected components of the graph in reverse
+	/// topological order of its condensation, that is, no edge leads from a
+	/// component to a later one.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.strongly_connected_components(), vec![vec![v], vec![u]]);
+	/// ```
+	fn strongly_connected_components(&self) -> Vec<Vec<Self::Vert>> {
+		strongly_connected::tarjan(self)
+	}
+
+	/// Returns whether a given graph is isomorphic to this graph, that is,
+	/// whether there are bijections between their vertices and edges which
+	/// preserve endpoints. Uses the VF2 algorithm, pruning candidate vertex
+	/// pairings by degree.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2)]);
+	/// let h = DenseOutAdjacencyList::from_edges(3, vec![(2, 0), (1, 2)]);
+	/// assert!(g.is_isomorphic(&h));
+	/// let h = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (0, 2)]);
+	/// assert!(!g.is_isomorphic(&h));
+	/// ```
+	fn is_isomorphic<G: OutGraph>(&self, other: &G) -> bool {
+		isomorphism::is_isomorphic(self, other)
+	}
+
+	/// Returns an embedding of a pattern graph into this graph, if one exists.
+	/// The embedding maps distinct vertices and edges of the pattern to distinct
+	/// vertices and edges of this graph and preserves endpoints, but edges
+	/// between mapped vertices need not be in the image. Uses the VF2 algorithm,
+	/// so it is best suited to small patterns.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2), (2, 0)]);
+	/// let path = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2)]);
+	/// let embedding = g.find_subgraph_isomorphism(&path).unwrap();
+	/// for e in path.edges() {
+	///     let (tail, head) = path.endpoints(e);
+	///     let image = embedding.map_edge(e);
+	///     assert_eq!(g.endpoints(image), (embedding.map_vert(tail), embedding.map_vert(head)));
+	/// }
+	/// let two_cycle = DenseOutAdjacencyList::from_edges(2, vec![(0, 1), (1, 0)]);
+	/// assert!(g.find_subgraph_isomorphism(&two_cycle).is_none());
+	/// ```
+	fn find_subgraph_isomorphism<'p, P: OutGraph>(
+		&self,
+		pattern: &'p P,
+	) -> Option<Homomorphism<'p, P, Self>>
+	where
+		Self: Sized,
+	{
+		isomorphism::find_subgraph_isomorphism(self, pattern)
+	}
+
+	/// Returns a map from target vertices to the total cost of the shortest path from the given source and the last edge in that path. Assumes `d + costs.get(e) >= d` for every edge `e` in the graph and `d: D`.
+	fn dijkstra<C: Clone, D: Clone + Ord>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		zero: D,
+	) -> Self::EphemeralVertMap<'_, Option<D>>
+	where
+		D: Add<C, Output = D>,
+	{
+		let mut queue = BinaryHeap::new(self.ephemeral_vert_map(None));
+		let mut distances = self.ephemeral_vert_map(None);
+		queue.try_decrease(source, zero);
+		while let Some((v, d)) = queue.pop() {
+			*distances.get_mut(v) = Some(d.clone());
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if distances.get(u).borrow().is_none() {
+					queue.try_decrease(u, d.clone() + costs.get(e).borrow().clone());
+				}
+			}
+		}
+		distances
+	}
+
+	/// Returns a map from target vertices to the total cost of the second
+	/// cheapest walk from the given source, or `None` if there are fewer than
+	/// two. Walks may repeat edges and are distinct if their edge sequences
+	/// differ, so the cost may equal that of the shortest path. The empty walk
+	/// is the cheapest from the source to itself. Assumes `d + costs.get(e) >= d`
+	/// for every edge `e` in the graph and `d: D`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let e = g.insert_edge(u, v);
+	/// g.insert_edge(v, u);
+	/// let distances = g.second_shortest(&|d| if d == e { 1 } else { 2 }, u, 0);
+	/// assert_eq!((*distances.get(u), *distances.get(v)), (Some(3), Some(4)));
+	/// ```
+	fn second_shortest<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		zero: D,
+	) -> Self::EphemeralVertMap<'_, Option<D>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		// Each vertex is popped once for each of its two cheapest walks, so the
+		// queue holds an entry per candidate walk rather than per vertex.
+		let mut queue = std::collections::BinaryHeap::new();
+		let mut pops = self.ephemeral_vert_map(0u8);
+		let mut distances = self.ephemeral_vert_map(None);
+		queue.push(Reverse((zero, source)));
+		while let Some(Reverse((d, v))) = queue.pop() {
+			let popped = {
+				let mut popped = pops.get_mut(v);
+				if *popped == 2 {
+					continue;
+				}
+				*popped += 1;
+				*popped
+			};
+			if popped == 2 {
+				*distances.get_mut(v) = Some(d.clone());
+			}
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if *pops.get(u).borrow() < 2 {
+					queue.push(Reverse((d.clone() + costs.get(e).borrow().clone(), u)));
+				}
+			}
+		}
+		distances
+	}
+
+	/// Like `dijkstra`, but additionally returns a map from each reachable vertex
+	/// other than the source to the last edge in a shortest path to it.
+	#[allow(clippy::type_complexity)]
+	fn dijkstra_paths<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		zero: D,
+	) -> (
+		Self::EphemeralVertMap<'_, Option<D>>,
+		Self::EphemeralVertMap<'_, Option<Self::Edge>>,
+	)
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		let mut queue = BinaryHeap::new(self.ephemeral_vert_map(None));
+		let mut distances = self.ephemeral_vert_map(None);
+		let mut preds = self.ephemeral_vert_map(None);
+		queue.try_decrease(source, zero);
+		while let Some((v, d)) = queue.pop() {
+			*distances.get_mut(v) = Some(d.clone());
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if distances.get(u).borrow().is_none()
+					&& queue.try_decrease(u, d.clone() + costs.get(e).borrow().clone())
+				{
+					*preds.get_mut(u) = Some(e);
+				}
+			}
+		}
+		(distances, preds)
+	}
+
+	/// Returns the edges of the path to a target encoded by a map from vertices to
+	/// the last edge in the path to them, such as that returned by
+	/// `dijkstra_paths`. The path is empty if the target has no such edge.
+	fn reconstruct_path(
+		&self,
+		preds: &impl Map<Self::Vert, Value = Option<Self::Edge>>,
+		target: Self::Vert,
+	) -> Vec<Self::Edge> {
+		let mut path = Vec::new();
+		let mut v = target;
+		while let Some(e) = *preds.get(v).borrow() {
+			path.push(e);
+			v = self.tail(e);
+		}
+		path.reverse();
+		path
+	}
+
+	/// Returns a map from vertices to the total cost of the shortest path from
+	/// the given source, or `None` if the vertex is unreachable, using the
+	/// shortest path faster algorithm (SPFA), a queue-based variant of
+	/// Bellman-Ford. Costs may be negative, but an error is returned if a
+	/// negative cycle is reachable from the source.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// let b = g.insert_edge(u, w);
+	/// g.insert_edge(w, v);
+	/// let costs = |e| if e == a { 1 } else if e == b { 2 } else { -2 };
+	/// assert_eq!(*g.spfa(&costs, u, 0).unwrap().get(v), Some(0));
+	/// g.insert_edge(v, w);
+	/// assert!(g.spfa(&costs, u, 0).is_err());
+	/// ```
+	#[allow(clippy::type_complexity)]
+	fn spfa<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		zero: D,
+	) -> Result<Self::EphemeralVertMap<'_, Option<D>>, NegativeCycle<Self>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		bellman_ford::spfa(self, costs, std::iter::once((source, zero)))
+	}
+
+	/// Returns each vertex along with a map from vertices to the total cost of
+	/// the shortest path to them from it, or `None` if they are unreachable,
+	/// using Johnson's algorithm. Costs may be negative, but an error is returned
+	/// if the graph contains a negative cycle. Assumes `zero` is an identity for
+	/// adding distances and that subtraction undoes addition.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// g.insert_edge(u, w);
+	/// let costs = |e| if e == a { -2 } else { 1 };
+	/// let distances = g.johnson(&costs, 0).unwrap();
+	/// assert_eq!(distances[0].0, u);
+	/// assert_eq!(*distances[0].1.get(w), Some(-1));
+	/// assert_eq!(*distances[2].1.get(u), None);
+	/// ```
+	#[allow(clippy::type_complexity)]
+	fn johnson<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		zero: D,
+	) -> Result<Vec<(Self::Vert, Self::EphemeralVertMap<'_, Option<D>>)>, NegativeCycle<Self>>
+	where
+		D: Clone + Ord + Add<C, Output = D> + Add<Output = D> + Sub<Output = D>,
+	{
+		bellman_ford::johnson(self, costs, zero)
+	}
+
+	/// Returns a map from vertices to the total cost of the shortest path from
+	/// the given source using at most `k` edges, or `None` if no such path
+	/// exists, using `k` rounds of Bellman-Ford relaxation. Costs may be
+	/// negative.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let shortcut = g.insert_edge(u, w);
+	/// let costs = |e| if e == shortcut { 3 } else { 1 };
+	/// assert_eq!(*g.shortest_path_bounded(&costs, u, 1, 0).get(w), Some(3));
+	/// assert_eq!(*g.shortest_path_bounded(&costs, u, 2, 0).get(w), Some(2));
+	/// ```
+	fn shortest_path_bounded<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		k: usize,
+		zero: D,
+	) -> Self::EphemeralVertMap<'_, Option<D>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		bellman_ford::shortest_path_bounded(self, costs, source, k, zero)
+	}
+
+	/// Returns the edges of a directed cycle with negative total cost, if one
+	/// exists, using the Bellman-Ford algorithm. The head of each edge is the
+	/// tail of the next, and the head of the last edge is the tail of the first.
+	/// Distances start at `zero` from every vertex, so cycles need not be
+	/// reachable from any particular source.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// let a = g.insert_edge(u, v);
+	/// let b = g.insert_edge(v, u);
+	/// assert_eq!(g.find_negative_cycle(&|_| -1, 0), Some(vec![a, b]));
+	/// assert_eq!(g.find_negative_cycle(&|e| if e == a { -1 } else { 1 }, 0), None);
+	/// ```
+	fn find_negative_cycle<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		zero: D,
+	) -> Option<Vec<Self::Edge>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		let order = self.verts().count();
+		let mut distances = self.ephemeral_vert_map(zero);
+		let mut preds = self.ephemeral_vert_map(None);
+		let mut relaxed = None;
+		// Without negative cycles, distances settle within `order - 1` rounds, so
+		// a relaxation in the last round means there is one.
+		for _ in 0..order {
+			relaxed = None;
+			for v in self.verts() {
+				for e in self.out_edges(v) {
+					let u = self.head(e);
+					let d = distances.get(v).borrow().clone() + costs.get(e).borrow().clone();
+					if d < *distances.get(u).borrow() {
+						*distances.get_mut(u) = d;
+						*preds.get_mut(u) = Some(e);
+						relaxed = Some(u);
+					}
+				}
+			}
+			relaxed?;
+		}
+		// Walking back `order` predecessors from a relaxed vertex reaches a cycle.
+		let mut v = relaxed?;
+		for _ in 0..order {
+			v = self.tail(
+				preds
+					.get(v)
+					.borrow()
+					.expect("relaxed vertex has predecessor"),
+			);
+		}
+		let mut cycle = Vec::new();
+		let mut u = v;
+		loop {
+			let e = preds.get(u).borrow().expect("cycle vertex has predecessor");
+			cycle.push(e);
+			u = self.tail(e);
+			if u == v {
+				break;
+			}
+		}
+		cycle.reverse();
+		Some(cycle)
+	}
+
+	/// Returns up to `k` shortest loopless paths from `source` to `target` along
+	/// with their total costs, in nondecreasing order of cost, using Yen's
+	/// algorithm. Fewer than `k` paths are returned if the graph does not contain
+	/// them. Assumes `d + costs.get(e) >= d` for every edge `e` in the graph and
+	/// `d: D`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let s = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let t = g.insert_vert();
+	/// let a = g.insert_edge(s, t);
+	/// let b = g.insert_edge(s, v);
+	/// let c = g.insert_edge(v, t);
+	/// let paths = g.k_shortest_paths(&|e| if e == a { 3 } else { 1 }, s, t, 3, 0);
+	/// assert_eq!(paths, vec![(2, vec![b, c]), (3, vec![a])]);
+	/// ```
+	fn k_shortest_paths<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		target: Self::Vert,
+		k: usize,
+		zero: D,
+	) -> Vec<(D, Vec<Self::Edge>)>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		let mut paths: Vec<(D, Vec<Self::Edge>)> = Vec::new();
+		if k == 0 {
+			return paths;
+		}
+		let (distances, preds) = self.dijkstra_paths(costs, source, zero.clone());
+		match distances.get(target).borrow() {
+			Some(distance) => paths.push((distance.clone(), self.reconstruct_path(&preds, target))),
+			None => return paths,
+		}
+		let mut candidates: Vec<(D, Vec<Self::Edge>)> = Vec::new();
+		while paths.len() < k {
+			let (_, last) = paths.last().expect("at least one path");
+			let mut spur = source;
+			for (i, &edge) in last.iter().enumerate() {
+				let root = &last[..i];
+				// Exclude the edges which continue any known path sharing this root,
+				// and the vertices of the root other than the spur to keep the path
+				// loopless.
+				let excluded_edges: HashSet<_> = paths
+					.iter()
+					.filter(|(_, path)| path.len() > i && path[..i] == *root)
+					.map(|(_, path)| path[i])
+					.collect();
+				let excluded_verts: HashSet<_> = root.iter().map(|e| self.tail(e)).collect();
+				let filtered = Filtered::new(
+					self,
+					|v| !excluded_verts.contains(&v),
+					|e| !excluded_edges.contains(&e),
+				);
+				let (distances, preds) = filtered.dijkstra_paths(costs, spur, zero.clone());
+				if distances.get(target).borrow().is_some() {
+					let mut path = root.to_vec();
+					path.extend(filtered.reconstruct_path(&preds, target));
+					if !candidates.iter().any(|(_, candidate)| *candidate == path) {
+						let cost = path
+							.iter()
+							.fold(zero.clone(), |d, &e| d + costs.get(e).borrow().clone());
+						candidates.push((cost, path));
+					}
+				}
+				spur = self.head(edge);
+			}
+			let best = match (0..candidates.len()).min_by(|&i, &j| candidates[i].0.cmp(&candidates[j].0))
+			{
+				Some(best) => best,
+				None => break,
+			};
+			paths.push(candidates.swap_remove(best));
+		}
+		paths
+	}
+
+	/// Returns a map from vertices to whether they are reachable from the given
+	/// source, which is always reachable from itself.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let reachable = g.reachable_from(u);
+	/// assert!(*reachable.get(u) && *reachable.get(v));
+	/// assert!(!*g.reachable_from(v).get(u));
+	/// ```
+	fn reachable_from(&self, source: Self::Vert) -> Self::EphemeralVertMap<'_, bool> {
+		let mut reachable = self.ephemeral_vert_map(false);
+		*reachable.get_mut(source) = true;
+		let mut stack = vec![source];
+		while let Some(v) = stack.pop() {
+			for u in self.successors(v) {
+				let mut visited = reachable.get_mut(u);
+				if !*visited {
+					*visited = true;
+					stack.push(u);
+				}
+			}
+		}
+		reachable
+	}
+
+	/// Returns a map from vertices to the number of edges in a shortest path to
+	/// them from the given source, or `None` if they are unreachable.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let distances = g.bfs_distances(u);
+	/// assert_eq!(*distances.get(u), Some(0));
+	/// assert_eq!(*distances.get(v), Some(1));
+	/// assert_eq!(*g.bfs_distances(v).get(u), None);
+	/// ```
+	fn bfs_distances(&self, source: Self::Vert) -> Self::EphemeralVertMap<'_, Option<u32>> {
+		let mut distances = self.ephemeral_vert_map(None);
+		*distances.get_mut(source) = Some(0);
+		let mut layer = vec![source];
+		let mut d = 0;
+		while !layer.is_empty() {
+			d += 1;
+			let mut next_layer = Vec::new();
+			for v in layer {
+				for u in self.successors(v) {
+					if distances.get(u).borrow().is_none() {
+						*distances.get_mut(u) = Some(d);
+						next_layer.push(u);
+					}
+				}
+			}
+			layer = next_layer;
+		}
+		distances
+	}
+
+	/// Runs `bfs_distances` from each of the given sources in parallel and
+	/// returns each source along with its distances, in the order given.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// let distances = g.par_bfs_distances_from(&[u, v]);
+	/// assert_eq!(*distances[0].1.get(v), Some(1));
+	/// assert_eq!(*distances[1].1.get(u), None);
+	/// ```
+	#[cfg(feature = "rayon")]
+	#[allow(clippy::type_complexity)]
+	fn par_bfs_distances_from<'a>(
+		&'a self,
+		sources: &[Self::Vert],
+	) -> Vec<(Self::Vert, Self::EphemeralVertMap<'a, Option<u32>>)>
+	where
+		Self: Sync,
+		Self::Vert: Send + Sync,
+		Self::EphemeralVertMap<'a, Option<u32>>: Send,
+	{
+		use rayon::prelude::*;
+		sources
+			.par_iter()
+			.map(|source| (*source, self.bfs_distances(*source)))
+			.collect()
+	}
+
+	/// Like `bfs_distances`, but additionally returns a map from each reachable
+	/// vertex other than the source to the last edge in a shortest path to it.
+	/// Paths can be recovered with `reconstruct_path`.
+	#[allow(clippy::type_complexity)]
+	fn bfs_paths(
+		&self,
+		source: Self::Vert,
+	) -> (
+		Self::EphemeralVertMap<'_, Option<u32>>,
+		Self::EphemeralVertMap<'_, Option<Self::Edge>>,
+	) {
+		let mut distances = self.ephemeral_vert_map(None);
+		let mut preds = self.ephemeral_vert_map(None);
+		*distances.get_mut(source) = Some(0);
+		let mut queue = VecDeque::new();
+		queue.push_back((source, 0));
+		while let Some((v, d)) = queue.pop_front() {
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if distances.get(u).borrow().is_none() {
+					*distances.get_mut(u) = Some(d + 1);
+					*preds.get_mut(u) = Some(e);
+					queue.push_back((u, d + 1));
+				}
+			}
+		}
+		(distances, preds)
+	}
+
+	/// Returns a map from vertices to their eccentricity, that is, the greatest
+	/// number of edges in a shortest path from them to any vertex they reach.
+	/// Unreachable vertices are ignored, so a vertex with no out-adjacencies has
+	/// eccentricity zero.
+	fn eccentricities(&self) -> Self::EphemeralVertMap<'_, u32> {
+		let mut eccentricities = self.ephemeral_vert_map(0);
+		for v in self.verts() {
+			let distances = self.bfs_distances(v);
+			let eccentricity = self
+				.verts()
+				.filter_map(|u| *distances.get(u).borrow())
+				.max()
+				.unwrap_or(0);
+			*eccentricities.get_mut(v) = eccentricity;
+		}
+		eccentricities
+	}
+
+	/// Returns the greatest eccentricity of any vertex, or `None` if the graph has
+	/// no vertices.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// assert_eq!(g.diameter(), None);
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// assert_eq!(g.diameter(), Some(2));
+	/// assert_eq!(*g.eccentricities().get(v), 1);
+	/// ```
+	fn diameter(&self) -> Option<u32> {
+		let eccentricities = self.eccentricities();
+		self.verts().map(|v| *eccentricities.get(v).borrow()).max()
+	}
+
+	/// Returns the least eccentricity of any vertex, or `None` if the graph has no
+	/// vertices. Since eccentricities ignore unreachable vertices, any vertex
+	/// with no out-adjacencies gives a radius of zero.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// assert_eq!(g.radius(), None);
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// g.insert_edge(w, v);
+	/// assert_eq!(g.radius(), Some(1));
+	/// ```
+	fn radius(&self) -> Option<u32> {
+		let eccentricities = self.eccentricities();
+		self.verts().map(|v| *eccentricities.get(v).borrow()).min()
+	}
+
+	/// Returns the vertices whose eccentricity is the radius, in the order of
+	/// `verts`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// g.insert_edge(w, v);
+	/// assert_eq!(g.center(), vec![v, w]);
+	/// ```
+	fn center(&self) -> Vec<Self::Vert> {
+		let eccentricities = self.eccentricities();
+		let radius = self.verts().map(|v| *eccentricities.get(v).borrow()).min();
+		self
+			.verts()
+			.filter(|v| Some(*eccentricities.get(*v).borrow()) == radius)
+			.collect()
+	}
+
+	/// Returns the number of edges in a shortest directed cycle, or `None` if the
+	/// graph is acyclic. A self-loop is a cycle of length one.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// g.insert_edge(u, v);
+	/// assert_eq!(g.girth(), None);
+	/// g.insert_edge(v, u);
+	/// assert_eq!(g.girth(), Some(2));
+	/// g.insert_edge(v, v);
+	/// assert_eq!(g.girth(), Some(1));
+	/// ```
+	fn girth(&self) -> Option<u32> {
+		let mut girth: Option<u32> = None;
+		for source in self.verts() {
+			// Search for the nearest edge back to the source, abandoning the search
+			// once it cannot improve on the shortest cycle so far.
+			let mut distances = self.ephemeral_vert_map(None);
+			*distances.get_mut(source) = Some(0);
+			let mut queue = VecDeque::new();
+			queue.push_back((source, 0));
+			'search: while let Some((v, d)) = queue.pop_front() {
+				if matches!(girth, Some(girth) if d + 1 >= girth) {
+					break;
+				}
+				for u in self.successors(v) {
+					if u == source {
+						girth = Some(d + 1);
+						break 'search;
+					}
+					if distances.get(u).borrow().is_none() {
+						*distances.get_mut(u) = Some(d + 1);
+						queue.push_back((u, d + 1));
+					}
+				}
+			}
+		}
+		girth
+	}
+
+	/// Like `dijkstra`, but for small integer costs using Dial's algorithm, which
+	/// replaces the heap with a circular array of `max_cost + 1` buckets of
+	/// vertices indexed by tentative distance.
+	///
+	/// # Panics
+	/// Panics if an edge reachable from the source costs more than `max_cost`.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let shortcut = g.insert_edge(u, w);
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let distances = g.dijkstra_buckets(&|e| if e == shortcut { 3 } else { 1 }, u, 3);
+	/// assert_eq!(*distances.get(w), Some(2));
+	/// ```
+	fn dijkstra_buckets(
+		&self,
+		costs: &impl Map<Self::Edge, Value = u32>,
+		source: Self::Vert,
+		max_cost: u32,
+	) -> Self::EphemeralVertMap<'_, Option<u32>> {
+		let mut distances = self.ephemeral_vert_map(None);
+		let mut settled = self.ephemeral_vert_map(false);
+		let bucket_count = max_cost as usize + 1;
+		let mut buckets = vec![Vec::new(); bucket_count];
+		*distances.get_mut(source) = Some(0);
+		buckets[0].push(source);
+		let mut queued = 1usize;
+		let mut d = 0u32;
+		while queued > 0 {
+			let bucket = d as usize % bucket_count;
+			// Zero-cost edges may add to the bucket while it is being emptied.
+			while let Some(v) = buckets[bucket].pop() {
+				queued -= 1;
+				// Vertices are not removed from buckets when their distance decreases,
+				// so skip stale entries.
+				if *settled.get(v).borrow() || *distances.get(v).borrow() != Some(d) {
+					continue;
+				}
+				*settled.get_mut(v) = true;
+				for e in self.out_edges(v) {
+					let c = *costs.get(e).borrow();
+					assert!(c <= max_cost, "edge cost exceeds max_cost");
+					let u = self.head(e);
+					let d_u = d + c;
+					let improved = match *distances.get(u).borrow() {
+						Some(d_prime) => d_u < d_prime,
+						None => true,
+					};
+					if improved && !*settled.get(u).borrow() {
+						*distances.get_mut(u) = Some(d_u);
+						buckets[d_u as usize % bucket_count].push(u);
+						queued += 1;
+					}
+				}
+			}
+			d += 1;
+		}
+		distances
+	}
+
+	/// Like `dijkstra`, but for edges which cost either zero or one, as given by
+	/// `weights`. This uses a double-ended queue in place of a heap and runs in
+	/// linear time.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let free = g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let distances = g.zero_one_bfs(&|e| e != free, u);
+	/// assert_eq!(*distances.get(v), Some(0));
+	/// assert_eq!(*distances.get(w), Some(1));
+	/// ```
+	fn zero_one_bfs(
+		&self,
+		weights: &impl Map<Self::Edge, Value = bool>,
+		source: Self::Vert,
+	) -> Self::EphemeralVertMap<'_, Option<u32>> {
+		let mut distances = self.ephemeral_vert_map(None);
+		let mut settled = self.ephemeral_vert_map(false);
+		let mut queue = VecDeque::new();
+		*distances.get_mut(source) = Some(0);
+		queue.push_back(source);
+		while let Some(v) = queue.pop_front() {
+			// A vertex is queued again each time its distance decreases, so skip all
+			// but the first time it is dequeued.
+			if *settled.get(v).borrow() {
+				continue;
+			}
+			*settled.get_mut(v) = true;
+			let d = distances
+				.get(v)
+				.borrow()
+				.expect("queued vertex has distance");
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if *settled.get(u).borrow() {
+					continue;
+				}
+				let weight = *weights.get(e).borrow();
+				let d_u = d + weight as u32;
+				let improved = match *distances.get(u).borrow() {
+					Some(d_prime) => d_u < d_prime,
+					None => true,
+				};
+				if improved {
+					*distances.get_mut(u) = Some(d_u);
+					if weight {
+						queue.push_back(u);
+					} else {
+						queue.push_front(u);
+					}
+				}
+			}
+		}
+		distances
+	}
+
+	/// Returns a map from vertices to the greatest bottleneck capacity of a path
+	/// to them from the given source, that is, the maximum over paths of the
+	/// minimum capacity of their edges, or `None` if they are unreachable. The
+	/// source itself has capacity `unbounded`, which should be at least the
+	/// capacity of every edge.
+	///
+	/// # Examples
+	/// ```
+	/// # use sif::*;
+	/// # let mut g = DenseOutAdjacencyList::new();
+	/// # let u = g.insert_vert();
+	/// # let v = g.insert_vert();
+	/// # let w = g.insert_vert();
+	/// let narrow = g.insert_edge(u, w);
+	/// g.insert_edge(u, v);
+	/// g.insert_edge(v, w);
+	/// let capacities = g.widest_path(&|e| if e == narrow { 1 } else { 5 }, u, u32::MAX);
+	/// assert_eq!(*capacities.get(w), Some(5));
+	/// assert_eq!(*g.widest_path(&|_| 1, w, u32::MAX).get(u), None);
+	/// ```
+	fn widest_path<C: Clone + Ord>(
+		&self,
+		capacities: &impl Map<Self::Edge, Value = C>,
+		source: Self::Vert,
+		unbounded: C,
+	) -> Self::EphemeralVertMap<'_, Option<C>> {
+		// Popping the least reversed key settles the widest vertex first.
+		let mut queue = BinaryHeap::new(self.ephemeral_vert_map(None));
+		let mut widths = self.ephemeral_vert_map(None);
+		queue.try_decrease(source, Reverse(unbounded));
+		while let Some((v, Reverse(width))) = queue.pop() {
+			*widths.get_mut(v) = Some(width.clone());
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				if widths.get(u).borrow().is_none() {
+					let capacity = capacities.get(e).borrow().clone();
+					queue.try_decrease(u, Reverse(width.clone().min(capacity)));
+				}
+			}
+		}
+		widths
+	}
+
+	/// Returns a map from vertices to their betweenness centrality, that is, the
+	/// sum over pairs of distinct other vertices of the fraction of shortest
+	/// paths between them which pass through the vertex. Paths are measured in
+	/// edges.
+	fn betweenness_centrality(&self) -> Self::EphemeralVertMap<'_, f64> {
+		centrality::betweenness(self)
+	}
+
+	/// Like `betweenness_centrality`, but with paths measured by the total cost
+	/// of their edges. Costs must be positive.
+	fn betweenness_centrality_weighted<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		zero: D,
+	) -> Self::EphemeralVertMap<'_, f64>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		centrality::betweenness_weighted(self, costs, zero)
+	}
+
+	/// Returns a map from vertices to the total cost of the longest path ending at
+	/// them, or an error if the graph contains a cycle. The empty path has cost
+	/// `zero`, so every vertex is mapped to at least `zero`.
+	fn longest_path<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		zero: D,
+	) -> Result<Self::EphemeralVertMap<'_, D>, Cycle<Self>>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		self
+			.longest_path_with_preds(costs, zero)
+			.map(|(distances, _)| distances)
+	}
+
+	/// Like `longest_path`, but additionally returns a map from each vertex to
+	/// the last edge in a longest path ending at it, if that path is nonempty.
+	/// The path can be recovered with `reconstruct_path`.
+	#[allow(clippy::type_complexity)]
+	fn longest_path_with_preds<C: Clone, D>(
+		&self,
+		costs: &impl Map<Self::Edge, Value = C>,
+		zero: D,
+	) -> Result<
+		(
+			Self::EphemeralVertMap<'_, D>,
+			Self::EphemeralVertMap<'_, Option<Self::Edge>>,
+		),
+		Cycle<Self>,
+	>
+	where
+		D: Clone + Ord + Add<C, Output = D>,
+	{
+		let order = self.topological_sort()?;
+		let mut distances = self.ephemeral_vert_map(zero);
+		let mut preds = self.ephemeral_vert_map(None);
+		for v in order {
+			let d = distances.get(v).borrow().clone();
+			for e in self.out_edges(v) {
+				let u = self.head(e);
+				let d_u = d.clone() + costs.get(e).borrow().clone();
+				if d_u > *distances.get(u).borrow() {
+					*distances.get_mut(u) = d_u;
+					*preds.get_mut(u) = Some(e);
+				}
+			}
+		}
+		Ok((distances, preds))
+	}
+}
+
+/// Represents a directed graph in which the out-degree of vertices is known.
+pub trait ExactOutDegreeDigraph: OutGraph {
+	/// Returns the out-degree of a vertex, that is, the number of
+	/// out-adjacencies.
+	fn out_degree(&self, v: impl Borrow<Self::Vert>) -> usize;
+
+	/// Returns a map from vertices to their PageRank after a fixed number of
+	/// iterations, starting from the uniform distribution. At each step, a
+	/// vertex passes `damping` of its rank evenly across its out-adjacencies, or
+	/// to every vertex if it has none, and the remainder to every vertex. Ranks
+	/// sum to one.
+	fn pagerank(&self, damping: f64, iterations: usize) -> Self::EphemeralVertMap<'_, f64> {
+		centrality::pagerank(self, damping, |i, _| i >= iterations)
+	}
+
+	/// Like `pagerank`, but iterates until the L1 distance between successive
+	/// ranks is less than `epsilon`. Rounding may prevent the distance from ever
+	/// falling below a tiny `epsilon`, in which case this does not return. Use
+	/// `pagerank_until_or_after` to also bound the number of iterations.
+	///
+	/// # Panics
+	/// Panics if `epsilon` is not positive.
+	fn pagerank_until(&self, damping: f64, epsilon: f64) -> Self::EphemeralVertMap<'_, f64> {
+		assert!(epsilon > 0.0, "epsilon must be positive");
+		centrality::pagerank(self, damping, |_, delta| delta < epsilon)
+	}
+
+	/// Like `pagerank_until`, but returns the ranks after `max_iterations` if
+	/// the distance has not fallen below `epsilon` by then.
+	///
+	/// # Panics
+	/// Panics if `epsilon` is not positive.
+	fn pagerank_until_or_after(
+		&self,
+		damping: f64,
+		epsilon: f64,
+		max_iterations: usize,
+	) -> Self::EphemeralVertMap<'_, f64> {
+		assert!(epsilon > 0.0, "epsilon must be positive");
+		centrality::pagerank(self, damping, |i, delta| {
+			delta < epsilon || i >= max_iterations
+		})
+	}
+}
+impl<G: OutGraph> ExactOutDegreeDigraph for G
+where
+	for<'a> G::OutEdges<'a>: ExactSizeIterator,
+{
+	fn out_degree(&self, v: impl Borrow<Self::Vert>) -> usize {
+		self.out_edges(v).len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{model::test_graph::*, DenseOutAdjacencyList, InsertGraph};
+	use proptest::{collection::vec, proptest};
+	use std::collections::HashMap;
+
+	#[derive(Debug, Clone, Copy)]
+	struct TestCost<C, E>(C, E);
+
+	#[derive(Debug, Clone, Copy)]
+	struct TestDistance<C, E> {
+		cost: C,
+		pred: Option<E>,
+	}
+
+	impl<C: PartialEq, E> PartialEq for TestDistance<C, E> {
+		fn eq(&self, other: &Self) -> bool {
+			self.cost.eq(&other.cost)
+		}
+	}
+
+	impl<C: Eq, E> Eq for TestDistance<C, E> {}
+
+	impl<C, E> TestDistance<C, E> {
+		fn new(cost: C) -> Self {
+			TestDistance { cost, pred: None }
+		}
+	}
+
+	impl<C: PartialOrd, E> PartialOrd for TestDistance<C, E> {
+		fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+			self.cost.partial_cmp(&other.cost)
+		}
+	}
+
+	impl<C: Ord, E> Ord for TestDistance<C, E> {
+		fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+			self.cost.cmp(&other.cost)
+		}
+	}
+
+	impl<C: Add<Output = C>, E> Add<TestCost<C, E>> for TestDistance<C, E> {
+		type Output = Self;
+		fn add(self, rhs: TestCost<C, E>) -> Self::Output {
+			TestDistance {
+				cost: self.cost + rhs.0,
+				pred: Some(rhs.1),
+			}
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn dijkstra_paths(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			for source in g.verts() {
+				let expected = g.dijkstra(&costs, source, 0);
+				let (distances, preds) = g.dijkstra_paths(&costs, source, 0);
+				assert!(preds.get(source).is_none());
+				for v in g.verts() {
+					assert_eq!(distances.get(v), expected.get(v));
+					let distance = match *distances.get(v) {
+						Some(distance) => distance,
+						None => continue,
+					};
+					// Following the edges backward reaches the source along a shortest path.
+					let path = g.reconstruct_path(&preds, v);
+					let mut u = source;
+					for e in path.iter() {
+						assert_eq!(g.tail(e), u);
+						u = g.head(e);
+					}
+					assert_eq!(u, v);
+					assert_eq!(path.iter().map(|e| *costs.get(*e)).sum::<usize>(), distance);
+				}
+			}
+		}
+
+		#[test]
+		fn second_shortest(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			for source in g.verts().take(10) {
+				let first = g.dijkstra(&costs, source, 0);
+				let second = g.second_shortest(&costs, source, 0);
+				// The two cheapest walks to each vertex extend one of the two cheapest
+				// walks to a predecessor by a single edge, unless they are empty.
+				let mut candidates = g.ephemeral_vert_map(Vec::new());
+				candidates.get_mut(source).push(0);
+				for e in g.edges() {
+					let (tail, head) = g.endpoints(e);
+					for d in [*first.get(tail), *second.get(tail)].iter().flatten() {
+						candidates.get_mut(head).push(d + costs.get(e));
+					}
+				}
+				for v in g.verts() {
+					let mut candidates = candidates.get(v).clone();
+					candidates.sort_unstable();
+					assert_eq!(*first.get(v), candidates.first().copied());
+					assert_eq!(*second.get(v), candidates.get(1).copied());
+					if let Some(d) = *second.get(v) {
+						assert!(first.get(v).unwrap() <= d);
+					}
+				}
+			}
+		}
+
+		#[test]
+		fn k_shortest_paths(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			let (source, target) = match (g.verts().next(), g.verts().next_back()) {
+				(Some(source), Some(target)) => (source, target),
+				_ => return Ok(()),
+			};
+			let paths = g.k_shortest_paths(&costs, source, target, 4, 0);
+			// The first path is the one found by Dijkstra's algorithm.
+			let (distances, preds) = g.dijkstra_paths(&costs, source, 0);
+			match *distances.get(target) {
+				Some(distance) => assert_eq!(paths[0], (distance, g.reconstruct_path(&preds, target))),
+				None => assert!(paths.is_empty()),
+			}
+			for (i, (cost, path)) in paths.iter().enumerate() {
+				// Each path is distinct, loopless, and no cheaper than the last.
+				assert!(!paths[..i].iter().any(|(_, other)| other == path));
+				if i > 0 {
+					assert!(paths[i - 1].0 <= *cost);
+				}
+				let mut visited = HashSet::new();
+				let mut v = source;
+				assert!(visited.insert(v));
+				for e in path {
+					assert_eq!(g.tail(e), v);
+					v = g.head(e);
+					assert!(visited.insert(v));
+				}
+				assert_eq!(v, target);
+				assert_eq!(path.iter().map(|e| *costs.get(*e)).sum::<usize>(), *cost);
+			}
+		}
+
+		#[test]
+		fn find_cycle(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			match g.find_cycle() {
+				Some(cycle) => {
+					assert!(!g.is_acyclic());
+					let first = cycle[0];
+					let last = cycle[cycle.len() - 1];
+					assert_eq!(g.head(last), g.tail(first));
+					for (d, e) in cycle.iter().zip(&cycle[1..]) {
+						assert_eq!(g.head(d), g.tail(e));
+					}
+				}
+				None => assert!(g.is_acyclic()),
+			}
+		}
+
+		#[test]
+		fn feedback_arc_set(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let feedback: HashSet<_> = g.feedback_arc_set().into_iter().collect();
+			let filtered = Filtered::new(&g, |_| true, |e| !feedback.contains(&e));
+			assert!(filtered.is_acyclic());
+		}
+
+		#[test]
+		fn feedback_arc_set_acyclic(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g.acyclic());
+			assert!(g.feedback_arc_set().is_empty());
+		}
+
+		#[test]
+		fn find_cycle_acyclic(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g.acyclic());
+			assert_eq!(g.find_cycle(), None);
+		}
+
+		#[test]
+		fn dfs_times(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let (discovered, finished) = g.dfs_times();
+			let interval = |v| (*discovered.get(v), *finished.get(v));
+			let mut times = HashSet::new();
+			for v in g.verts() {
+				let (d, f) = interval(v);
+				assert!(d < f);
+				assert!(times.insert(d) && times.insert(f));
+			}
+			// Intervals are nested or disjoint.
+			for u in g.verts() {
+				for v in g.verts() {
+					let ((du, fu), (dv, fv)) = (interval(u), interval(v));
+					assert!(fu < dv || fv < du || (du <= dv && fv <= fu) || (dv <= du && fu <= fv));
+				}
+			}
+			let tree: HashSet<_> = g
+				.depth_first_out()
+				.filter_map(|event| match event {
+					DepthFirstEvent::OpenEdge(e) => Some(e),
+					_ => None,
+				})
+				.collect();
+			for e in g.edges() {
+				let ((dt, ft), (dh, fh)) = (interval(g.tail(e)), interval(g.head(e)));
+				if tree.contains(&e) || dt < dh {
+					// Tree and forward edges lead to descendants.
+					assert!(dt < dh && fh < ft);
+				} else {
+					// Back edges lead to ancestors, and cross edges to vertices
+					// finished before the tail was discovered.
+					assert!((dh <= dt && ft <= fh) || fh < dt);
+				}
+			}
+		}
+
+		#[test]
+		fn eulerian_circuit(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			if let Some(circuit) = g.eulerian_circuit() {
+				assert_eq!(circuit.iter().copied().collect::<HashSet<_>>(), g.edges().collect::<HashSet<_>>());
+				assert_eq!(circuit.len(), g.edges().count());
+				if let (Some(first), Some(last)) = (circuit.first(), circuit.last()) {
+					assert_eq!(g.head(last), g.tail(first));
+				}
+				for (d, e) in circuit.iter().zip(circuit.iter().skip(1)) {
+					assert_eq!(g.head(d), g.tail(e));
 				}
 			}
 		}
-		distances
-	}
-}
 
-/// Represents a directed graph in which the out-degree of vertices is known.
-pub trait ExactOutDegreeDigraph: OutGraph {
-	/// Returns the out-degree of a vertex, that is, the number of
-	/// out-adjacencies.
-	fn out_degree(&self, v: impl Borrow<Self::Vert>) -> usize;
-}
-impl<G: OutGraph> ExactOutDegreeDigraph for G
-where
-	for<'a> G::OutEdges<'a>: ExactSizeIterator,
-{
-	fn out_degree(&self, v: impl Borrow<Self::Vert>) -> usize {
-		self.out_edges(v).len()
-	}
-}
+		#[test]
+		fn widest_path(order in 1usize..7, edges in vec((0usize..7, 0usize..7, 0u32..10), 0..16)) {
+			let edges: Vec<_> = edges.into_iter().filter(|(tail, head, _)| *tail < order && *head < order).collect();
+			let g = DenseOutAdjacencyList::from_edges(order, edges.iter().map(|(tail, head, _)| (*tail, *head)));
+			let mut capacities = g.ephemeral_edge_map(0);
+			for (e, (_, _, capacity)) in g.edges().zip(&edges) {
+				*capacities.get_mut(e) = *capacity;
+			}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate::{model::test_graph::*, DenseOutAdjacencyList};
-	use proptest::proptest;
+			/// Records the widest bottleneck of every simple path extending one.
+			fn enumerate<G: OutGraph>(
+				g: &G,
+				capacities: &impl Map<G::Edge, Value = u32>,
+				v: G::Vert,
+				width: u32,
+				visited: &mut HashSet<G::Vert>,
+				widths: &mut HashMap<G::Vert, u32>,
+			) {
+				let best = widths.entry(v).or_insert(width);
+				*best = (*best).max(width);
+				for e in g.out_edges(v) {
+					let u = g.head(e);
+					if visited.insert(u) {
+						let width = width.min(*capacities.get(e).borrow());
+						enumerate(g, capacities, u, width, visited, widths);
+						visited.remove(&u);
+					}
+				}
+			}
 
-	#[derive(Debug, Clone, Copy)]
-	struct TestCost<C, E>(C, E);
+			for source in g.verts() {
+				let mut expected = HashMap::new();
+				let mut visited = HashSet::new();
+				visited.insert(source);
+				enumerate(&g, &capacities, source, u32::MAX, &mut visited, &mut expected);
+				let widths = g.widest_path(&capacities, source, u32::MAX);
+				for v in g.verts() {
+					assert_eq!(*widths.get(v), expected.get(&v).copied());
+				}
+			}
+		}
 
-	#[derive(Debug, Clone, Copy)]
-	struct TestDistance<C, E> {
-		cost: C,
-		pred: Option<E>,
-	}
+		#[test]
+		fn eccentricities(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let eccentricities = g.eccentricities();
+			for v in g.verts() {
+				let distances = g.bfs_distances(v);
+				let eccentricity = *eccentricities.get(v);
+				assert!(g.verts().all(|u| !matches!(*distances.get(u), Some(d) if d > eccentricity)));
+				assert!(g.verts().any(|u| *distances.get(u) == Some(eccentricity)));
+			}
+			assert_eq!(g.diameter(), g.verts().map(|v| *eccentricities.get(v)).max());
+		}
 
-	impl<C: PartialEq, E> PartialEq for TestDistance<C, E> {
-		fn eq(&self, other: &Self) -> bool {
-			self.cost.eq(&other.cost)
+		#[test]
+		fn diameter_path(order in 1usize..50) {
+			let g = DenseOutAdjacencyList::from_edges(order, (1..order).map(|i| (i - 1, i)));
+			assert_eq!(g.diameter(), Some(order as u32 - 1));
 		}
-	}
 
-	impl<C: Eq, E> Eq for TestDistance<C, E> {}
+		#[test]
+		fn radius_center(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let eccentricities = g.eccentricities();
+			let radius = g.radius();
+			assert_eq!(radius, g.verts().map(|v| *eccentricities.get(v)).min());
+			let center = g.center();
+			assert_eq!(center.is_empty(), radius.is_none());
+			for v in g.verts() {
+				assert_eq!(center.contains(&v), Some(*eccentricities.get(v)) == radius);
+			}
+		}
 
-	impl<C, E> TestDistance<C, E> {
-		fn new(cost: C) -> Self {
-			TestDistance { cost, pred: None }
+		#[test]
+		fn center_path(order in 1usize..50) {
+			// Only the last vertex reaches nothing further.
+			let g = DenseOutAdjacencyList::from_edges(order, (1..order).map(|i| (i - 1, i)));
+			let verts: Vec<_> = g.verts().collect();
+			assert_eq!(g.radius(), Some(0));
+			assert_eq!(g.center(), vec![verts[order - 1]]);
 		}
-	}
 
-	impl<C: PartialOrd, E> PartialOrd for TestDistance<C, E> {
-		fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-			self.cost.partial_cmp(&other.cost)
+		#[test]
+		fn center_cycle(order in 1usize..50) {
+			let g = DenseOutAdjacencyList::from_edges(order, (0..order).map(|i| (i, (i + 1) % order)));
+			assert_eq!(g.radius(), Some(order as u32 - 1));
+			assert_eq!(g.center(), g.verts().collect::<Vec<_>>());
 		}
-	}
 
-	impl<C: Ord, E> Ord for TestDistance<C, E> {
-		fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-			self.cost.cmp(&other.cost)
+		#[test]
+		fn girth(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let girth = g.girth();
+			assert_eq!(girth.is_none(), g.is_acyclic());
+			// The shortest cycle through each vertex closes a shortest path from it.
+			let mut expected = None;
+			for v in g.verts() {
+				let distances = g.bfs_distances(v);
+				for e in g.edges() {
+					if let (Some(d), true) = (*distances.get(g.tail(e)), g.head(e) == v) {
+						expected = Some(expected.map_or(d + 1, |expected: u32| expected.min(d + 1)));
+					}
+				}
+			}
+			assert_eq!(girth, expected);
 		}
-	}
 
-	impl<C: Add<Output = C>, E> Add<TestCost<C, E>> for TestDistance<C, E> {
-		type Output = Self;
-		fn add(self, rhs: TestCost<C, E>) -> Self::Output {
-			TestDistance {
-				cost: self.cost + rhs.0,
-				pred: Some(rhs.1),
+		#[cfg(feature = "rayon")]
+		#[test]
+		fn par_bfs_distances_from(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let sources: Vec<_> = g.verts().collect();
+			let results = g.par_bfs_distances_from(&sources);
+			assert_eq!(results.len(), sources.len());
+			for ((source, distances), expected_source) in results.iter().zip(sources.iter()) {
+				assert_eq!(source, expected_source);
+				let expected = g.bfs_distances(*source);
+				for v in g.verts() {
+					assert_eq!(distances.get(v), expected.get(v));
+				}
+			}
+		}
+
+		#[test]
+		fn line_graph(g: TestGraph) {
+			use crate::ExactSizeDigraph;
+			let g = DenseOutAdjacencyList::from(&g);
+			let (line_graph, verts) = g.line_graph();
+			assert_eq!(line_graph.verts().count(), g.size());
+			let mut size = 0;
+			for d in g.edges() {
+				for e in g.edges() {
+					let adjacent = g.head(d) == g.tail(e);
+					let (d, e) = (verts.get(d).unwrap(), verts.get(e).unwrap());
+					assert_eq!(line_graph.out_edges(d).filter(|f| line_graph.head(f) == e).count(), adjacent as usize);
+					size += adjacent as usize;
+				}
+			}
+			assert_eq!(line_graph.edges().count(), size);
+		}
+
+		#[test]
+		fn bfs_forest(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let (forest, parents) = g.bfs_forest();
+			assert!(forest.is_acyclic());
+			let roots = g.verts().filter(|v| parents.get(*v).is_none()).count();
+			assert_eq!(forest.verts().count(), g.verts().count());
+			assert_eq!(forest.edges().count(), g.verts().count() - roots);
+			// Parents are adjacent and describe the same forest.
+			let mut from_parents = DenseOutAdjacencyList::new();
+			let mut verts = g.ephemeral_vert_map(None);
+			for v in g.verts() {
+				*verts.get_mut(v) = Some(from_parents.insert_vert());
+			}
+			for v in g.verts() {
+				if let Some(parent) = *parents.get(v) {
+					assert!(g.has_edge(parent, v));
+					from_parents.insert_edge(verts.get(parent).unwrap(), verts.get(v).unwrap());
+				}
+			}
+			assert!(forest.is_isomorphic(&from_parents));
+			// Each tree contains the vertices reachable from its root which are not
+			// in an earlier tree.
+			let mut trees = g.ephemeral_vert_map(None);
+			for root in g.verts().filter(|v| parents.get(*v).is_none()) {
+				let reachable = g.reachable_from(root);
+				for v in g.verts() {
+					if *reachable.get(v) && trees.get(v).is_none() {
+						*trees.get_mut(v) = Some(root);
+					}
+				}
+			}
+			for v in g.verts() {
+				if let Some(parent) = *parents.get(v) {
+					assert_eq!(*trees.get(v), *trees.get(parent));
+				}
+			}
+		}
+
+		#[test]
+		fn induced_subgraph(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let (all, _) = g.induced_subgraph(&|_| true);
+			assert!(g.is_isomorphic(&all));
+			// Greedily select an independent set.
+			let mut selected = HashSet::new();
+			for v in g.verts() {
+				let independent = g.edges().all(|e| {
+					let (tail, head) = g.endpoints(e);
+					!(tail == v && (head == v || selected.contains(&head))
+						|| head == v && selected.contains(&tail))
+				});
+				if independent {
+					selected.insert(v);
+				}
+			}
+			let (independent, verts) = g.induced_subgraph(&|v| selected.contains(&v));
+			assert_eq!(independent.verts().count(), selected.len());
+			assert_eq!(independent.edges().count(), 0);
+			for v in g.verts() {
+				assert_eq!(verts.get(v).is_some(), selected.contains(&v));
+			}
+		}
+
+		#[test]
+		fn longest_path(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g.acyclic());
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c;
+			}
+			let (distances, preds) = g.longest_path_with_preds(&costs, 0).unwrap();
+			let mut expected = g.ephemeral_vert_map(0);
+			for e in g.edges() {
+				let d = *distances.get(g.tail(e)) + *costs.get(e);
+				let d_head = expected.get_mut(g.head(e));
+				*d_head = d.max(*d_head);
+			}
+			for v in g.verts() {
+				assert_eq!(distances.get(v), expected.get(v));
+				let path = g.reconstruct_path(&preds, v);
+				assert_eq!(path.iter().map(|e| *costs.get(*e)).sum::<usize>(), *distances.get(v));
+			}
+		}
+
+		#[test]
+		fn longest_path_cycle(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			assert_eq!(g.longest_path(&|_| 1, 0).is_ok(), g.is_acyclic());
+		}
+
+		#[test]
+		fn has_edge(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let endpoints: HashSet<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			for u in g.verts() {
+				for v in g.verts() {
+					assert_eq!(g.has_edge(u, v), endpoints.contains(&(u, v)));
+					if let Some(e) = g.find_edge(u, v) {
+						assert_eq!(g.endpoints(e), (u, v));
+					}
+				}
+			}
+		}
+
+		#[test]
+		fn reachable_from(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			// Compute the transitive closure by repeatedly composing with the edges.
+			let mut closure: HashSet<_> = g.edges().map(|e| g.endpoints(e)).collect();
+			loop {
+				let extended: Vec<_> = closure
+					.iter()
+					.flat_map(|&(u, v)| g.successors(v).map(move |w| (u, w)))
+					.filter(|pair| !closure.contains(pair))
+					.collect();
+				if extended.is_empty() {
+					break;
+				}
+				closure.extend(extended);
+			}
+			for u in g.verts() {
+				let reachable = g.reachable_from(u);
+				for v in g.verts() {
+					assert_eq!(*reachable.get(v), u == v || closure.contains(&(u, v)));
+				}
+			}
+		}
+
+		#[test]
+		fn successors(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			for v in g.verts() {
+				let mut successors: Vec<_> = g.successors(v).collect();
+				let mut heads: Vec<_> = g.out_edges(v).map(|e| g.head(e)).collect();
+				successors.sort();
+				heads.sort();
+				assert_eq!(successors, heads);
+				assert_eq!(g.successors_unique(v), heads.into_iter().collect());
+			}
+		}
+
+		#[test]
+		fn dijkstra_buckets(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c % 8;
+			}
+			for source in g.verts() {
+				let expected = g.dijkstra(&costs, source, 0);
+				let actual = g.dijkstra_buckets(&costs, source, 7);
+				for v in g.verts() {
+					assert_eq!(actual.get(v), expected.get(v));
+				}
+			}
+		}
+
+		#[test]
+		fn zero_one_bfs(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut weights = g.ephemeral_edge_map(false);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				// Favor zero weights to produce long zero-weight chains.
+				*weights.get_mut(e) = c % 3 == 0;
+			}
+			for source in g.verts() {
+				let expected = g.dijkstra(&|e| *weights.get(e) as u32, source, 0);
+				let actual = g.zero_one_bfs(&weights, source);
+				for v in g.verts() {
+					assert_eq!(actual.get(v), expected.get(v));
+				}
+			}
+		}
+
+		#[test]
+		fn bfs_paths(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			for source in g.verts() {
+				let expected = g.dijkstra(&|_| 1, source, 0);
+				let distances = g.bfs_distances(source);
+				let (path_distances, preds) = g.bfs_paths(source);
+				assert_eq!(*distances.get(source), Some(0));
+				for v in g.verts() {
+					assert_eq!(distances.get(v), expected.get(v));
+					assert_eq!(path_distances.get(v), expected.get(v));
+					let path = g.reconstruct_path(&preds, v);
+					if let Some(d) = *distances.get(v) {
+						assert_eq!(path.len(), d as usize);
+						if let Some(e) = path.first() {
+							assert_eq!(g.tail(e), source);
+						}
+					} else {
+						assert!(path.is_empty());
+					}
+				}
+			}
+		}
+
+		#[test]
+		fn is_acyclic(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			assert_eq!(g.is_acyclic(), g.topological_sort().is_ok());
+		}
+
+		#[test]
+		fn is_acyclic_acyclic(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g.acyclic());
+			assert!(g.is_acyclic());
+		}
+
+		#[test]
+		fn find_negative_cycle(g: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g);
+			let mut costs = g.ephemeral_edge_map(0i64);
+			let mut c = 0;
+			for e in g.edges() {
+				c = (c + 43) % 101;
+				*costs.get_mut(e) = c - 10;
+			}
+			// Compare against Floyd-Warshall, clamping to avoid overflow.
+			let verts: Vec<_> = g.verts().collect();
+			let index = |v| verts.iter().position(|u| *u == v).unwrap();
+			let mut distances = vec![vec![None; verts.len()]; verts.len()];
+			for e in g.edges() {
+				let (t, h) = (index(g.tail(e)), index(g.head(e)));
+				let d: &mut Option<i64> = &mut distances[t][h];
+				*d = Some(d.map_or(*costs.get(e), |d| d.min(*costs.get(e))));
+			}
+			for k in 0..verts.len() {
+				for i in 0..verts.len() {
+					for j in 0..verts.len() {
+						if let (Some(a), Some(b)) = (distances[i][k], distances[k][j]) {
+							let through = (a + b).max(-1 << 40);
+							if !matches!(distances[i][j], Some(d) if d <= through) {
+								distances[i][j] = Some(through);
+							}
+						}
+					}
+				}
+			}
+			let negative = (0..verts.len()).any(|i| matches!(distances[i][i], Some(d) if d < 0));
+			match g.find_negative_cycle(&costs, 0) {
+				Some(cycle) => {
+					assert!(negative);
+					for (i, e) in cycle.iter().enumerate() {
+						assert_eq!(g.head(e), g.tail(cycle[(i + 1) % cycle.len()]));
+					}
+					assert!(cycle.iter().map(|e| *costs.get(*e)).sum::<i64>() < 0);
+				}
+				None => assert!(!negative),
 			}
 		}
-	}
 
-	proptest! {
 		#[test]
 		fn dijkstra(g: TestGraph) {
 			let g = DenseOutAdjacencyList::from(&g);
@@ -156,4 +2284,111 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn find_cycle_self_loop() {
+		let mut g = DenseOutAdjacencyList::new();
+		let u = g.insert_vert();
+		let v = g.insert_vert();
+		g.insert_edge(u, v);
+		let e = g.insert_edge(v, v);
+		assert_eq!(g.find_cycle(), Some(vec![e]));
+	}
+
+	#[test]
+	fn feedback_arc_set_many_cycles() {
+		// Each two-cycle needs a bucketed choice, which would take quadratic time
+		// if every choice scanned the remaining vertices.
+		let cycles = 100_000;
+		let edges = (0..cycles).flat_map(|i| vec![(2 * i, 2 * i + 1), (2 * i + 1, 2 * i)]);
+		let g = DenseOutAdjacencyList::from_edges(2 * cycles, edges);
+		assert_eq!(g.feedback_arc_set().len(), cycles);
+	}
+
+	#[test]
+	fn eulerian_circuit_known() {
+		// Two triangles sharing a vertex form an Eulerian graph.
+		let g =
+			DenseOutAdjacencyList::from_edges(5, vec![(0, 1), (1, 2), (2, 0), (0, 3), (3, 4), (4, 0)]);
+		let circuit = g.eulerian_circuit().unwrap();
+		assert_eq!(circuit.len(), 6);
+		assert_eq!(g.head(circuit[5]), g.tail(circuit[0]));
+		for (d, e) in circuit.iter().zip(&circuit[1..]) {
+			assert_eq!(g.head(d), g.tail(e));
+		}
+
+		// Balanced but disconnected.
+		let g = DenseOutAdjacencyList::from_edges(4, vec![(0, 1), (1, 0), (2, 3), (3, 2)]);
+		assert_eq!(g.eulerian_circuit(), None);
+
+		// Connected but unbalanced.
+		let g = DenseOutAdjacencyList::from_edges(3, vec![(0, 1), (1, 2), (0, 2)]);
+		assert_eq!(g.eulerian_circuit(), None);
+	}
+
+	#[test]
+	fn count_paths_grid() {
+		// Paths through a grid moving only right or down number `C(rows + columns - 2, rows - 1)`.
+		let (rows, columns) = (5, 7);
+		let index = |row, column| row * columns + column;
+		let mut edges = Vec::new();
+		for row in 0..rows {
+			for column in 0..columns {
+				if column + 1 < columns {
+					edges.push((index(row, column), index(row, column + 1)));
+				}
+				if row + 1 < rows {
+					edges.push((index(row, column), index(row + 1, column)));
+				}
+			}
+		}
+		let g = DenseOutAdjacencyList::from_edges(rows * columns, edges);
+		let verts: Vec<_> = g.verts().collect();
+		let (first, last) = (verts[0], verts[rows * columns - 1]);
+		assert_eq!(g.count_paths(first, last), Ok(210));
+		assert_eq!(g.count_paths(last, first), Ok(0));
+		assert_eq!(g.count_paths(last, last), Ok(1));
+
+		let mut g = g;
+		g.insert_edge(last, first);
+		assert!(g.count_paths(first, last).is_err());
+	}
+
+	#[test]
+	fn line_graph_self_loop() {
+		let mut g = DenseOutAdjacencyList::new();
+		let v = g.insert_vert();
+		let e = g.insert_edge(v, v);
+		let (line_graph, verts) = g.line_graph();
+		let u = verts.get(e).unwrap();
+		assert_eq!(line_graph.verts().collect::<Vec<_>>(), vec![u]);
+		assert_eq!(
+			line_graph
+				.edges()
+				.map(|e| line_graph.endpoints(e))
+				.collect::<Vec<_>>(),
+			vec![(u, u)]
+		);
+	}
+
+	#[test]
+	fn find_negative_cycle_planted() {
+		// A path leads into a cycle of cost -1, alongside a positive cycle.
+		let g = DenseOutAdjacencyList::from_edges(
+			6,
+			vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 2), (5, 0), (0, 5)],
+		);
+		let edges: Vec<_> = g.edges().collect();
+		let costs = |e| [5, -3, 2, -4, 1, 1, 0][edges.iter().position(|d| *d == e).unwrap()];
+		let cycle = g.find_negative_cycle(&costs, 0).unwrap();
+		let mut cycle_edges = cycle.clone();
+		cycle_edges.sort();
+		assert_eq!(cycle_edges, vec![edges[2], edges[3], edges[4]]);
+		for (i, e) in cycle.iter().enumerate() {
+			assert_eq!(g.head(e), g.tail(cycle[(i + 1) % cycle.len()]));
+		}
+		assert_eq!(cycle.iter().map(|e| costs(*e)).sum::<i32>(), -1);
+		let positive = |e| costs(e).abs();
+		assert_eq!(g.find_negative_cycle(&positive, 0), None);
+	}
 }
@@ -0,0 +1,208 @@
+use std::borrow::Borrow;
+
+use crate::{DepthFirstEvent, InGraph, Map, MapMut, OutGraph};
+
+/// Partitions the vertices of a graph into strongly connected components using
+/// an iterative formulation of Tarjan's algorithm. Components are returned in
+/// reverse topological order of the condensation.
+pub(crate) fn tarjan<G: OutGraph + ?Sized>(g: &G) -> Vec<Vec<G::Vert>> {
+	let mut indices = g.ephemeral_vert_map(None);
+	let mut lowlinks = g.ephemeral_vert_map(0usize);
+	let mut on_stack = g.ephemeral_vert_map(false);
+	let mut stack = Vec::new();
+	let mut call_stack = Vec::new();
+	let mut next_index = 0usize;
+	let mut components = Vec::new();
+
+	for root in g.verts() {
+		if indices.get(root).borrow().is_some() {
+			continue;
+		}
+		let mut visit = Some(root);
+		loop {
+			if let Some(v) = visit.take() {
+				*indices.get_mut(v) = Some(next_index);
+				*lowlinks.get_mut(v) = next_index;
+				next_index += 1;
+				stack.push(v);
+				*on_stack.get_mut(v) = true;
+				call_stack.push((v, g.out_edges(v)));
+			}
+			let (v, edges) = match call_stack.last_mut() {
+				Some(frame) => frame,
+				None => break,
+			};
+			let v = *v;
+			if let Some(e) = edges.next() {
+				let u = g.head(e);
+				let u_index = *indices.get(u).borrow();
+				match u_index {
+					None => visit = Some(u),
+					Some(u_index) => {
+						if *on_stack.get(u).borrow() {
+							let mut lowlink = lowlinks.get_mut(v);
+							*lowlink = (*lowlink).min(u_index);
+						}
+					}
+				}
+				continue;
+			}
+			call_stack.pop();
+			let v_lowlink = *lowlinks.get(v).borrow();
+			if let Some((parent, _)) = call_stack.last() {
+				let mut lowlink = lowlinks.get_mut(*parent);
+				*lowlink = (*lowlink).min(v_lowlink);
+			}
+			if Some(v_lowlink) == *indices.get(v).borrow() {
+				let mut component = Vec::new();
+				loop {
+					let u = stack.pop().expect("vertex on stack");
+					*on_stack.get_mut(u) = false;
+					component.push(u);
+					if u == v {
+						break;
+					}
+				}
+				components.push(component);
+			}
+		}
+	}
+	components
+}
+
+/// Partitions the vertices of a graph into strongly connected components using
+/// Kosaraju's algorithm. Components are returned in topological order of the
+/// condensation.
+pub(crate) fn kosaraju<G: OutGraph + InGraph + ?Sized>(g: &G) -> Vec<Vec<G::Vert>> {
+	// Order the vertices by the time at which a depth-first traversal finishes
+	// exploring them.
+	let (size_hint, _) = g.verts().size_hint();
+	let mut finished = Vec::with_capacity(size_hint);
+	let mut root = None;
+	for event in g.depth_first_out() {
+		match event {
+			DepthFirstEvent::StartTree(v) => root = Some(v),
+			DepthFirstEvent::CloseEdge(e) => finished.push(g.head(e)),
+			DepthFirstEvent::EndTree => finished.push(root.take().expect("tree started")),
+			_ => {}
+		}
+	}
+
+	// Each vertex reaches, over in-adjacencies, exactly its component among
+	// those not already assigned.
+	let mut assigned = g.ephemeral_vert_map(false);
+	let mut components = Vec::new();
+	for root in finished.into_iter().rev() {
+		if *assigned.get(root).borrow() {
+			continue;
+		}
+		*assigned.get_mut(root) = true;
+		let mut component = vec![root];
+		let mut stack = vec![root];
+		while let Some(v) = stack.pop() {
+			for e in g.in_edges(v) {
+				let u = g.tail(e);
+				if !*assigned.get(u).borrow() {
+					*assigned.get_mut(u) = true;
+					component.push(u);
+					stack.push(u);
+				}
+			}
+		}
+		components.push(component);
+	}
+	components
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use crate::model::test_graph::*;
+	use crate::{BiGraph, DenseBiAdjacencyList, DenseOutAdjacencyList, InsertGraph, OutGraph};
+	use proptest::proptest;
+	use std::collections::{BTreeSet, HashMap, HashSet};
+
+	/// Returns the set of vertices reachable from a source.
+	pub fn reachable<G: OutGraph>(g: &G, source: G::Vert) -> HashSet<G::Vert> {
+		let mut reached = HashSet::new();
+		reached.insert(source);
+		let mut stack = vec![source];
+		while let Some(v) = stack.pop() {
+			for e in g.out_edges(v) {
+				let u = g.head(e);
+				if reached.insert(u) {
+					stack.push(u);
+				}
+			}
+		}
+		reached
+	}
+
+	/// Asserts that a list of components is the strongly connected partition of
+	/// a graph in reverse topological order.
+	pub fn assert_strongly_connected_components<G: OutGraph>(g: &G, components: &[Vec<G::Vert>]) {
+		let mut component_ids = HashMap::new();
+		for (i, component) in components.iter().enumerate() {
+			assert!(!component.is_empty());
+			for v in component {
+				assert!(
+					component_ids.insert(*v, i).is_none(),
+					"vertex in one component"
+				);
+			}
+		}
+		assert_eq!(component_ids.len(), g.verts().count());
+		let reachable: HashMap<_, _> = g.verts().map(|v| (v, reachable(g, v))).collect();
+		for u in g.verts() {
+			for v in g.verts() {
+				let strongly_connected = reachable[&u].contains(&v) && reachable[&v].contains(&u);
+				assert_eq!(strongly_connected, component_ids[&u] == component_ids[&v]);
+			}
+		}
+		for e in g.edges() {
+			assert!(component_ids[&g.tail(e)] >= component_ids[&g.head(e)]);
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn strongly_connected_components(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			let components = g.strongly_connected_components();
+			assert_strongly_connected_components(&g, &components);
+		}
+
+		#[test]
+		fn kosaraju_matches_tarjan(g_test: TestGraph) {
+			let g = DenseBiAdjacencyList::from(&g_test);
+			let partition = |components: Vec<Vec<_>>| {
+				components
+					.into_iter()
+					.map(|component| component.into_iter().collect::<BTreeSet<_>>())
+					.collect::<BTreeSet<_>>()
+			};
+			assert_eq!(
+				partition(g.strongly_connected_components_kosaraju()),
+				partition(g.strongly_connected_components())
+			);
+		}
+	}
+
+	#[test]
+	fn self_loop() {
+		let mut g = DenseOutAdjacencyList::new();
+		let v = g.insert_vert();
+		g.insert_edge(v, v);
+		assert_eq!(g.strongly_connected_components(), vec![vec![v]]);
+	}
+
+	#[test]
+	fn disconnected() {
+		let mut g = DenseOutAdjacencyList::new();
+		for _ in 0..3 {
+			g.insert_vert();
+		}
+		let components = g.strongly_connected_components();
+		assert_eq!(components.len(), 3);
+		assert!(components.iter().all(|component| component.len() == 1));
+	}
+}
@@ -0,0 +1,175 @@
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+use crate::{Digraph, Map, MapMut, OutGraph};
+
+/// Error indicating that a graph contains a directed cycle.
+pub struct Cycle<G: Digraph + ?Sized> {
+	vert: G::Vert,
+}
+
+impl<G: Digraph + ?Sized> Cycle<G> {
+	pub(crate) fn new(vert: G::Vert) -> Self {
+		Cycle { vert }
+	}
+
+	/// Returns a vertex which lies on or is reachable from a cycle.
+	pub fn vert(&self) -> G::Vert {
+		self.vert
+	}
+}
+
+impl_vert_error!(Cycle);
+
+/// Returns the number of edges with each vertex as their head.
+pub(crate) fn in_degrees<G: OutGraph + ?Sized>(g: &G) -> G::EphemeralVertMap<'_, usize> {
+	let mut in_degrees = g.default_ephemeral_vert_map();
+	for v in g.verts() {
+		for e in g.out_edges(v) {
+			*in_degrees.get_mut(g.head(e)) += 1;
+		}
+	}
+	in_degrees
+}
+
+/// Orders the vertices of a graph using Kahn's algorithm.
+pub(crate) fn topological_sort<G: OutGraph + ?Sized>(g: &G) -> Result<Vec<G::Vert>, Cycle<G>> {
+	let mut in_degrees = in_degrees(g);
+	let mut queue: VecDeque<_> = g
+		.verts()
+		.filter(|v| *in_degrees.get(*v).borrow() == 0)
+		.collect();
+	let (size_hint, _) = g.verts().size_hint();
+	let mut order = Vec::with_capacity(size_hint);
+	while let Some(v) = queue.pop_front() {
+		order.push(v);
+		for e in g.out_edges(v) {
+			let u = g.head(e);
+			let mut in_degree = in_degrees.get_mut(u);
+			*in_degree -= 1;
+			if *in_degree == 0 {
+				queue.push_back(u);
+			}
+		}
+	}
+	// Any vertex with remaining in-edges must be on or reachable from a cycle.
+	match g.verts().find(|v| *in_degrees.get(*v).borrow() > 0) {
+		Some(v) => Err(Cycle::new(v)),
+		None => Ok(order),
+	}
+}
+
+/// Partitions the vertices of a graph into generations using Kahn's algorithm,
+/// grouping vertices by the round in which they are removed.
+pub(crate) fn topological_generations<G: OutGraph + ?Sized>(
+	g: &G,
+) -> Result<Vec<Vec<G::Vert>>, Cycle<G>> {
+	let mut in_degrees = in_degrees(g);
+	let mut generation: Vec<_> = g
+		.verts()
+		.filter(|v| *in_degrees.get(*v).borrow() == 0)
+		.collect();
+	let mut generations = Vec::new();
+	while !generation.is_empty() {
+		let mut next = Vec::new();
+		for v in generation.iter() {
+			for e in g.out_edges(v) {
+				let u = g.head(e);
+				let mut in_degree = in_degrees.get_mut(u);
+				*in_degree -= 1;
+				if *in_degree == 0 {
+					next.push(u);
+				}
+			}
+		}
+		generations.push(generation);
+		generation = next;
+	}
+	match g.verts().find(|v| *in_degrees.get(*v).borrow() > 0) {
+		Some(v) => Err(Cycle::new(v)),
+		None => Ok(generations),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::model::test_graph::*;
+	use crate::{DenseOutAdjacencyList, Digraph, InsertGraph, OutGraph};
+	use proptest::proptest;
+	use std::collections::HashMap;
+
+	proptest! {
+		#[test]
+		fn topological_sort_acyclic(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test.acyclic());
+			let order = g.topological_sort().unwrap();
+			assert_eq!(order.len(), g.verts().count());
+			let positions: HashMap<_, _> = order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+			assert_eq!(positions.len(), order.len());
+			for e in g.edges() {
+				assert!(positions[&g.tail(e)] < positions[&g.head(e)]);
+			}
+		}
+
+		#[test]
+		fn topological_generations(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test.acyclic());
+			let generations = g.topological_generations().unwrap();
+			let mut indices = HashMap::new();
+			for (i, generation) in generations.iter().enumerate() {
+				assert!(!generation.is_empty());
+				for v in generation {
+					assert!(indices.insert(*v, i).is_none());
+				}
+			}
+			assert_eq!(indices.len(), g.verts().count());
+			for e in g.edges() {
+				assert!(indices[&g.tail(e)] < indices[&g.head(e)]);
+			}
+			// Each vertex is in the generation after its latest predecessor.
+			let mut latest = HashMap::new();
+			for e in g.edges() {
+				let latest = latest.entry(g.head(e)).or_insert(0);
+				*latest = (*latest).max(indices[&g.tail(e)] + 1);
+			}
+			for v in g.verts() {
+				assert_eq!(indices[&v], latest.get(&v).copied().unwrap_or(0));
+			}
+		}
+
+		#[test]
+		fn topological_sort(g_test: TestGraph) {
+			let g = DenseOutAdjacencyList::from(&g_test);
+			if let Ok(order) = g.topological_sort() {
+				assert_eq!(order.len(), g.verts().count());
+				let positions: HashMap<_, _> = order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+				for e in g.edges() {
+					assert!(positions[&g.tail(e)] < positions[&g.head(e)]);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn topological_sort_cycle() {
+		let mut g = DenseOutAdjacencyList::new();
+		let u = g.insert_vert();
+		let v = g.insert_vert();
+		let w = g.insert_vert();
+		g.insert_edge(u, v);
+		g.insert_edge(v, w);
+		g.insert_edge(w, v);
+		let cycle = g.topological_sort().unwrap_err();
+		assert!(cycle.vert() == v || cycle.vert() == w);
+		let cycle = g.topological_generations().unwrap_err();
+		assert!(cycle.vert() == v || cycle.vert() == w);
+	}
+
+	#[test]
+	fn topological_sort_self_loop() {
+		let mut g = DenseOutAdjacencyList::new();
+		let v = g.insert_vert();
+		g.insert_edge(v, v);
+		assert_eq!(g.topological_sort().unwrap_err().vert(), v);
+	}
+}
@@ -0,0 +1,95 @@
+//! Module implementing a solver for boolean satisfiability problems in which
+//! every clause has two literals.
+
+use crate::{DenseOutAdjacencyList, Digraph, InsertGraph, Map, MapMut, OutGraph};
+
+/// Returns the vertex index of a literal in the implication graph. Variables
+/// are numbered from one, and negative literals are negations.
+fn literal_index(num_vars: usize, literal: i32) -> usize {
+	let var = literal.unsigned_abs() as usize;
+	assert!(var != 0 && var <= num_vars, "literal out of range");
+	2 * (var - 1) + (literal < 0) as usize
+}
+
+/// Returns an assignment of the variables `1..=num_vars` satisfying every
+/// clause, or `None` if there is none. Each clause `(a, b)` is the disjunction
+/// of two literals, where a positive literal `k` is the `k`th variable and `-k`
+/// is its negation. The solution is read off the strongly connected components
+/// of the implication graph, in which each clause `(a, b)` contributes edges
+/// from `-a` to `b` and from `-b` to `a`.
+///
+/// # Panics
+/// Panics if a literal is zero or its variable exceeds `num_vars`.
+///
+/// # Examples
+/// ```
+/// # use sif::two_sat::solve;
+/// assert_eq!(solve(2, &[(1, 2), (-1, 2), (-2, -1)]), Some(vec![false, true]));
+/// assert_eq!(solve(1, &[(1, 1), (-1, -1)]), None);
+/// ```
+pub fn solve(num_vars: usize, clauses: &[(i32, i32)]) -> Option<Vec<bool>> {
+	let implications = clauses.iter().flat_map(|&(a, b)| {
+		let (a, b) = (literal_index(num_vars, a), literal_index(num_vars, b));
+		// The negation of a literal differs only in the lowest bit.
+		vec![(a ^ 1, b), (b ^ 1, a)]
+	});
+	let g = DenseOutAdjacencyList::from_edges(2 * num_vars, implications);
+	let literals: Vec<_> = g.verts().collect();
+
+	// Tarjan's algorithm yields components in reverse topological order.
+	let mut component_ids = g.ephemeral_vert_map(0usize);
+	for (i, component) in g.strongly_connected_components().into_iter().enumerate() {
+		for v in component {
+			*component_ids.get_mut(v) = i;
+		}
+	}
+
+	(0..num_vars)
+		.map(|var| {
+			let positive = *component_ids.get(literals[2 * var]);
+			let negative = *component_ids.get(literals[2 * var + 1]);
+			// A literal is true if it follows its negation in topological order.
+			match positive.cmp(&negative) {
+				std::cmp::Ordering::Less => Some(true),
+				std::cmp::Ordering::Greater => Some(false),
+				std::cmp::Ordering::Equal => None,
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::{collection::vec, proptest};
+
+	fn satisfies(assignment: &[bool], clauses: &[(i32, i32)]) -> bool {
+		let value = |literal: i32| assignment[literal.unsigned_abs() as usize - 1] == (literal > 0);
+		clauses.iter().all(|&(a, b)| value(a) || value(b))
+	}
+
+	proptest! {
+		#[test]
+		fn solve_matches_brute_force(
+			num_vars in 1usize..7,
+			literals in vec(((0usize..7, proptest::bool::ANY), (0usize..7, proptest::bool::ANY)), 0..24),
+		) {
+			let literal = |(var, negated): (usize, bool)| {
+				let literal = (var % num_vars + 1) as i32;
+				if negated { -literal } else { literal }
+			};
+			let clauses: Vec<_> = literals.into_iter().map(|(a, b)| (literal(a), literal(b))).collect();
+			let satisfiable = (0..1u32 << num_vars).any(|bits| {
+				let assignment: Vec<_> = (0..num_vars).map(|i| bits & (1 << i) != 0).collect();
+				satisfies(&assignment, &clauses)
+			});
+			match solve(num_vars, &clauses) {
+				Some(assignment) => {
+					assert_eq!(assignment.len(), num_vars);
+					assert!(satisfies(&assignment, &clauses));
+				}
+				None => assert!(!satisfiable),
+			}
+		}
+	}
+}
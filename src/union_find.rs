@@ -0,0 +1,149 @@
+use crate::MapMut;
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+/// Disjoint-set forest over keys with union by rank and path halving.
+pub struct UnionFind<K, P, R> {
+	parents: P,
+	ranks: R,
+	_phantom_data: PhantomData<K>,
+}
+
+impl<K: Copy + Eq, P: MapMut<K, Value = Option<K>>, R: MapMut<K, Value = u8>> UnionFind<K, P, R> {
+	/// Constructs a new forest in which every key is in its own set. The maps
+	/// should initially map every key to `None` and `0`, respectively.
+	pub fn new(parents: P, ranks: R) -> Self {
+		UnionFind {
+			parents,
+			ranks,
+			_phantom_data: PhantomData,
+		}
+	}
+
+	/// Returns the representative of the set containing a key.
+	pub fn find(&mut self, mut k: K) -> K {
+		loop {
+			let parent = *self.parents.get(k).borrow();
+			let parent = match parent {
+				Some(parent) => parent,
+				None => return k,
+			};
+			let grandparent = *self.parents.get(parent).borrow();
+			let grandparent = match grandparent {
+				Some(grandparent) => grandparent,
+				None => return parent,
+			};
+			*self.parents.get_mut(k) = Some(grandparent);
+			k = grandparent;
+		}
+	}
+
+	/// Merges the sets containing two keys and returns whether they were
+	/// previously distinct.
+	pub fn union(&mut self, a: K, b: K) -> bool {
+		let a = self.find(a);
+		let b = self.find(b);
+		if a == b {
+			return false;
+		}
+		let a_rank = *self.ranks.get(a).borrow();
+		let b_rank = *self.ranks.get(b).borrow();
+		if a_rank < b_rank {
+			*self.parents.get_mut(a) = Some(b);
+		} else {
+			*self.parents.get_mut(b) = Some(a);
+			if a_rank == b_rank {
+				*self.ranks.get_mut(a) += 1;
+			}
+		}
+		true
+	}
+}
+
+/// Disjoint-set forest over the indices `0..len` which can be shared across
+/// threads. Roots are only ever linked beneath smaller indices, so concurrent
+/// unions cannot form cycles.
+#[cfg(feature = "rayon")]
+pub struct ConcurrentUnionFind {
+	parents: Vec<std::sync::atomic::AtomicUsize>,
+}
+
+#[cfg(feature = "rayon")]
+impl ConcurrentUnionFind {
+	/// Constructs a new forest in which every index is in its own set.
+	pub fn new(len: usize) -> Self {
+		ConcurrentUnionFind {
+			parents: (0..len).map(std::sync::atomic::AtomicUsize::new).collect(),
+		}
+	}
+
+	/// Returns the representative of the set containing an index.
+	pub fn find(&self, mut k: usize) -> usize {
+		use std::sync::atomic::Ordering;
+		loop {
+			let parent = self.parents[k].load(Ordering::Acquire);
+			if parent == k {
+				return k;
+			}
+			let grandparent = self.parents[parent].load(Ordering::Acquire);
+			// Path halving may fail if another thread changed the parent, which is
+			// harmless since parents only ever decrease toward the root.
+			let _ =
+				self.parents[k].compare_exchange(parent, grandparent, Ordering::AcqRel, Ordering::Acquire);
+			k = grandparent;
+		}
+	}
+
+	/// Merges the sets containing two indices.
+	pub fn union(&self, a: usize, b: usize) {
+		use std::sync::atomic::Ordering;
+		loop {
+			let a_root = self.find(a);
+			let b_root = self.find(b);
+			if a_root == b_root {
+				return;
+			}
+			let (low, high) = (a_root.min(b_root), a_root.max(b_root));
+			// Retry if `high` stopped being a root in the meantime.
+			if self.parents[high]
+				.compare_exchange(high, low, Ordering::AcqRel, Ordering::Acquire)
+				.is_ok()
+			{
+				return;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_graph::*;
+	use crate::Digraph;
+	use proptest::proptest;
+
+	proptest! {
+		#[test]
+		fn union_find(g: TestGraph) {
+			let mut sets = UnionFind::new(g.ephemeral_vert_map(None), g.ephemeral_vert_map(0));
+			// Naively track a label for each vertex.
+			let mut labels: Vec<_> = g.verts().collect();
+			for e in g.edges() {
+				let (tail, head) = g.endpoints(e);
+				let merged = sets.union(tail, head);
+				let (from, to) = (labels[tail], labels[head]);
+				assert_eq!(merged, from != to);
+				for label in labels.iter_mut() {
+					if *label == from {
+						*label = to;
+					}
+				}
+			}
+			for u in g.verts() {
+				for v in g.verts() {
+					assert_eq!(sets.find(u) == sets.find(v), labels[u] == labels[v]);
+				}
+			}
+		}
+	}
+}